@@ -0,0 +1,516 @@
+//! Derives [`clap`]'s `ClapApp` and `FromArgMatches` traits, so a command line interface can be
+//! declared as a struct (whose fields are the arguments) or an enum (whose variants are the
+//! subcommands) instead of being built by hand and read back out of the `ArgMatches`.
+//!
+//! # Fields
+//!
+//! The type of a field decides what kind of argument it is:
+//!
+//! * `bool`: a flag, `true` if it was present
+//! * `u64`: a flag which may be used multiple times, the number of occurrences
+//! * `Option<T>`: an optional value
+//! * `Vec<T>`: any number of values
+//! * any other `T`: a required value, unless it has a `default_value`
+//!
+//! Values are parsed with `T`'s `FromStr` impl through `Arg::value_parser`, so `T` must also be
+//! `Clone`, `Send`, `Sync` and `'static`, and its parse error `Display`. Fields with a value and
+//! neither a `short`, `long` nor `index` are positional arguments, in the order they are
+//! declared. Flags without any of them get a `long` named after the field.
+//!
+//! A field marked `#[clap(subcommand)]` holds the subcommand, its type must be an enum which
+//! derives `ClapApp` as well. Wrapping it in an `Option` makes the subcommand optional. Enums
+//! implement `FromSubcommand` too, so they can also be used with hand written code.
+//!
+//! # Attributes
+//!
+//! `#[clap(...)]` on a field configures its `Arg`:
+//!
+//! * `name = "..."`: the name of the argument, which defaults to the field's name
+//! * `short`, `long`: a short or long named after the field, `short = "s"` and `long = "..."`
+//!   set them explicitly
+//! * any other `key = value` calls `Arg::key(value)`, such as `default_value = "1"`,
+//!   `value_name = "FILE"` or `index = 1`, and any other `key` on its own calls `Arg::key(true)`,
+//!   such as `global` or `hidden`
+//!
+//! On the struct or enum, or on a variant, it configures the `App`:
+//!
+//! * `name = "..."`: the name of the app, which defaults to the crate's name, or for a variant,
+//!   to its name in kebab-case
+//! * `version` and `author` on their own use the crate's version and authors
+//! * `setting = "..."` and `global_setting = "..."` add an `AppSettings` by its name
+//! * any other `key = value` calls `App::key(value)`, such as `version = "1.0"`
+//!
+//! Doc comments become the `help` of an argument or the `about` of an app. If they have more
+//! than one paragraph, the first one is the `help` and all of them are the `long_help` (or
+//! `long_about`).
+//!
+//! # Examples
+//!
+//! ```rust
+//! #[macro_use]
+//! extern crate clap_derive;
+//! extern crate clap;
+//!
+//! use clap::ClapApp;
+//!
+//! /// Copies files around
+//! #[derive(ClapApp)]
+//! #[clap(name = "cp", version = "1.0")]
+//! struct Opts {
+//!     /// Prints more, can be used multiple times
+//!     #[clap(short)]
+//!     verbose: u64,
+//!     #[clap(subcommand)]
+//!     cmd: Command,
+//! }
+//!
+//! #[derive(ClapApp)]
+//! enum Command {
+//!     /// Copies a file
+//!     Copy {
+//!         /// Overwrites existing files
+//!         #[clap(short, long)]
+//!         force: bool,
+//!         from: String,
+//!         to: String,
+//!     },
+//!     /// Copies files matching a pattern
+//!     Glob(GlobOpts),
+//! }
+//!
+//! #[derive(ClapApp)]
+//! struct GlobOpts {
+//!     #[clap(long, default_value = "1")]
+//!     depth: usize,
+//!     patterns: Vec<String>,
+//! }
+//!
+//! # fn main() {
+//! let opts = Opts::parse_from(vec!["cp", "-vv", "glob", "--depth", "2", "*.rs", "*.md"]);
+//! assert_eq!(opts.verbose, 2);
+//! match opts.cmd {
+//!     Command::Glob(g) => {
+//!         assert_eq!(g.depth, 2);
+//!         assert_eq!(g.patterns, ["*.rs", "*.md"]);
+//!     }
+//!     Command::Copy { .. } => unreachable!(),
+//! }
+//! # }
+//! ```
+//! [`clap`]: https://docs.rs/clap/
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as Tokens;
+use syn::{Attribute, Data, DeriveInput, Fields, GenericArgument, Ident, Lit, LitStr, Meta,
+          NestedMeta, PathArguments, Type};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+
+/// Implements `clap::ClapApp` and `clap::FromArgMatches`, and for an enum `clap::FromSubcommand`
+/// as well, see the [crate docs](./index.html).
+#[proc_macro_derive(ClapApp, attributes(clap))]
+pub fn clap_app(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<Tokens> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics,
+                                           "#[derive(ClapApp)] doesn't support generics"));
+    }
+    let ty = &input.ident;
+    let (name, methods) = app_methods(&input.attrs)?;
+    let name = name.map_or_else(|| quote!(env!("CARGO_PKG_NAME")), |n| quote!(#n));
+    let (augment, from_impl, settings) = match input.data {
+        Data::Struct(ref s) => {
+            let (augment, inits) = fields(&s.fields)?;
+            let ctor = construct(quote!(#ty), &s.fields, &inits);
+            let from_impl = quote! {
+                impl ::clap::FromArgMatches for #ty {
+                    fn from_matches(matches: &::clap::ArgMatches) -> ::clap::Result<Self> {
+                        Ok(#ctor)
+                    }
+                }
+            };
+            (augment, from_impl, quote!())
+        }
+        Data::Enum(ref e) => {
+            let (augment, from_subcommand) = variants(ty, &e.variants)?;
+            let from_impl = quote! {
+                impl ::clap::FromSubcommand for #ty {
+                    fn from_subcommand(name: &str,
+                                       matches: &::clap::ArgMatches)
+                                       -> ::clap::Result<Option<Self>> {
+                        #from_subcommand
+                    }
+                }
+
+                impl ::clap::FromArgMatches for #ty {
+                    fn from_matches(matches: &::clap::ArgMatches) -> ::clap::Result<Self> {
+                        ::clap::from_subcommand_matches(matches)
+                    }
+                }
+            };
+            (augment,
+             from_impl,
+             quote!(.setting(::clap::AppSettings::SubcommandRequiredElseHelp)))
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(input,
+                                               "#[derive(ClapApp)] doesn't support unions"))
+        }
+    };
+
+    Ok(quote! {
+        impl ::clap::ClapApp for #ty {
+            fn clap<'a, 'b>() -> ::clap::App<'a, 'b> {
+                <#ty as ::clap::ClapApp>::augment_clap(::clap::App::new(#name)#methods#settings)
+            }
+
+            fn augment_clap<'a, 'b>(app: ::clap::App<'a, 'b>) -> ::clap::App<'a, 'b> {
+                #augment
+                app
+            }
+        }
+
+        #from_impl
+    })
+}
+
+// The statements adding each field's argument (or subcommands) to `app`, and the expressions
+// reading each field from `matches`
+fn fields(fields: &Fields) -> syn::Result<(Tokens, Vec<Tokens>)> {
+    let mut augment = quote!();
+    let mut inits = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let attrs = clap_attrs(&field.attrs)?;
+        let kind = Kind::of(&field.ty);
+        let init = if attrs.iter().any(|&(ref k, _)| k == "subcommand") {
+            let (ty, optional) = match kind {
+                Kind::Option(ty) => (ty, true),
+                _ => (&field.ty, false),
+            };
+            augment.extend(quote!(let app = <#ty as ::clap::ClapApp>::augment_clap(app);));
+            if optional {
+                quote! {
+                    match matches.subcommand_name() {
+                        Some(_) => Some(<#ty as ::clap::FromArgMatches>::from_matches(matches)?),
+                        None => None,
+                    }
+                }
+            } else {
+                augment.extend(quote! {
+                    let app = app.setting(::clap::AppSettings::SubcommandRequiredElseHelp);
+                });
+                quote!(<#ty as ::clap::FromArgMatches>::from_matches(matches)?)
+            }
+        } else {
+            let name = match field.ident {
+                Some(ref ident) => unraw(ident),
+                None => i.to_string(),
+            };
+            let name = attrs.iter()
+                .filter(|&&(ref k, _)| k == "name")
+                .filter_map(|&(_, ref v)| v.as_ref())
+                .last()
+                .map_or_else(|| Ok(name.clone()), lit_str)?;
+            let arg = arg(&name, &attrs, &field.attrs, &kind)?;
+            augment.extend(quote!(let app = app.arg(#arg);));
+            kind.read(&name)
+        };
+        inits.push(init);
+    }
+    Ok((augment, inits))
+}
+
+// Builds a value of `path` (a struct or variant) with `fields` set to `inits`
+fn construct(path: Tokens, fields: &Fields, inits: &[Tokens]) -> Tokens {
+    match *fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|f| &f.ident);
+            quote!(#path { #(#names: #inits),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#inits),*)),
+        Fields::Unit => path,
+    }
+}
+
+// The statements adding a subcommand for each variant to `app`, and the expression building the
+// variant of the subcommand `name` from its `matches`
+fn variants(ty: &Ident,
+            variants: &Punctuated<syn::Variant, Comma>)
+            -> syn::Result<(Tokens, Tokens)> {
+    let mut augment = quote!();
+    let mut arms = quote!();
+    for v in variants {
+        let ident = &v.ident;
+        let (name, methods) = app_methods(&v.attrs)?;
+        let name = name.map_or_else(|| kebab(&unraw(ident)), |n| n.value());
+        let sc = quote!(::clap::SubCommand::with_name(#name)#methods);
+        let (sc, variant) = match v.fields {
+            Fields::Unnamed(ref f) if f.unnamed.len() == 1 => {
+                let inner = &f.unnamed[0].ty;
+                (quote!(<#inner as ::clap::ClapApp>::augment_clap(#sc)),
+                 quote!(#ty::#ident(<#inner as ::clap::FromArgMatches>::from_matches(matches)?)))
+            }
+            _ => {
+                let (sc_augment, inits) = fields(&v.fields)?;
+                (quote!({ let app = #sc; #sc_augment app }),
+                 construct(quote!(#ty::#ident), &v.fields, &inits))
+            }
+        };
+        augment.extend(quote!(let app = app.subcommand(#sc);));
+        arms.extend(quote!(#name => #variant,));
+    }
+    let from_subcommand = quote! {
+        Ok(Some(match name {
+            #arms
+            _ => return Ok(None),
+        }))
+    };
+    Ok((augment, from_subcommand))
+}
+
+// The kind of argument a field is, by its type
+enum Kind<'a> {
+    Flag,
+    Count,
+    Option(&'a Type),
+    Vec(&'a Type),
+    Required(&'a Type),
+}
+
+impl<'a> Kind<'a> {
+    fn of(ty: &'a Type) -> Self {
+        if let Type::Path(ref p) = *ty {
+            if let Some(seg) = p.path.segments.last() {
+                let inner = match seg.arguments {
+                    PathArguments::AngleBracketed(ref a) if a.args.len() == 1 => {
+                        match a.args[0] {
+                            GenericArgument::Type(ref t) => Some(t),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                match (&*seg.ident.to_string(), inner) {
+                    ("bool", None) => return Kind::Flag,
+                    ("u64", None) => return Kind::Count,
+                    ("Option", Some(t)) => return Kind::Option(t),
+                    ("Vec", Some(t)) => return Kind::Vec(t),
+                    _ => (),
+                }
+            }
+        }
+        Kind::Required(ty)
+    }
+
+    fn takes_value(&self) -> bool {
+        match *self {
+            Kind::Flag | Kind::Count => false,
+            _ => true,
+        }
+    }
+
+    // The expression reading the argument `name` of this kind from `matches`
+    fn read(&self, name: &str) -> Tokens {
+        match *self {
+            Kind::Flag => quote!(matches.is_present(#name)),
+            Kind::Count => quote!(matches.occurrences_of(#name)),
+            Kind::Option(ty) => quote!(matches.get::<#ty>(#name).cloned()),
+            Kind::Vec(ty) => {
+                quote! {
+                    matches.get_all::<#ty>(#name)
+                        .map(|vals| vals.into_iter().cloned().collect())
+                        .unwrap_or_else(Vec::new)
+                }
+            }
+            Kind::Required(ty) => {
+                quote! {
+                    match matches.get::<#ty>(#name) {
+                        Some(val) => val.clone(),
+                        None => return Err(::clap::Error::argument_not_found_auto(#name)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Builds the `Arg` of a field
+fn arg(name: &str,
+       attrs: &[(Ident, Option<Lit>)],
+       all_attrs: &[Attribute],
+       kind: &Kind)
+       -> syn::Result<Tokens> {
+    let mut arg = quote!(::clap::Arg::with_name(#name));
+    let mut named = false;
+    for &(ref key, ref val) in attrs {
+        arg = match (&*key.to_string(), val) {
+            ("name", _) => continue,
+            ("short", &None) => {
+                let short = name.chars().next().unwrap().to_string();
+                quote!(#arg.short(#short))
+            }
+            ("long", &None) => {
+                let long = kebab(name);
+                quote!(#arg.long(#long))
+            }
+            (_, &Some(ref val)) => quote!(#arg.#key(#val)),
+            (_, &None) => quote!(#arg.#key(true)),
+        };
+        named |= key == "short" || key == "long" || key == "index";
+    }
+    let has = |k: &str| attrs.iter().any(|&(ref key, _)| key == k);
+    let (help, long_help) = doc(all_attrs);
+    if let (false, Some(help)) = (has("help"), help) {
+        arg = quote!(#arg.help(#help));
+    }
+    if let (false, Some(long_help)) = (has("long_help"), long_help) {
+        arg = quote!(#arg.long_help(#long_help));
+    }
+    if !named && !kind.takes_value() {
+        let long = kebab(name);
+        arg = quote!(#arg.long(#long));
+    }
+    Ok(match *kind {
+        Kind::Flag => arg,
+        Kind::Count => quote!(#arg.multiple(true)),
+        Kind::Option(ty) => quote!(#arg.value_parser(str::parse::<#ty>)),
+        Kind::Vec(ty) => quote!(#arg.multiple(true).value_parser(str::parse::<#ty>)),
+        Kind::Required(ty) => {
+            if has("default_value") || has("required") {
+                quote!(#arg.value_parser(str::parse::<#ty>))
+            } else {
+                quote!(#arg.required(true).value_parser(str::parse::<#ty>))
+            }
+        }
+    })
+}
+
+// The explicit name of an app and the builder calls for the rest of its attributes and its doc
+// comment
+fn app_methods(attrs: &[Attribute]) -> syn::Result<(Option<LitStr>, Tokens)> {
+    let mut name = None;
+    let mut methods = quote!();
+    let clap = clap_attrs(attrs)?;
+    for &(ref key, ref val) in &clap {
+        methods = match (&*key.to_string(), val) {
+            ("name", &Some(ref n)) => {
+                name = Some(LitStr::new(&lit_str(n)?, n.span()));
+                continue;
+            }
+            ("version", &None) => quote!(#methods.version(env!("CARGO_PKG_VERSION"))),
+            ("author", &None) => quote!(#methods.author(env!("CARGO_PKG_AUTHORS"))),
+            ("setting", &Some(ref s)) |
+            ("global_setting", &Some(ref s)) => {
+                let setting = Ident::new(&lit_str(s)?, s.span());
+                quote!(#methods.#key(::clap::AppSettings::#setting))
+            }
+            (_, &Some(ref val)) => quote!(#methods.#key(#val)),
+            (_, &None) => {
+                return Err(syn::Error::new_spanned(key, format!("expected `{} = ...`", key)))
+            }
+        };
+    }
+    let has = |k: &str| clap.iter().any(|&(ref key, _)| key == k);
+    let (about, long_about) = doc(attrs);
+    if let (false, Some(about)) = (has("about"), about) {
+        methods = quote!(#methods.about(#about));
+    }
+    if let (false, Some(long_about)) = (has("long_about"), long_about) {
+        methods = quote!(#methods.long_about(#long_about));
+    }
+    Ok((name, methods))
+}
+
+// The keys of all `#[clap(...)]` attributes, with their values if they have one
+fn clap_attrs(attrs: &[Attribute]) -> syn::Result<Vec<(Ident, Option<Lit>)>> {
+    let mut keys = vec![];
+    for attr in attrs.iter().filter(|a| a.path.is_ident("clap")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected `#[clap(...)]`")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(ref p)) if p.get_ident().is_some() => {
+                    keys.push((p.get_ident().unwrap().clone(), None))
+                }
+                NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.get_ident().is_some() => {
+                    keys.push((nv.path.get_ident().unwrap().clone(), Some(nv.lit.clone())))
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(other, "expected `key` or `key = value`"))
+                }
+            }
+        }
+    }
+    Ok(keys)
+}
+
+// The first paragraph of a doc comment, and all of it if there's more than one paragraph
+fn doc(attrs: &[Attribute]) -> (Option<String>, Option<String>) {
+    let mut paragraphs: Vec<String> = vec![];
+    let mut new_paragraph = true;
+    for attr in attrs.iter().filter(|a| a.path.is_ident("doc")) {
+        let line = match attr.parse_meta() {
+            Ok(Meta::NameValue(syn::MetaNameValue { lit: Lit::Str(ref s), .. })) => s.value(),
+            _ => continue,
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            new_paragraph = true;
+        } else if new_paragraph || paragraphs.is_empty() {
+            paragraphs.push(line.to_owned());
+            new_paragraph = false;
+        } else {
+            let last = paragraphs.last_mut().unwrap();
+            last.push(' ');
+            last.push_str(line);
+        }
+    }
+    match paragraphs.len() {
+        0 => (None, None),
+        1 => (paragraphs.pop(), None),
+        _ => (Some(paragraphs[0].clone()), Some(paragraphs.join("\n\n"))),
+    }
+}
+
+fn lit_str(lit: &Lit) -> syn::Result<String> {
+    match *lit {
+        Lit::Str(ref s) => Ok(s.value()),
+        _ => Err(syn::Error::new_spanned(lit, "expected a string")),
+    }
+}
+
+fn unraw(ident: &Ident) -> String { ident.to_string().trim_start_matches("r#").to_owned() }
+
+// `dry_run` and `DryRun` both become `dry-run`
+fn kebab(s: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if c == '_' {
+            out.push('-');
+        } else if c.is_uppercase() {
+            if i > 0 && !out.ends_with('-') {
+                out.push('-');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,162 @@
+#[macro_use]
+extern crate clap_derive;
+extern crate clap;
+
+use std::path::PathBuf;
+
+use clap::{ClapApp, ErrorKind, FromArgMatches};
+
+/// Does things
+///
+/// Does them thoroughly, too.
+#[derive(ClapApp, Debug, PartialEq)]
+#[clap(name = "prog", version = "1.0", setting = "ColorNever")]
+struct Opts {
+    /// Prints more
+    #[clap(short, long)]
+    verbose: u64,
+    /// Doesn't touch anything
+    ///
+    /// Only prints what would have been done.
+    #[clap(short = "n")]
+    dry_run: bool,
+    #[clap(short, long, value_name = "N")]
+    jobs: Option<usize>,
+    #[clap(short = "I", long = "include", number_of_values = 1)]
+    includes: Vec<PathBuf>,
+    #[clap(long, default_value = "auto", possible_value = "auto", possible_value = "never")]
+    color: String,
+    input: PathBuf,
+    rest: Vec<String>,
+}
+
+fn opts(args: &[&str]) -> clap::Result<Opts> {
+    let mut argv = vec!["prog"];
+    argv.extend(args);
+    Opts::try_parse_from(argv)
+}
+
+#[test]
+fn fields() {
+    assert_eq!(opts(&["-vv", "--jobs", "4", "-I", "a", "-Ib", "in", "x", "y"]).unwrap(),
+               Opts {
+                   verbose: 2,
+                   dry_run: false,
+                   jobs: Some(4),
+                   includes: vec![PathBuf::from("a"), PathBuf::from("b")],
+                   color: "auto".into(),
+                   input: PathBuf::from("in"),
+                   rest: vec!["x".into(), "y".into()],
+               });
+}
+
+#[test]
+fn defaults() {
+    let o = opts(&["-n", "in"]).unwrap();
+    assert!(o.dry_run);
+    assert_eq!(o.verbose, 0);
+    assert_eq!(o.jobs, None);
+    assert!(o.includes.is_empty() && o.rest.is_empty());
+}
+
+#[test]
+fn errors() {
+    assert_eq!(opts(&[]).unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+    assert_eq!(opts(&["-j", "four", "in"]).unwrap_err().kind, ErrorKind::InvalidValue);
+    assert_eq!(opts(&["--color", "always", "in"]).unwrap_err().kind, ErrorKind::InvalidValue);
+    assert_eq!(opts(&["--version"]).unwrap_err().kind, ErrorKind::VersionDisplayed);
+}
+
+#[test]
+fn help_from_doc_comments() {
+    let mut help = Vec::new();
+    Opts::clap().write_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.starts_with("prog 1.0\nDoes things\n"));
+    assert!(help.contains("    -n               Doesn't touch anything\n"));
+    assert!(help.contains("    -v, --verbose    Prints more\n"));
+    assert!(help.contains("-j, --jobs <N>"));
+
+    let mut long_help = Vec::new();
+    Opts::clap().write_long_help(&mut long_help).unwrap();
+    let long_help = String::from_utf8(long_help).unwrap();
+    assert!(long_help.contains("Doesn't touch anything\n"));
+    assert!(long_help.contains("Only prints what would have been done.\n"));
+}
+
+#[derive(ClapApp, Debug, PartialEq)]
+#[clap(name = "git")]
+struct Git {
+    #[clap(long, global)]
+    quiet: bool,
+    #[clap(subcommand)]
+    cmd: Option<Command>,
+}
+
+#[derive(ClapApp, Debug, PartialEq)]
+enum Command {
+    /// Adds files
+    Add {
+        #[clap(short)]
+        force: bool,
+        files: Vec<String>,
+    },
+    #[clap(name = "rm", alias = "remove")]
+    Remove(RemoveOpts),
+    StashList,
+}
+
+#[derive(ClapApp, Debug, PartialEq)]
+struct RemoveOpts {
+    #[clap(long)]
+    cached: bool,
+    file: String,
+}
+
+fn git(args: &[&str]) -> clap::Result<Git> {
+    let mut argv = vec!["git"];
+    argv.extend(args);
+    Git::try_parse_from(argv)
+}
+
+#[test]
+fn subcommands() {
+    assert_eq!(git(&["add", "-f", "a", "b"]).unwrap().cmd,
+               Some(Command::Add {
+                   force: true,
+                   files: vec!["a".into(), "b".into()],
+               }));
+    assert_eq!(git(&["remove", "--cached", "a"]).unwrap().cmd,
+               Some(Command::Remove(RemoveOpts {
+                   cached: true,
+                   file: "a".into(),
+               })));
+    assert_eq!(git(&["--quiet", "stash-list"]).unwrap(),
+               Git {
+                   quiet: true,
+                   cmd: Some(Command::StashList),
+               });
+    assert_eq!(git(&[]).unwrap().cmd, None);
+}
+
+#[test]
+fn enum_on_its_own() {
+    let m = Command::clap().get_matches_from(vec!["prog", "rm", "a"]);
+    assert_eq!(Command::from_matches(&m).unwrap(),
+               Command::Remove(RemoveOpts {
+                   cached: false,
+                   file: "a".into(),
+               }));
+
+    let err = Command::try_parse_from(vec!["prog"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingArgumentOrSubcommand);
+}
+
+#[test]
+fn subcommand_about_from_doc_comment() {
+    let mut help = Vec::new();
+    Git::clap().write_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.contains("    add           Adds files\n"));
+    assert!(help.contains("    stash-list"));
+}
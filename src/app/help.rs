@@ -1,8 +1,10 @@
 // Std
 use std::cmp;
 use std::collections::BTreeMap;
+use std::env;
 use std::fmt::Display;
 use std::io::{self, Cursor, Read, Write};
+use std::rc::Rc;
 use std::usize;
 
 // Internal
@@ -11,6 +13,7 @@ use app::parser::Parser;
 use args::{AnyArg, ArgSettings, DispOrder};
 use errors::{Error, Result as ClapResult};
 use fmt::{Format, Colorizer, ColorizerOption};
+use messages::{English, Messages};
 use app::usage;
 
 // Third Party
@@ -44,6 +47,40 @@ impl<'b, 'c, T> ArgWithOrder<'b, 'c> for T
 
 fn as_arg_trait<'a, 'b, T: ArgWithOrder<'a, 'b>>(x: &T) -> &ArgWithOrder<'a, 'b> { x }
 
+// The value names `--help` shows for `arg`, such as `<FILE>` or `<a> <b>` along with any value
+// hint, and whether `...` follows them. `None` when `arg` takes no value.
+pub fn val_names<'b, 'c, A>(arg: &A) -> Option<(Vec<String>, bool)>
+    where A: AnyArg<'b, 'c> + ?Sized
+{
+    if !arg.takes_value() {
+        return None;
+    }
+    let hint = arg.val_hint().map_or(String::new(), |h| format!(": {}", h));
+    let mult = arg.is_set(ArgSettings::Multiple);
+    Some(if let Some(vec) = arg.val_names() {
+             let names = vec.values().map(|n| format!("<{}{}>", n, hint)).collect();
+             (names, mult && vec.len() == 1)
+         } else if let Some(num) = arg.num_vals() {
+             ((0..num).map(|_| format!("<{}{}>", arg.name(), hint)).collect(), mult && num == 1)
+         } else if arg.has_switch() {
+             (vec![format!("<{}{}>", arg.name(), hint)], mult)
+         } else {
+             (vec![arg.to_string()], false)
+         })
+}
+
+// The subcommands of `p` which `--help` lists, in the same order
+pub fn subcommands<'a, 'b, 'p>(p: &'p Parser<'a, 'b>) -> Vec<&'p App<'a, 'b>> {
+    let mut scs: Vec<_> = p.subcommands
+        .iter()
+        .filter(|s| !s.p.is_set(AppSettings::Hidden))
+        .collect();
+    scs.sort_by(|a, b| {
+                    (a.p.meta.disp_ord, &a.p.meta.name).cmp(&(b.p.meta.disp_ord, &b.p.meta.name))
+                });
+    scs
+}
+
 impl<'b, 'c> DispOrder for App<'b, 'c> {
     fn disp_ord(&self) -> usize { 999 }
 }
@@ -78,6 +115,7 @@ pub struct Help<'a> {
     longest: usize,
     force_next_line: bool,
     use_long: bool,
+    messages: Rc<Messages>,
 }
 
 // Public Functions
@@ -90,7 +128,8 @@ impl<'a> Help<'a> {
                cizer: Colorizer,
                term_w: Option<usize>,
                max_w: Option<usize>,
-               use_long: bool)
+               use_long: bool,
+               messages: Rc<Messages>)
                -> Self {
         debugln!("Help::new;");
         Help {
@@ -112,6 +151,7 @@ impl<'a> Help<'a> {
             longest: 0,
             force_next_line: false,
             use_long: use_long,
+            messages: messages,
         }
     }
 
@@ -154,7 +194,8 @@ impl<'a> Help<'a> {
                   cizer,
                   parser.meta.term_w,
                   parser.meta.max_w,
-                  use_long)
+                  use_long,
+                  parser.meta.messages.clone().unwrap_or_else(|| Rc::new(English)))
                 .write_help(parser)
     }
 
@@ -287,7 +328,11 @@ impl<'a> Help<'a> {
             if arg.short().is_some() {
                 try!(write!(self.writer, ", "));
             }
-            try!(color!(self, "--{}", l, good));
+            if arg.is_set(ArgSettings::Negatable) {
+                try!(color!(self, "--[no-]{}", l, good));
+            } else {
+                try!(color!(self, "--{}", l, good));
+            }
         }
         Ok(())
     }
@@ -295,37 +340,16 @@ impl<'a> Help<'a> {
     /// Writes argument's possible values to the wrapped stream.
     fn val<'b, 'c>(&mut self, arg: &ArgWithDisplay<'b, 'c>) -> Result<String, io::Error> {
         debugln!("Help::val: arg={}", arg);
-        if arg.takes_value() {
-            if let Some(vec) = arg.val_names() {
-                let mut it = vec.iter().peekable();
-                while let Some((_, val)) = it.next() {
-                    try!(color!(self, "<{}>", val, good));
-                    if it.peek().is_some() {
-                        try!(write!(self.writer, " "));
-                    }
-                }
-                let num = vec.len();
-                if arg.is_set(ArgSettings::Multiple) && num == 1 {
-                    try!(color!(self, "...", good));
+        if let Some((names, multiple)) = val_names(arg) {
+            let mut it = names.iter().peekable();
+            while let Some(name) = it.next() {
+                try!(color!(self, "{}", name, good));
+                if it.peek().is_some() {
+                    try!(write!(self.writer, " "));
                 }
-            } else if let Some(num) = arg.num_vals() {
-                let mut it = (0..num).peekable();
-                while let Some(_) = it.next() {
-                    try!(color!(self, "<{}>", arg.name(), good));
-                    if it.peek().is_some() {
-                        try!(write!(self.writer, " "));
-                    }
-                }
-                if arg.is_set(ArgSettings::Multiple) && num == 1 {
-                    try!(color!(self, "...", good));
-                }
-            } else if arg.has_switch() {
-                try!(color!(self, "<{}>", arg.name(), good));
-                if arg.is_set(ArgSettings::Multiple) {
-                    try!(color!(self, "...", good));
-                }
-            } else {
-                try!(color!(self, "{}", arg, good));
+            }
+            if multiple {
+                try!(color!(self, "...", good));
             }
         }
 
@@ -463,10 +487,30 @@ impl<'a> Help<'a> {
     fn spec_vals(&self, a: &ArgWithDisplay) -> String {
         debugln!("Help::spec_vals: a={}", a);
         let mut spec_vals = vec![];
+        if let Some(name) = a.env() {
+            debugln!("Help::spec_vals: Found environment variable...[{:?}]", name);
+            if a.is_set(ArgSettings::HideEnvValues) || a.is_set(ArgSettings::Secret) {
+                spec_vals.push(format!(" [{}: {}]",
+                                       self.messages.env_label(),
+                                       name.to_string_lossy()));
+            } else {
+                let val = env::var_os(name)
+                    .map_or(String::new(), |v| v.to_string_lossy().into_owned());
+                spec_vals.push(format!(" [{}: {}={}]",
+                                       self.messages.env_label(),
+                                       name.to_string_lossy(),
+                                       if self.color {
+                                           self.cizer.good(val)
+                                       } else {
+                                           Format::None(val)
+                                       }));
+            }
+        }
         if !a.is_set(ArgSettings::HideDefaultValue) {
             if let Some(pv) = a.default_val() {
                 debugln!("Help::spec_vals: Found default value...[{:?}]", pv);
-                spec_vals.push(format!(" [default: {}]",
+                spec_vals.push(format!(" [{}: {}]",
+                                       self.messages.default_label(),
                                        if self.color {
                                            self.cizer.good(pv.to_string_lossy())
                                        } else {
@@ -476,7 +520,8 @@ impl<'a> Help<'a> {
         }
         if let Some(ref aliases) = a.aliases() {
             debugln!("Help::spec_vals: Found aliases...{:?}", aliases);
-            spec_vals.push(format!(" [aliases: {}]",
+            spec_vals.push(format!(" [{}: {}]",
+                                   self.messages.aliases_label(),
                                    if self.color {
                                        aliases
                                            .iter()
@@ -491,13 +536,31 @@ impl<'a> Help<'a> {
             if let Some(pv) = a.possible_vals() {
                 debugln!("Help::spec_vals: Found possible vals...{:?}", pv);
                 spec_vals.push(if self.color {
-                                   format!(" [values: {}]",
+                                   format!(" [{}: {}]",
+                                           self.messages.values_label(),
                                            pv.iter()
                                                .map(|v| format!("{}", self.cizer.good(v)))
                                                .collect::<Vec<_>>()
                                                .join(", "))
                                } else {
-                                   format!(" [values: {}]", pv.join(", "))
+                                   format!(" [{}: {}]",
+                                           self.messages.values_label(),
+                                           pv.join(", "))
+                               });
+            }
+            if let Some(pk) = a.possible_keys() {
+                debugln!("Help::spec_vals: Found possible keys...{:?}", pk);
+                spec_vals.push(if self.color {
+                                   format!(" [{}: {}]",
+                                           self.messages.keys_label(),
+                                           pk.iter()
+                                               .map(|k| format!("{}", self.cizer.good(k)))
+                                               .collect::<Vec<_>>()
+                                               .join(", "))
+                               } else {
+                                   format!(" [{}: {}]",
+                                           self.messages.keys_label(),
+                                           pk.join(", "))
                                });
             }
         }
@@ -530,12 +593,12 @@ impl<'a> Help<'a> {
                 .flags()
                 .map(as_arg_trait)
                 .chain(parser.opts().map(as_arg_trait));
-            try!(color!(self, "OPTIONS:\n", warning));
+            try!(color!(self, "{}\n", self.messages.options_heading(), warning));
             try!(self.write_args(opts_flags));
             first = false;
         } else {
             if flags {
-                try!(color!(self, "FLAGS:\n", warning));
+                try!(color!(self, "{}\n", self.messages.flags_heading(), warning));
                 try!(self.write_args(parser.flags().map(as_arg_trait)));
                 first = false;
             }
@@ -543,7 +606,7 @@ impl<'a> Help<'a> {
                 if !first {
                     try!(self.writer.write_all(b"\n\n"));
                 }
-                try!(color!(self, "OPTIONS:\n", warning));
+                try!(color!(self, "{}\n", self.messages.options_heading(), warning));
                 try!(self.write_args(parser.opts().map(as_arg_trait)));
                 first = false;
             }
@@ -553,7 +616,7 @@ impl<'a> Help<'a> {
             if !first {
                 try!(self.writer.write_all(b"\n\n"));
             }
-            try!(color!(self, "ARGS:\n", warning));
+            try!(color!(self, "{}\n", self.messages.args_heading(), warning));
             try!(self.write_args_unsorted(parser.positionals().map(as_arg_trait)));
             first = false;
         }
@@ -562,7 +625,7 @@ impl<'a> Help<'a> {
             if !first {
                 try!(self.writer.write_all(b"\n\n"));
             }
-            try!(color!(self, "SUBCOMMANDS:\n", warning));
+            try!(color!(self, "{}\n", self.messages.subcommands_heading(), warning));
             try!(self.write_subcommands(&parser));
         }
 
@@ -574,28 +637,19 @@ impl<'a> Help<'a> {
         debugln!("Help::write_subcommands;");
         // The shortest an arg can legally be is 2 (i.e. '-x')
         self.longest = 2;
-        let mut ord_m = VecMap::new();
-        for sc in parser
-                .subcommands
-                .iter()
-                .filter(|s| !s.p.is_set(AppSettings::Hidden)) {
-            let btm = ord_m
-                .entry(sc.p.meta.disp_ord)
-                .or_insert(BTreeMap::new());
+        let scs = subcommands(parser);
+        for sc in &scs {
             self.longest = cmp::max(self.longest, sc.p.meta.name.len());
-            btm.insert(sc.p.meta.name.clone(), sc.clone());
         }
 
         let mut first = true;
-        for btm in ord_m.values() {
-            for sc in btm.values() {
-                if first {
-                    first = false;
-                } else {
-                    try!(self.writer.write_all(b"\n"));
-                }
-                try!(self.write_arg(sc));
+        for sc in scs {
+            if first {
+                first = false;
+            } else {
+                try!(self.writer.write_all(b"\n"));
             }
+            try!(self.write_arg(sc));
         }
         Ok(())
     }
@@ -658,7 +712,7 @@ impl<'a> Help<'a> {
             write_thing!(about)
         }
 
-        try!(color!(self, "\nUSAGE:", warning));
+        try!(color!(self, "\n{}", self.messages.usage_heading(), warning));
         try!(write!(self.writer,
                     "\n{}{}\n\n",
                     TAB,
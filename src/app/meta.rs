@@ -1,3 +1,10 @@
+// Std
+use std::rc::Rc;
+
+// Internal
+use errors::ErrorKind;
+use messages::Messages;
+
 #[doc(hidden)]
 #[allow(missing_debug_implementations)]
 #[derive(Default, Clone)]
@@ -19,6 +26,8 @@ pub struct AppMeta<'b> {
     pub term_w: Option<usize>,
     pub max_w: Option<usize>,
     pub template: Option<&'b str>,
+    pub messages: Option<Rc<Messages>>,
+    pub exit_codes: Vec<(ErrorKind, i32)>,
 }
 
 impl<'b> AppMeta<'b> {
@@ -3,32 +3,51 @@ mod settings;
 mod macros;
 pub mod parser;
 mod meta;
-mod help;
+pub mod help;
 mod validator;
-mod usage;
+mod response_files;
+mod prompt;
+mod repl;
+mod shell_words;
+pub mod usage;
 
 // Std
 use std::env;
 use std::ffi::{OsStr, OsString};
 use std::fmt;
 use std::io::{self, BufRead, BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::any::Any;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::result::Result as StdResult;
 
 // Third Party
 use vec_map::{self, VecMap};
 #[cfg(feature = "yaml")]
 use yaml_rust::Yaml;
+#[cfg(feature = "json")]
+use serde_json::Value as Json;
+#[cfg(feature = "toml")]
+use toml::Value as Toml;
 
 // Internal
 use app::help::Help;
 use app::parser::Parser;
 use args::{AnyArg, Arg, ArgGroup, ArgMatcher, ArgMatches, ArgSettings};
+use errors::{Error, ErrorKind};
 use errors::Result as ClapResult;
 pub use self::settings::AppSettings;
-use completions::Shell;
+pub use self::repl::Repl;
+use completions::{self, Shell};
+use docs::DocFormat;
+use definition;
+#[cfg(any(feature = "yaml", feature = "json", feature = "toml"))]
+use definition::Node;
+use config::ConfigSource;
+use messages::Messages;
+use validators::Check;
 
 /// Used to create a representation of a command line program and all possible command line
 /// arguments. Application settings are set using the "builder pattern" with the
@@ -151,6 +170,86 @@ impl<'a, 'b> App<'a, 'b> {
     #[cfg(feature = "yaml")]
     pub fn from_yaml(yaml: &'a Yaml) -> App<'a, 'a> { App::from(yaml) }
 
+    /// Creates a new instance of [`App`] from a JSON document. It uses exactly the same keys as
+    /// [`App::from_yaml`], so the JSON written by [`App::to_json`] (or any of the YAML examples
+    /// converted to JSON) can be loaded with it. The [`load_json!`] macro loads a JSON file at
+    /// compile time, a string can be parsed into a [`JsonValue`] with [`str::parse`].
+    ///
+    /// In order to use this function you must compile `clap` with the `features = ["json"]` in
+    /// your settings for the `[dependencies.clap]` table of your `Cargo.toml`
+    ///
+    /// # Panics
+    ///
+    /// Like [`App::from_yaml`], this will [`panic!`] if the document isn't a valid app
+    /// definition.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// # #[macro_use]
+    /// # extern crate clap;
+    /// # use clap::{App, JsonValue};
+    /// # fn main() {
+    /// let json: JsonValue = r#"{
+    ///     "name": "prog",
+    ///     "args": [{ "verbose": { "short": "v", "multiple": true } }]
+    /// }"#.parse().unwrap();
+    /// let m = App::from_json(&json).get_matches_from(vec!["prog", "-vv"]);
+    /// assert_eq!(m.occurrences_of("verbose"), 2);
+    /// # }
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [`App::from_yaml`]: ./struct.App.html#method.from_yaml
+    /// [`App::to_json`]: ./struct.App.html#method.to_json
+    /// [`load_json!`]: ./macro.load_json!.html
+    /// [`JsonValue`]: ./type.JsonValue.html
+    /// [`str::parse`]: https://doc.rust-lang.org/std/primitive.str.html#method.parse
+    /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &'a Json) -> App<'a, 'a> { App::from_node(json) }
+
+    /// Creates a new instance of [`App`] from a TOML document. It uses exactly the same keys as
+    /// [`App::from_yaml`], lists of args, subcommands and groups are arrays of tables each with a
+    /// single key naming the item. Since TOML has no null, `default_value_ifs` entries without a
+    /// value are written as `[arg, default]`. The [`load_toml!`] macro loads a TOML file at
+    /// compile time, a string can be parsed into a [`TomlValue`] with [`str::parse`].
+    ///
+    /// In order to use this function you must compile `clap` with the `features = ["toml"]` in
+    /// your settings for the `[dependencies.clap]` table of your `Cargo.toml`
+    ///
+    /// # Panics
+    ///
+    /// Like [`App::from_yaml`], this will [`panic!`] if the document isn't a valid app
+    /// definition.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// # #[macro_use]
+    /// # extern crate clap;
+    /// # use clap::{App, TomlValue};
+    /// # fn main() {
+    /// let toml: TomlValue = r#"
+    ///     name = "prog"
+    ///
+    ///     [[args]]
+    ///     [args.verbose]
+    ///     short = "v"
+    ///     multiple = true
+    /// "#.parse().unwrap();
+    /// let m = App::from_toml(&toml).get_matches_from(vec!["prog", "-vv"]);
+    /// assert_eq!(m.occurrences_of("verbose"), 2);
+    /// # }
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [`App::from_yaml`]: ./struct.App.html#method.from_yaml
+    /// [`load_toml!`]: ./macro.load_toml!.html
+    /// [`TomlValue`]: ./type.TomlValue.html
+    /// [`str::parse`]: https://doc.rust-lang.org/std/primitive.str.html#method.parse
+    /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &'a Toml) -> App<'a, 'a> { App::from_node(toml) }
+
     /// Sets a string of author(s) that will be displayed to the user when they
     /// request the help information with `--help` or `-h`.
     ///
@@ -1133,6 +1232,152 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Reads values for any arguments not given on the command line from the config file at
+    /// `path`. Keys in the file are argument [`long`]s (or names, for those without one) and
+    /// `[sections]` are [`SubCommand`] names; see [`ConfigSource`] for the full syntax.
+    ///
+    /// Values are looked for in the order: command line, [environment], config file, and finally
+    /// [default values]. [`ArgMatches::value_source`] reports where each one came from.
+    ///
+    /// A file that doesn't exist is silently skipped, so this may be called multiple times to
+    /// layer configs, e.g. a system wide one followed by a per user one. Keys in later files take
+    /// precedence over the same keys in earlier ones.
+    ///
+    /// Config values are validated in the same way as those from the command line, any errors
+    /// include the file and line of the offending entry. Sections or keys which don't match any
+    /// subcommand or argument are also errors. A file which can't be read is an
+    /// [`ErrorKind::Io`] error, while one which isn't valid syntax is an
+    /// [`ErrorKind::InvalidValue`] pointing at the offending line.
+    ///
+    /// **NOTE:** This only has an effect on the top level `App`, since sections are used for
+    /// subcommands.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .config_file("/etc/prog.conf")
+    ///     .config_file("prog.conf")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .takes_value(true))
+    ///     .get_matches();
+    /// ```
+    /// [`long`]: ./struct.Arg.html#method.long
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`ConfigSource`]: ./trait.ConfigSource.html
+    /// [environment]: ./struct.Arg.html#method.env
+    /// [default values]: ./struct.Arg.html#method.default_value
+    /// [`ArgMatches::value_source`]: ./struct.ArgMatches.html#method.value_source
+    /// [`ErrorKind::Io`]: ./enum.ErrorKind.html#variant.Io
+    /// [`ErrorKind::InvalidValue`]: ./enum.ErrorKind.html#variant.InvalidValue
+    pub fn config_file<P: Into<PathBuf>>(self, path: P) -> Self {
+        self.config_source(path.into())
+    }
+
+    /// Reads values for any arguments not given on the command line from a custom
+    /// [`ConfigSource`], in exactly the same manner as [`App::config_file`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ConfigSource, ValueSource};
+    /// # use std::io;
+    /// struct Defaults;
+    ///
+    /// impl ConfigSource for Defaults {
+    ///     fn name(&self) -> String { String::from("<built-in defaults>") }
+    ///
+    ///     fn read(&self) -> io::Result<Option<String>> {
+    ///         Ok(Some(String::from("color = never\nlevel = 3")))
+    ///     }
+    /// }
+    ///
+    /// let m = App::new("prog")
+    ///     .config_source(Defaults)
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .takes_value(true))
+    ///     .arg(Arg::with_name("level")
+    ///         .long("level")
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec!["prog", "--level", "1"]);
+    ///
+    /// assert_eq!(m.value_of("color"), Some("never"));
+    /// assert_eq!(m.value_source("color"), Some(ValueSource::Config));
+    /// assert_eq!(m.value_of("level"), Some("1"));
+    /// ```
+    /// [`ConfigSource`]: ./trait.ConfigSource.html
+    /// [`App::config_file`]: ./struct.App.html#method.config_file
+    pub fn config_source<S: ConfigSource + 'static>(mut self, source: S) -> Self {
+        self.p.config_sources.push(Rc::new(source));
+        self
+    }
+
+    /// Sets the status the process exits with when [`App::get_matches`] or [`Error::exit`] stop
+    /// because of an error of the given [`ErrorKind`], such as the codes of `sysexits(3)`.
+    /// Errors of other kinds exit with `1`, while help, version and completions exit with `0`.
+    ///
+    /// Calling this again for the same kind replaces the code. The codes of the `App` which
+    /// starts parsing apply to the errors of its subcommands too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// const EX_USAGE: i32 = 64;
+    /// const EX_DATAERR: i32 = 65;
+    ///
+    /// let err = App::new("prog")
+    ///     .exit_code(ErrorKind::UnknownArgument, EX_USAGE)
+    ///     .exit_code(ErrorKind::MissingRequiredArgument, EX_USAGE)
+    ///     .exit_code(ErrorKind::ValueValidation, EX_DATAERR)
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .takes_value(true)
+    ///         .validator(|v| v.parse::<u16>().map(|_| ()).map_err(|e| e.to_string())))
+    ///     .get_matches_from_safe(vec!["prog", "--port", "eighty"])
+    ///     .unwrap_err();
+    /// assert_eq!(err.exit_code(), EX_DATAERR);
+    /// ```
+    /// [`App::get_matches`]: ./struct.App.html#method.get_matches
+    /// [`Error::exit`]: ./struct.Error.html#method.exit
+    /// [`ErrorKind`]: ./enum.ErrorKind.html
+    pub fn exit_code(mut self, kind: ErrorKind, code: i32) -> Self {
+        self.p.meta.exit_codes.push((kind, code));
+        self
+    }
+
+    /// Replaces the English headings of the help message and the error messages with those of a
+    /// [`Messages`] implementation, such as a translation. Subcommands use the same messages
+    /// unless they set their own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Messages};
+    /// struct Shouting;
+    ///
+    /// impl Messages for Shouting {
+    ///     fn flags_heading(&self) -> &str { "SWITCHES:" }
+    ///     fn missing_required_arguments(&self, args: &str) -> String {
+    ///         format!("YOU FORGOT:{}", args)
+    ///     }
+    /// }
+    ///
+    /// let res = App::new("prog")
+    ///     .messages(Shouting)
+    ///     .arg(Arg::with_name("input").required(true))
+    ///     .get_matches_from_safe(vec!["prog"]);
+    /// assert!(res.unwrap_err().message.starts_with("error: YOU FORGOT:\n    <input>"));
+    /// ```
+    /// [`Messages`]: ./trait.Messages.html
+    pub fn messages<M: Messages + 'static>(mut self, messages: M) -> Self {
+        self.p.meta.messages = Some(Rc::new(messages));
+        self
+    }
+
     /// Prints the full help message to [`io::stdout()`] using a [`BufWriter`] using the same
     /// method as if someone ran `-h` to request the help message
     ///
@@ -1379,7 +1624,8 @@ impl<'a, 'b> App<'a, 'b> {
     /// Assuming we compiled with debug mode, it would be somewhere similar to
     /// `<project>/target/debug/build/myapp-<hash>/out/myapp.bash-completion`.
     ///
-    /// Fish shell completions will use the file format `{bin_name}.fish`
+    /// Fish shell completions will use the file format `{bin_name}.fish`, Elvish completions
+    /// `{bin_name}.elv` and Nushell completions `{bin_name}.nu`
     pub fn gen_completions<T: Into<OsString>, S: Into<String>>(&mut self,
                                                                bin_name: S,
                                                                for_shell: Shell,
@@ -1429,6 +1675,176 @@ impl<'a, 'b> App<'a, 'b> {
         self.p.gen_completions_to(for_shell, buf);
     }
 
+    /// Generates a `man(7)` page for this application and writes it to `buf`, so the manual can
+    /// be built from the same definition as `--help` instead of being maintained by hand.
+    ///
+    /// The page contains the `NAME`, `SYNOPSIS`, `DESCRIPTION` (from [`App::long_about`], or
+    /// [`App::about`]), `OPTIONS` and `ARGS` sections, followed by one section for each
+    /// subcommand and finally `VERSION` and `AUTHOR`. Where an argument has an [`Arg::long_help`]
+    /// it's used in favor of [`Arg::help`]. Hidden arguments and subcommands are left out.
+    ///
+    /// Like [`App::gen_completions`] this is usually called from a `build.rs` script, or from a
+    /// hidden subcommand of the application itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("myapp")
+    ///     .version("1.0")
+    ///     .author("Kevin K. <kbknapp@gmail.com>")
+    ///     .about("Does awesome things")
+    ///     .arg(Arg::with_name("config")
+    ///         .short("c")
+    ///         .long("config")
+    ///         .takes_value(true)
+    ///         .help("Sets a custom config file"));
+    ///
+    /// let mut page = Vec::new();
+    /// app.gen_manpage_to(&mut page);
+    ///
+    /// let page = String::from_utf8(page).unwrap();
+    /// assert!(page.starts_with(".TH MYAPP 1"));
+    /// assert!(page.contains("\\fB\\-c\\fR, \\fB\\-\\-config\\fR \\fI<config>\\fR"));
+    /// ```
+    /// [`App::long_about`]: ./struct.App.html#method.long_about
+    /// [`App::about`]: ./struct.App.html#method.about
+    /// [`Arg::long_help`]: ./struct.Arg.html#method.long_help
+    /// [`Arg::help`]: ./struct.Arg.html#method.help
+    /// [`App::gen_completions`]: ./struct.App.html#method.gen_completions
+    pub fn gen_manpage_to<W: Write>(&mut self, buf: &mut W) { self.p.gen_manpage_to(buf); }
+
+    /// Writes a Markdown reference document for the whole command tree to `w`. Each command,
+    /// starting with this one and followed by every subcommand (recursively), gets an anchor
+    /// named after its full bin name with spaces replaced by `-` (i.e. `myapp-test`), its about
+    /// text, its usage string and a table of its arguments showing the short, long, value names,
+    /// default value, possible values, environment variable, whether it's required and its help.
+    ///
+    /// The arguments and subcommands appear in the same order as with `--help` and hidden ones
+    /// are left out. Where an argument has an [`Arg::long_help`] it's used in favor of
+    /// [`Arg::help`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand};
+    /// let mut app = App::new("myapp")
+    ///     .about("Does awesome things")
+    ///     .arg(Arg::with_name("config")
+    ///         .short("c")
+    ///         .long("config")
+    ///         .value_name("FILE")
+    ///         .help("Sets a custom config file"))
+    ///     .subcommand(SubCommand::with_name("test")
+    ///         .about("Tests things"));
+    ///
+    /// let mut doc = Vec::new();
+    /// app.write_markdown(&mut doc).unwrap();
+    ///
+    /// let doc = String::from_utf8(doc).unwrap();
+    /// assert!(doc.starts_with("<a name=\"myapp\"></a>\n# myapp\n"));
+    /// assert!(doc.contains("| `-c` | `--config` | `<FILE>` |"));
+    /// assert!(doc.contains("* [`myapp test`](#myapp-test): Tests things"));
+    /// ```
+    /// [`Arg::long_help`]: ./struct.Arg.html#method.long_help
+    /// [`Arg::help`]: ./struct.Arg.html#method.help
+    pub fn write_markdown<W: Write>(&mut self, w: &mut W) -> ClapResult<()> {
+        self.p.gen_docs_to(DocFormat::Markdown, w)
+    }
+
+    /// Writes an HTML reference document for the whole command tree to `w`. The document
+    /// contains the same information as [`App::write_markdown`], with each command in its own
+    /// `<section>` whose `id` is the anchor. Only the fragment is written (no `<html>` or
+    /// `<body>` tags) so it can be embedded in an existing page.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("myapp")
+    ///     .arg(Arg::with_name("input")
+    ///         .required(true)
+    ///         .help("The input file"));
+    ///
+    /// let mut doc = Vec::new();
+    /// app.write_html(&mut doc).unwrap();
+    ///
+    /// let doc = String::from_utf8(doc).unwrap();
+    /// assert!(doc.starts_with("<section id=\"myapp\">\n<h1>myapp</h1>\n"));
+    /// assert!(doc.contains("<td><code>&lt;input&gt;</code></td>"));
+    /// ```
+    /// [`App::write_markdown`]: ./struct.App.html#method.write_markdown
+    pub fn write_html<W: Write>(&mut self, w: &mut W) -> ClapResult<()> {
+        self.p.gen_docs_to(DocFormat::Html, w)
+    }
+
+    /// Writes the definition of this `App`, its arguments, groups and subcommands as YAML using
+    /// the keys [`App::from_yaml`] reads, so that loading the result gives an equivalent parser.
+    /// This is useful for snapshot testing the interface of a CLI or diffing it between releases.
+    ///
+    /// Only what `from_yaml` understands is written, so things like validators, default values
+    /// which aren't valid UTF-8, or settings such as [`Arg::last`] are left out. Keys are only
+    /// written when they differ from the default, and the `--help` and `--version` flags and the
+    /// `help` subcommand `clap` generates itself are left out too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand};
+    /// let app = App::new("prog")
+    ///     .version("1.0")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short("v")
+    ///         .multiple(true)
+    ///         .help("be loud"))
+    ///     .subcommand(SubCommand::with_name("init"));
+    ///
+    /// assert_eq!(app.to_yaml(),
+    /// r#"name: "prog"
+    /// version: "1.0"
+    /// args:
+    ///   - verbose:
+    ///       short: "v"
+    ///       help: "be loud"
+    ///       multiple: true
+    /// subcommands:
+    ///   - init: {}
+    /// "#);
+    /// ```
+    /// [`App::from_yaml`]: ./struct.App.html#method.from_yaml
+    /// [`Arg::last`]: ./struct.Arg.html#method.last
+    pub fn to_yaml(&self) -> String { definition::to_yaml(&definition::app(&self.p)) }
+
+    /// Writes the same definition as [`App::to_yaml`], but as JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("prog")
+    ///     .arg(Arg::with_name("mode")
+    ///         .long("mode")
+    ///         .takes_value(true)
+    ///         .possible_values(&["fast", "slow"]));
+    ///
+    /// assert_eq!(app.to_json(),
+    /// r#"{
+    ///   "name": "prog",
+    ///   "args": [
+    ///     {
+    ///       "mode": {
+    ///         "long": "mode",
+    ///         "takes_value": true,
+    ///         "possible_values": ["fast", "slow"]
+    ///       }
+    ///     }
+    ///   ]
+    /// }
+    /// "#);
+    /// ```
+    /// [`App::to_yaml`]: ./struct.App.html#method.to_yaml
+    pub fn to_json(&self) -> String { definition::to_json(&definition::app(&self.p)) }
+
     /// Starts the parsing process, upon a failed parse an error will be displayed to the user and
     /// the process will exit with the appropriate error code. By default this method gets all user
     /// provided arguments from [`env::args_os`] in order to allow for invalid UTF-8 code points,
@@ -1502,17 +1918,16 @@ impl<'a, 'b> App<'a, 'b> {
     {
         self.get_matches_from_safe_borrow(itr).unwrap_or_else(|e| {
             // Otherwise, write to stderr and exit
-            if e.use_stderr() {
-                wlnerr!("{}", e.message);
-                if self.p.is_set(AppSettings::WaitOnError) {
-                    wlnerr!("\nPress [ENTER] / [RETURN] to continue...");
-                    let mut s = String::new();
-                    let i = io::stdin();
-                    i.lock().read_line(&mut s).unwrap();
-                }
+            if e.use_stderr() && self.p.is_set(AppSettings::WaitOnError) {
+                e.print();
+                wlnerr!("\nPress [ENTER] / [RETURN] to continue...");
+                let mut s = String::new();
+                let i = io::stdin();
+                i.lock().read_line(&mut s).unwrap();
+                let code = e.exit_code();
                 drop(self);
                 drop(e);
-                process::exit(1);
+                process::exit(code);
             }
 
             drop(self);
@@ -1583,6 +1998,105 @@ impl<'a, 'b> App<'a, 'b> {
     pub fn get_matches_from_safe_borrow<I, T>(&mut self, itr: I) -> ClapResult<ArgMatches<'a>>
         where I: IntoIterator<Item = T>,
               T: Into<OsString> + Clone
+    {
+        let res = self.parse(itr);
+        res.map_err(|e| self.finish_error(e))
+    }
+
+    /// Splits `line` into arguments the way a POSIX shell would and parses them like
+    /// [`App::get_matches_from_safe`], so the first one is the binary name unless
+    /// [`AppSettings::NoBinaryName`] is used. This is handy for command lines embedded in config
+    /// files or tests, where splitting on whitespace would break quoted values.
+    ///
+    /// Arguments are separated by whitespace. Quoting with `'...'` keeps everything inside as it
+    /// is, while inside `"..."` a `\` escapes `$`, `` ` ``, `"` and `\`. Elsewhere a `\` escapes
+    /// any character, and `\` followed by a newline joins the lines. A `#` at the start of an
+    /// argument comments out the rest of the line. Nothing is expanded, so `$HOME` or `*.rs` are
+    /// taken literally.
+    ///
+    /// A quote which isn't closed, or a `\` at the very end, is an [`ErrorKind::ShellSyntax`]
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("tool")
+    ///     .arg(Arg::with_name("opt").long("opt").takes_value(true))
+    ///     .arg(Arg::with_name("input"))
+    ///     .get_matches_from_str(r#"tool --opt 'a b' "c\"d" # a comment"#)
+    ///     .unwrap();
+    /// assert_eq!(m.value_of("opt"), Some("a b"));
+    /// assert_eq!(m.value_of("input"), Some("c\"d"));
+    /// ```
+    /// [`App::get_matches_from_safe`]: ./struct.App.html#method.get_matches_from_safe
+    /// [`AppSettings::NoBinaryName`]: ./enum.AppSettings.html#variant.NoBinaryName
+    /// [`ErrorKind::ShellSyntax`]: ./enum.ErrorKind.html#variant.ShellSyntax
+    pub fn get_matches_from_str(mut self, line: &str) -> ClapResult<ArgMatches<'a>> {
+        match shell_words::split(line, self.p.color()) {
+            Ok(args) => self.get_matches_from_safe_borrow(args),
+            Err(e) => Err(self.finish_error(e)),
+        }
+    }
+
+    // Applies App::messages, AppSettings::JsonErrors and App::exit_code to an error
+    fn finish_error(&self, mut e: Error) -> Error {
+        if let Some(ref m) = self.p.meta.messages {
+            e = e.localize(&**m);
+        }
+        if self.p.is_set(AppSettings::JsonErrors) {
+            e = e.as_json();
+        }
+        match self.p.meta.exit_codes.iter().rev().find(|&&(k, _)| k == e.kind) {
+            Some(&(_, code)) => e.with_exit_code(code),
+            None => e,
+        }
+    }
+
+    /// Turns the `App` into a [`Repl`] parsing the lines read from `stdin`, each of which is a
+    /// command line, such as for an interactive console.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, SubCommand};
+    /// let repl = App::new("console")
+    ///     .subcommand(SubCommand::with_name("add").arg(Arg::with_name("file")))
+    ///     .repl()
+    ///     .prompt("> ");
+    /// for res in repl {
+    ///     match res {
+    ///         Ok(m) => println!("{:?}", m.subcommand_name()),
+    ///         // Also --help and --version, which don't exit
+    ///         Err(e) => println!("{}", e),
+    ///     }
+    /// }
+    /// ```
+    /// [`Repl`]: ./struct.Repl.html
+    pub fn repl(self) -> Repl<'a, 'b, io::BufReader<io::Stdin>> {
+        self.repl_from(io::BufReader::new(io::stdin()))
+    }
+
+    /// Turns the `App` into a [`Repl`] parsing the lines read from `input`. See [`App::repl`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// # use std::io::Cursor;
+    /// let mut repl = App::new("console")
+    ///     .arg(Arg::with_name("verbose").short("v"))
+    ///     .repl_from(Cursor::new("-v\n\n"));
+    /// assert!(repl.next().unwrap().unwrap().is_present("verbose"));
+    /// assert!(repl.next().is_none());
+    /// ```
+    /// [`Repl`]: ./struct.Repl.html
+    /// [`App::repl`]: ./struct.App.html#method.repl
+    pub fn repl_from<R: BufRead>(self, input: R) -> Repl<'a, 'b, R> { Repl::new(self, input) }
+
+    fn parse<I, T>(&mut self, itr: I) -> ClapResult<ArgMatches<'a>>
+        where I: IntoIterator<Item = T>,
+              T: Into<OsString> + Clone
     {
         // If there are global arguments, or settings we need to propgate them down to subcommands
         // before parsing incase we run into a subcommand
@@ -1614,8 +2128,26 @@ impl<'a, 'b> App<'a, 'b> {
             }
         }
 
+        // Index 0 always belongs to the binary name, whether or not one was passed
+        self.p.cur_idx.set(0);
+
+        let mut it = it.peekable();
+        if completions::dynamic::has_completers(&self.p) &&
+           it.peek().map_or(false, |a| a.clone().into() == completions::COMPLETE_CMD) {
+            let words: Vec<OsString> = it.skip(1).map(|a| a.into()).collect();
+            return Err(self.p.complete_words(&*words));
+        }
+
+        try!(self.p.load_config());
+
         // do the real parsing
-        if let Err(e) = self.p.get_matches_with(&mut matcher, &mut it.peekable()) {
+        let res = if self.p.is_set(AppSettings::ResponseFiles) {
+            let args = try!(response_files::expand(it, self.p.color()));
+            self.p.get_matches_with(&mut matcher, &mut args.into_iter().peekable())
+        } else {
+            self.p.get_matches_with(&mut matcher, &mut it)
+        };
+        if let Err(e) = res {
             return Err(e);
         }
 
@@ -1631,106 +2163,80 @@ impl<'a, 'b> App<'a, 'b> {
 
 #[cfg(feature = "yaml")]
 impl<'a> From<&'a Yaml> for App<'a, 'a> {
-    fn from(mut yaml: &'a Yaml) -> Self {
-        use args::SubCommand;
+    fn from(yaml: &'a Yaml) -> Self { App::from_node(yaml) }
+}
+
+#[cfg(any(feature = "yaml", feature = "json", feature = "toml"))]
+impl<'a> App<'a, 'a> {
+    // Builds the app from a YAML, JSON or TOML document, which is either the app's settings
+    // (including its `name`) or, for subcommands, a single key naming it with the settings as value
+    fn from_node<N: Node>(mut node: &'a N) -> Self {
         // We WANT this to panic on error...so expect() is good.
-        let mut is_sc = None;
-        let mut a = if let Some(name) = yaml["name"].as_str() {
+        let mut a = if let Some(name) = node.get("name").and_then(N::as_str) {
             App::new(name)
         } else {
-            let yaml_hash = yaml.as_hash().unwrap();
-            let sc_key = yaml_hash.keys().nth(0).unwrap();
-            is_sc = Some(yaml_hash.get(sc_key).unwrap());
-            App::new(sc_key.as_str().unwrap())
+            let (sc_name, sc) = node.entries().unwrap()[0];
+            node = sc;
+            App::new(sc_name)
         };
-        yaml = if let Some(sc) = is_sc { sc } else { yaml };
 
-        macro_rules! yaml_str {
+        macro_rules! node_str {
             ($a:ident, $y:ident, $i:ident) => {
-                if let Some(v) = $y[stringify!($i)].as_str() {
-                    $a = $a.$i(v);
-                } else if $y[stringify!($i)] != Yaml::BadValue {
-                    panic!("Failed to convert YAML value {:?} to a string", $y[stringify!($i)]);
+                if let Some(v) = $y.get(stringify!($i)) {
+                    $a = $a.$i(v.as_str().unwrap_or_else(|| {
+                        panic!("Failed to convert value {:?} to a string", v)
+                    }));
                 }
             };
         }
 
-        yaml_str!(a, yaml, version);
-        yaml_str!(a, yaml, author);
-        yaml_str!(a, yaml, bin_name);
-        yaml_str!(a, yaml, about);
-        yaml_str!(a, yaml, before_help);
-        yaml_str!(a, yaml, after_help);
-        yaml_str!(a, yaml, template);
-        yaml_str!(a, yaml, usage);
-        yaml_str!(a, yaml, help);
-        yaml_str!(a, yaml, help_short);
-        yaml_str!(a, yaml, version_short);
-        yaml_str!(a, yaml, help_message);
-        yaml_str!(a, yaml, version_message);
-        yaml_str!(a, yaml, alias);
-        yaml_str!(a, yaml, visible_alias);
-
-        if let Some(v) = yaml["display_order"].as_i64() {
-            a = a.display_order(v as usize);
-        } else if yaml["display_order"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to a u64",
-                   yaml["display_order"]);
+        node_str!(a, node, version);
+        node_str!(a, node, author);
+        node_str!(a, node, bin_name);
+        node_str!(a, node, about);
+        node_str!(a, node, before_help);
+        node_str!(a, node, after_help);
+        node_str!(a, node, template);
+        node_str!(a, node, usage);
+        node_str!(a, node, help);
+        node_str!(a, node, help_short);
+        node_str!(a, node, version_short);
+        node_str!(a, node, help_message);
+        node_str!(a, node, version_message);
+        node_str!(a, node, alias);
+        node_str!(a, node, visible_alias);
+
+        if let Some(v) = node.get("display_order") {
+            let ord = v.as_i64()
+                .unwrap_or_else(|| panic!("Failed to convert value {:?} to a u64", v));
+            a = a.display_order(ord as usize);
         }
-        if let Some(v) = yaml["setting"].as_str() {
-            a = a.setting(v.parse().expect("unknown AppSetting found in YAML file"));
-        } else if yaml["setting"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to an AppSetting",
-                   yaml["setting"]);
+        if let Some(v) = node.get("setting") {
+            let s = v.as_str()
+                .unwrap_or_else(|| panic!("Failed to convert value {:?} to an AppSetting", v));
+            a = a.setting(s.parse().expect("unknown AppSetting found in app definition"));
         }
-        if let Some(v) = yaml["settings"].as_vec() {
-            for ys in v {
-                if let Some(s) = ys.as_str() {
-                    a = a.setting(s.parse().expect("unknown AppSetting found in YAML file"));
-                }
-            }
-        } else if let Some(v) = yaml["settings"].as_str() {
-            a = a.setting(v.parse().expect("unknown AppSetting found in YAML file"));
-        } else if yaml["settings"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to a string",
-                   yaml["settings"]);
-        }
-        if let Some(v) = yaml["global_setting"].as_str() {
-            a = a.setting(v.parse().expect("unknown AppSetting found in YAML file"));
-        } else if yaml["global_setting"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to an AppSetting",
-                   yaml["setting"]);
-        }
-        if let Some(v) = yaml["global_settings"].as_vec() {
-            for ys in v {
-                if let Some(s) = ys.as_str() {
-                    a = a.global_setting(s.parse()
-                        .expect("unknown AppSetting found in YAML file"));
-                }
-            }
-        } else if let Some(v) = yaml["global_settings"].as_str() {
-            a = a.global_setting(v.parse().expect("unknown AppSetting found in YAML file"));
-        } else if yaml["global_settings"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to a string",
-                   yaml["global_settings"]);
+        if let Some(v) = node.get("global_setting") {
+            let s = v.as_str()
+                .unwrap_or_else(|| panic!("Failed to convert value {:?} to an AppSetting", v));
+            a = a.global_setting(s.parse().expect("unknown AppSetting found in app definition"));
         }
 
         macro_rules! vec_or_str {
             ($a:ident, $y:ident, $as_vec:ident, $as_single:ident) => {{
-                    let maybe_vec = $y[stringify!($as_vec)].as_vec();
-                    if let Some(vec) = maybe_vec {
-                        for ys in vec {
-                            if let Some(s) = ys.as_str() {
-                                $a = $a.$as_single(s);
-                            } else {
-                                panic!("Failed to convert YAML value {:?} to a string", ys);
+                    if let Some(v) = $y.get(stringify!($as_vec)) {
+                        if let Some(vec) = v.as_vec() {
+                            for ys in vec {
+                                if let Some(s) = ys.as_str() {
+                                    $a = $a.$as_single(s);
+                                } else {
+                                    panic!("Failed to convert value {:?} to a string", ys);
+                                }
                             }
-                        }
-                    } else {
-                        if let Some(s) = $y[stringify!($as_vec)].as_str() {
+                        } else if let Some(s) = v.as_str() {
                             $a = $a.$as_single(s);
-                        } else if $y[stringify!($as_vec)] != Yaml::BadValue {
-                            panic!("Failed to convert YAML value {:?} to either a vec or string", $y[stringify!($as_vec)]);
+                        } else {
+                            panic!("Failed to convert value {:?} to either a vec or string", v);
                         }
                     }
                     $a
@@ -1738,27 +2244,38 @@ impl<'a> From<&'a Yaml> for App<'a, 'a> {
             };
         }
 
-        a = vec_or_str!(a, yaml, aliases, alias);
-        a = vec_or_str!(a, yaml, visible_aliases, visible_alias);
+        a = vec_or_str!(a, node, settings, setting_str);
+        a = vec_or_str!(a, node, global_settings, global_setting_str);
+        a = vec_or_str!(a, node, aliases, alias);
+        a = vec_or_str!(a, node, visible_aliases, visible_alias);
 
-        if let Some(v) = yaml["args"].as_vec() {
-            for arg_yaml in v {
-                a = a.arg(Arg::from_yaml(arg_yaml.as_hash().unwrap()));
+        if let Some(v) = node.get("args").and_then(N::as_vec) {
+            for arg in v {
+                let (name, settings) = arg.entries().unwrap()[0];
+                a = a.arg(Arg::from_node(name, settings));
             }
         }
-        if let Some(v) = yaml["subcommands"].as_vec() {
-            for sc_yaml in v {
-                a = a.subcommand(SubCommand::from_yaml(sc_yaml));
+        if let Some(v) = node.get("subcommands").and_then(N::as_vec) {
+            for sc in v {
+                a = a.subcommand(App::from_node(sc));
             }
         }
-        if let Some(v) = yaml["groups"].as_vec() {
-            for ag_yaml in v {
-                a = a.group(ArgGroup::from(ag_yaml.as_hash().unwrap()));
+        if let Some(v) = node.get("groups").and_then(N::as_vec) {
+            for ag in v {
+                a = a.group(ArgGroup::from_entries(ag.entries().unwrap()));
             }
         }
 
         a
     }
+
+    fn setting_str(self, s: &str) -> Self {
+        self.setting(s.parse().expect("unknown AppSetting found in app definition"))
+    }
+
+    fn global_setting_str(self, s: &str) -> Self {
+        self.global_setting(s.parse().expect("unknown AppSetting found in app definition"))
+    }
 }
 
 impl<'a, 'b> Clone for App<'a, 'b> {
@@ -1785,10 +2302,18 @@ impl<'n, 'e> AnyArg<'n, 'e> for App<'n, 'e> {
     fn possible_vals(&self) -> Option<&[&'e str]> { None }
     fn validator(&self) -> Option<&Rc<Fn(String) -> StdResult<(), String>>> { None }
     fn validator_os(&self) -> Option<&Rc<Fn(&OsStr) -> StdResult<(), OsString>>> { None }
+    fn possible_keys(&self) -> Option<&[&'e str]> { None }
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>> { None }
+    fn value_parser(&self) -> Option<&Rc<Fn(&str) -> StdResult<Arc<Any + Send + Sync>, String>>> {
+        None
+    }
     fn min_vals(&self) -> Option<u64> { None }
+    fn checks(&self) -> &[Check] { &[] }
+    fn val_hint(&self) -> Option<&str> { None }
     fn short(&self) -> Option<char> { None }
     fn long(&self) -> Option<&'e str> { None }
     fn val_delim(&self) -> Option<char> { None }
+    fn kv_delim(&self) -> Option<char> { None }
     fn takes_value(&self) -> bool { true }
     fn help(&self) -> Option<&'e str> { self.p.meta.about }
     fn long_help(&self) -> Option<&'e str> { self.p.meta.long_about }
@@ -1796,6 +2321,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for App<'n, 'e> {
     fn default_vals_ifs(&self) -> Option<vec_map::Values<(&'n str, Option<&'e OsStr>, &'e OsStr)>> {
         None
     }
+    fn env(&self) -> Option<&'e OsStr> { None }
     fn longest_filter(&self) -> bool { true }
     fn aliases(&self) -> Option<Vec<&'e str>> {
         if let Some(ref aliases) = self.p.meta.aliases {
@@ -1,4 +1,6 @@
 // Std
+use std::cell::Cell;
+use std::env;
 use std::ffi::{OsStr, OsString};
 use std::fmt::Display;
 use std::fs::File;
@@ -6,6 +8,7 @@ use std::io::{self, BufWriter, Write};
 #[cfg(feature = "debug")]
 use std::os::unix::ffi::OsStrExt;
 use std::path::PathBuf;
+use std::rc::Rc;
 use std::slice::Iter;
 use std::iter::Peekable;
 
@@ -20,12 +23,17 @@ use app::App;
 use app::help::Help;
 use app::meta::AppMeta;
 use app::settings::AppFlags;
-use args::{AnyArg, ArgMatcher, Base, Switched, Arg, ArgGroup, FlagBuilder, OptBuilder, PosBuilder};
+use args::{AnyArg, ArgMatcher, Base, Switched, Arg, ArgGroup, FlagBuilder, OptBuilder, PosBuilder,
+           ValueSource};
 use args::settings::ArgSettings;
-use completions::ComplGen;
+use completions::{self, ComplGen};
+use config::{Config, ConfigSource, Entry};
+use manpage::ManGen;
+use docs::{DocFormat, DocGen};
 use errors::{Error, ErrorKind};
 use errors::Result as ClapResult;
 use fmt::ColorWhen;
+use messages::{English, Messages};
 use osstringext::OsStrExt2;
 use completions::Shell;
 use suggestions;
@@ -64,11 +72,17 @@ pub struct Parser<'a, 'b>
     pub r_ifs: Vec<(&'a str, &'b str, &'a str)>,
     pub blacklist: Vec<&'b str>,
     pub overrides: Vec<&'b str>,
-    help_short: Option<char>,
-    version_short: Option<char>,
+    pub help_short: Option<char>,
+    pub version_short: Option<char>,
     cache: Option<&'a str>,
     pub help_message: Option<&'a str>,
     pub version_message: Option<&'a str>,
+    pub cur_idx: Cell<usize>,
+    pub config_sources: Vec<Rc<ConfigSource>>,
+    pub config: Option<Rc<Config>>,
+    pub config_section: String,
+    // `required`, `blacklist` and `overrides` as they were before the first parse changed them
+    before_parse: Option<(Vec<&'a str>, Vec<&'b str>, Vec<&'b str>)>,
 }
 
 impl<'a, 'b> Parser<'a, 'b>
@@ -89,6 +103,9 @@ impl<'a, 'b> Parser<'a, 'b>
         self.help_short = Some(c);
     }
 
+    // Moves on to the next "clap index", see ArgMatches::index_of
+    fn next_idx(&self) { self.cur_idx.set(self.cur_idx.get() + 1); }
+
     pub fn version_short(&mut self, s: &str) {
         let c = s.trim_left_matches(|c| c == '-')
             .chars()
@@ -97,7 +114,9 @@ impl<'a, 'b> Parser<'a, 'b>
         self.version_short = Some(c);
     }
 
-    pub fn gen_completions_to<W: Write>(&mut self, for_shell: Shell, buf: &mut W) {
+    // Generators need to see the whole tree the same way the parser would, i.e. with help and
+    // version args, bin names, global args and settings present in every subcommand
+    fn propogate_all(&mut self) {
         if !self.is_set(AS::Propogated) {
             self.propogate_help_version();
             self.build_bin_names();
@@ -105,10 +124,42 @@ impl<'a, 'b> Parser<'a, 'b>
             self.propogate_settings();
             self.set(AS::Propogated);
         }
+    }
+
+    pub fn gen_completions_to<W: Write>(&mut self, for_shell: Shell, buf: &mut W) {
+        self.propogate_all();
 
         ComplGen::new(self).generate(for_shell, buf)
     }
 
+    // Answers a `__complete` request from one of the generated completion scripts
+    pub fn complete_words(&mut self, words: &[OsString]) -> Error {
+        debugln!("Parser::complete_words;");
+        self.propogate_help_version();
+        let candidates = completions::dynamic::complete(self, words);
+        Error::raw(candidates.join("\n"), ErrorKind::CompletionsDisplayed)
+    }
+
+    pub fn gen_manpage_to<W: Write>(&mut self, buf: &mut W) {
+        if self.meta.bin_name.is_none() {
+            self.meta.bin_name = Some(self.meta.name.clone());
+        }
+        self.propogate_all();
+
+        ManGen::new(self).generate_to(buf)
+    }
+
+    pub fn gen_docs_to<W: Write>(&mut self, format: DocFormat, buf: &mut W) -> ClapResult<()> {
+        if self.meta.bin_name.is_none() {
+            self.meta.bin_name = Some(self.meta.name.clone());
+        }
+        self.propogate_all();
+        // The docs list everything in the same order as --help
+        self.derive_display_order();
+
+        DocGen::new(self).generate_to(format, buf)
+    }
+
     pub fn gen_completions(&mut self, for_shell: Shell, od: OsString) {
         use std::error::Error;
 
@@ -119,6 +170,8 @@ impl<'a, 'b> Parser<'a, 'b>
             Shell::Fish => format!("{}.fish", name),
             Shell::Zsh => format!("_{}", name),
             Shell::PowerShell => format!("_{}.ps1", name),
+            Shell::Elvish => format!("{}.elv", name),
+            Shell::Nushell => format!("{}.nu", name),
         };
 
         let mut file = match File::create(out_dir.join(file_name)) {
@@ -176,7 +229,7 @@ impl<'a, 'b> Parser<'a, 'b>
         assert!(!arg_names!(self).any(|name| name == a.b.name),
                 format!("Non-unique argument name: {} is already in use", a.b.name));
         if let Some(l) = a.s.long {
-            assert!(!self.contains_long(l),
+            assert!(!self.contains_long(l) && !negated_longs!(self).any(|n| n == l),
                     "Argument long must be unique\n\n\t--{} is already in use",
                     l);
         }
@@ -211,6 +264,15 @@ impl<'a, 'b> Parser<'a, 'b>
                     "Flags or Options may not have last(true) set. {} has both a short and last(true) set.",
                     a.b.name);
         }
+        if a.b.is_set(ArgSettings::Negatable) {
+            assert!(a.s.long.is_some() && !a.is_set(ArgSettings::TakesValue),
+                    "Only flags with a long may have negatable(true) set. '{}' isn't one.",
+                    a.b.name);
+            let neg = format!("no-{}", a.s.long.unwrap());
+            assert!(!self.contains_long(&*neg),
+                    "Argument long must be unique\n\n\t--{} is already in use",
+                    neg);
+        }
         true
     }
 
@@ -394,6 +456,9 @@ impl<'a, 'b> Parser<'a, 'b>
                 sc.p.g_settings = sc.p.g_settings | self.g_settings;
                 sc.p.meta.term_w = self.meta.term_w;
                 sc.p.meta.max_w = self.meta.max_w;
+                if sc.p.meta.messages.is_none() {
+                    sc.p.meta.messages = self.meta.messages.clone();
+                }
             }
             sc.p.propogate_settings();
         }
@@ -617,8 +682,12 @@ impl<'a, 'b> Parser<'a, 'b>
             // We have to create a new scope in order to tell rustc the borrow of `sc` is
             // done and to recursively call this method
             {
+                // Parsing again with the same App propagates them again, so skip the ones the
+                // subcommand already got
                 for a in &self.global_args {
-                    sc.p.add_arg_ref(a);
+                    if !sc.p.global_args.iter().any(|g| g.b.name == a.b.name) {
+                        sc.p.add_arg_ref(a);
+                    }
                 }
             }
             sc.p.propogate_globals();
@@ -831,6 +900,18 @@ impl<'a, 'b> Parser<'a, 'b>
         // necessary
         self.create_help_and_version();
 
+        // Forget what a previous parse with the same App found
+        self.unset(AS::ValidArgFound);
+        self.unset(AS::TrailingValues);
+        if let Some((r, b, o)) = self.before_parse.clone() {
+            self.required = r;
+            self.blacklist = b;
+            self.overrides = o;
+        } else {
+            self.before_parse =
+                Some((self.required.clone(), self.blacklist.clone(), self.overrides.clone()));
+        }
+
         let mut subcmd_name: Option<String> = None;
         let mut needs_val_of: ParseResult<'a> = ParseResult::NotFound;
         let mut pos_counter = 1;
@@ -839,6 +920,7 @@ impl<'a, 'b> Parser<'a, 'b>
             debugln!("Parser::get_matches_with: Begin parsing '{:?}' ({:?})",
                      arg_os,
                      &*arg_os.as_bytes());
+            self.next_idx();
 
             self.unset(AS::ValidNegNumFound);
             // Is this a new argument, or values from a previous option?
@@ -906,7 +988,7 @@ impl<'a, 'b> Parser<'a, 'b>
                                 if !(arg_os.to_string_lossy().parse::<i64>().is_ok() ||
                                      arg_os.to_string_lossy().parse::<f64>().is_ok()) {
                                     return Err(Error::unknown_argument(&*arg_os.to_string_lossy(),
-                                        "",
+                                        None,
                                         &*usage::create_error_usage(self, matcher, None),
                                         self.color()));
                                 }
@@ -980,7 +1062,7 @@ impl<'a, 'b> Parser<'a, 'b>
             if let Some(p) = self.positionals.get(pos_counter) {
                 if p.is_set(ArgSettings::Last) && !self.is_set(AS::TrailingValues) {
                     return Err(Error::unknown_argument(&*arg_os.to_string_lossy(),
-                                                       "",
+                                                       None,
                                                        &*usage::create_error_usage(self,
                                                                                    matcher,
                                                                                    None),
@@ -1013,7 +1095,9 @@ impl<'a, 'b> Parser<'a, 'b>
                                                                                    None),
                                                        self.color()));
                     }
+                    self.next_idx();
                     sc_m.add_val_to("", &a);
+                    sc_m.add_index_to("", self.cur_idx.get());
                 }
 
                 matcher.subcommand(SubCommand {
@@ -1024,7 +1108,7 @@ impl<'a, 'b> Parser<'a, 'b>
                         self.is_set(AS::AllowNegativeNumbers)) &&
                       !self.is_set(AS::InferSubcommands) {
                 return Err(Error::unknown_argument(&*arg_os.to_string_lossy(),
-                                                   "",
+                                                   None,
                                                    &*usage::create_error_usage(self,
                                                                                matcher,
                                                                                None),
@@ -1074,11 +1158,8 @@ impl<'a, 'b> Parser<'a, 'b>
             debugln!("Parser::get_matches_with: SubcommandRequiredElseHelp=true");
             let mut out = vec![];
             try!(self.write_help_err(&mut out));
-            return Err(Error {
-                           message: String::from_utf8_lossy(&*out).into_owned(),
-                           kind: ErrorKind::MissingArgumentOrSubcommand,
-                           info: None,
-                       });
+            return Err(Error::raw(String::from_utf8_lossy(&*out).into_owned(),
+                                  ErrorKind::MissingArgumentOrSubcommand));
         }
 
         Validator::new(self).validate(needs_val_of, subcmd_name, matcher)
@@ -1172,6 +1253,14 @@ impl<'a, 'b> Parser<'a, 'b>
             debugln!("Parser::parse_subcommand: About to parse sc={}",
                      sc.p.meta.name);
             debugln!("Parser::parse_subcommand: sc settings={:#?}", sc.p.settings);
+            // The subcommand continues counting from its own name's position
+            sc.p.cur_idx.set(self.cur_idx.get());
+            sc.p.config = self.config.clone();
+            sc.p.config_section = if self.config_section.is_empty() {
+                sc.p.meta.name.clone()
+            } else {
+                format!("{}.{}", self.config_section, sc.p.meta.name)
+            };
             try!(sc.p.get_matches_with(&mut sc_matcher, it));
             matcher.subcommand(SubCommand {
                                    name: sc.p.meta.name.clone(),
@@ -1363,11 +1452,7 @@ impl<'a, 'b> Parser<'a, 'b>
             Err(e) => return e,
             _ => (),
         }
-        Error {
-            message: unsafe { String::from_utf8_unchecked(buf) },
-            kind: ErrorKind::HelpDisplayed,
-            info: None,
-        }
+        Error::raw(unsafe { String::from_utf8_unchecked(buf) }, ErrorKind::HelpDisplayed)
     }
 
     fn _version(&self, use_long: bool) -> Error {
@@ -1378,11 +1463,7 @@ impl<'a, 'b> Parser<'a, 'b>
             Err(e) => return e,
             _ => (),
         }
-        Error {
-            message: String::new(),
-            kind: ErrorKind::VersionDisplayed,
-            info: None,
-        }
+        Error::raw(String::new(), ErrorKind::VersionDisplayed)
     }
 
     fn parse_long_arg(&mut self,
@@ -1430,6 +1511,15 @@ impl<'a, 'b> Parser<'a, 'b>
             // self.cache = Some(flag.b.name);
             // }
 
+            return Ok(ParseResult::Flag);
+        } else if let Some(name) = self.negated_flag(arg) {
+            debugln!("Parser::parse_long_arg: Found negated flag '{}'", name);
+            self.settings.set(AS::ValidArgFound);
+            // The last of --flag and --no-flag wins
+            if matcher.contains(name) {
+                self.forget_flag(name, matcher);
+            }
+            matcher.negate(name);
             return Ok(ParseResult::Flag);
         } else if self.is_set(AS::AllowLeadingHyphen) {
             return Ok(ParseResult::MaybeHyphenValue);
@@ -1467,8 +1557,12 @@ impl<'a, 'b> Parser<'a, 'b>
         }
 
         let mut ret = ParseResult::NotFound;
-        for c in arg.chars() {
+        for (i, c) in arg.chars().enumerate() {
             debugln!("Parser::parse_short_arg:iter:{}", c);
+            // Every short after the first in a combined group (-fzF) gets its own index
+            if i != 0 {
+                self.next_idx();
+            }
             // Check for matching short options, and return the name if there is no trailing
             // concatenated value: -oval
             // Option: -o
@@ -1518,7 +1612,7 @@ impl<'a, 'b> Parser<'a, 'b>
             } else {
                 let arg = format!("-{}", c);
                 return Err(Error::unknown_argument(&*arg,
-                                                   "",
+                                                   None,
                                                    &*usage::create_error_usage(self,
                                                                                matcher,
                                                                                None),
@@ -1549,6 +1643,8 @@ impl<'a, 'b> Parser<'a, 'b>
                                               &*usage::create_error_usage(self, matcher, None),
                                               self.color()));
             }
+            // An attached value (--opt=val or -oval) sits one index after the opt itself
+            self.next_idx();
             sdebugln!("Found - {:?}, len: {}", v, v.len_());
             debugln!("Parser::parse_opt: {:?} contains '='...{:?}",
                      fv,
@@ -1598,7 +1694,10 @@ impl<'a, 'b> Parser<'a, 'b>
                 if val.is_empty_() {
                     iret = try!(self.add_single_val_to_arg(arg, val, matcher));
                 } else {
-                    for v in val.split(delim as u32 as u8) {
+                    for (i, v) in val.split(delim as u32 as u8).enumerate() {
+                        if i != 0 {
+                            self.next_idx();
+                        }
                         iret = try!(self.add_single_val_to_arg(arg, v, matcher));
                     }
                     // If there was a delimiter used, we're not looking for more values
@@ -1632,6 +1731,7 @@ impl<'a, 'b> Parser<'a, 'b>
             }
         }
         matcher.add_val_to(arg.name(), v);
+        matcher.add_index_to(arg.name(), self.cur_idx.get());
 
         // Increment or create the group "args"
         if let Some(grps) = self.groups_for_arg(arg.name()) {
@@ -1654,6 +1754,10 @@ impl<'a, 'b> Parser<'a, 'b>
         debugln!("Parser::parse_flag;");
 
         matcher.inc_occurrence_of(flag.b.name);
+        matcher.add_index_to(flag.b.name, self.cur_idx.get());
+        if flag.b.is_set(ArgSettings::Negatable) {
+            matcher.unnegate(flag.b.name);
+        }
         // Increment or create the group "args"
         self.groups_for_arg(flag.b.name)
             .and_then(|vec| Some(matcher.inc_occurrences_of(&*vec)));
@@ -1661,15 +1765,71 @@ impl<'a, 'b> Parser<'a, 'b>
         Ok(ParseResult::Flag)
     }
 
+    // Takes back what finding the negatable flag `name` did, for a `--no-<long>` after it
+    fn forget_flag(&mut self, name: &'a str, matcher: &mut ArgMatcher<'a>) {
+        debugln!("Parser::forget_flag: name={}", name);
+        let occurs = matcher.get(name).map_or(0, |ma| ma.occurs);
+        matcher.remove(name);
+        remove_overriden!(self, &name);
+        let initial_reqs = self.before_parse.as_ref().map_or(vec![], |s| s.0.clone());
+        for grp in self.groups.iter().filter(|g| g.args.contains(&name)) {
+            let left = match matcher.get_mut(grp.name) {
+                Some(ma) => {
+                    ma.occurs = ma.occurs.saturating_sub(occurs);
+                    ma.occurs
+                }
+                None => 0,
+            };
+            if left == 0 {
+                matcher.remove(grp.name);
+            }
+            // Other args may have added the same names, so only one of each is removed
+            for r in grp.requires.iter().flat_map(|reqs| reqs.iter()) {
+                remove_one(&mut self.required, r);
+            }
+            for c in grp.conflicts.iter().flat_map(|bl| bl.iter()) {
+                remove_one(&mut self.blacklist, c);
+            }
+            if !grp.multiple {
+                for a in grp.args.iter().filter(|&&a| a != name) {
+                    remove_one(&mut self.blacklist, a);
+                }
+            }
+            // Finding an arg of the group made the others no longer required, so the ones which
+            // still are need adding back
+            for &a in grp.args.iter().filter(|&&a| a != name) {
+                let required_by_matched = matcher.iter().any(|(n, _)| {
+                    self.find_any_arg(n)
+                        .and_then(|arg| arg.requires())
+                        .map_or(false, |reqs| reqs.iter().any(|&(v, r)| v.is_none() && r == a))
+                });
+                if (initial_reqs.contains(&a) || required_by_matched) &&
+                   !matcher.contains(a) && !self.required.contains(&a) {
+                    self.required.push(a);
+                }
+            }
+        }
+    }
+
+    // Finds the negatable flag turned off by `--no-<long>`, given the `no-<long>`
+    fn negated_flag(&self, arg: &OsStr) -> Option<&'a str> {
+        if !arg.starts_with(b"no-") {
+            return None;
+        }
+        let (_, long) = arg.split_at(3);
+        self.flags
+            .iter()
+            .filter(|f| f.b.is_set(ArgSettings::Negatable))
+            .find(|f| f.s.long.map_or(false, |l| OsStr::new(l) == long))
+            .map(|f| f.b.name)
+    }
+
     fn did_you_mean_error(&self, arg: &str, matcher: &mut ArgMatcher<'a>) -> ClapResult<()> {
         // Didn't match a flag or option...maybe it was a typo and close to one
-        let suffix =
-            suggestions::did_you_mean_suffix(arg,
-                                             longs!(self),
-                                             suggestions::DidYouMeanMessageStyle::LongFlag);
+        let suggestion = suggestions::did_you_mean(arg, longs!(self));
 
         // Add the arg to the matches to build a proper usage string
-        if let Some(name) = suffix.1 {
+        if let Some(name) = suggestion {
             if let Some(opt) = find_opt_by_long!(self, &name) {
                 self.groups_for_arg(&*opt.b.name)
                     .and_then(|grps| Some(matcher.inc_occurrences_of(&*grps)));
@@ -1683,7 +1843,7 @@ impl<'a, 'b> Parser<'a, 'b>
 
         let used_arg = format!("--{}", arg);
         Err(Error::unknown_argument(&*used_arg,
-                                    &*suffix.0,
+                                    suggestion.map(|name| format!("--{}", name)),
                                     &*usage::create_error_usage(self, matcher, None),
                                     self.color()))
     }
@@ -1736,10 +1896,53 @@ impl<'a, 'b> Parser<'a, 'b>
 
     pub fn add_defaults(&mut self, matcher: &mut ArgMatcher<'a>) -> ClapResult<()> {
         macro_rules! add_val {
+            (@env $_self:ident, $a:ident, $m:ident) => {
+                if let Some(name) = $a.v.env {
+                    if $m.get($a.b.name).is_none() {
+                        if let Some(ref val) = env::var_os(name) {
+                            try!($_self.add_val_to_arg($a, val, $m));
+                            $m.set_source($a.b.name, ValueSource::Environment);
+
+                            if $_self.cache.map_or(true, |name| name != $a.name()) {
+                                arg_post_processing!($_self, $a, $m);
+                                $_self.cache = Some($a.name());
+                            }
+                            continue; // outer loop (outside macro)
+                        }
+                    }
+                }
+            };
+            (@config $_self:ident, $a:ident, $m:ident) => {
+                if $m.get($a.b.name).is_none() {
+                    if let Some(entry) = $_self.config_entry($a) {
+                        if entry.vals.len() > 1 && !$a.is_set(ArgSettings::Multiple) &&
+                           $a.num_vals().is_none() {
+                            let usage = usage::create_error_usage($_self, $m, None);
+                            return Err(entry.error(Error::too_many_values(&*entry.vals[1],
+                                                                          $a,
+                                                                          &*usage,
+                                                                          $_self.color())));
+                        }
+                        for val in &entry.vals {
+                            if let Err(e) = $_self.add_val_to_arg($a, OsStr::new(val), $m) {
+                                return Err(entry.error(e));
+                            }
+                        }
+                        $m.set_source($a.b.name, ValueSource::Config);
+
+                        if $_self.cache.map_or(true, |name| name != $a.name()) {
+                            arg_post_processing!($_self, $a, $m);
+                            $_self.cache = Some($a.name());
+                        }
+                        continue; // outer loop (outside macro)
+                    }
+                }
+            };
             (@default $_self:ident, $a:ident, $m:ident) => {
                 if let Some(ref val) = $a.v.default_val {
                     if $m.get($a.b.name).is_none() {
                         try!($_self.add_val_to_arg($a, OsStr::new(val), $m));
+                        $m.set_source($a.b.name, ValueSource::DefaultValue);
 
                         if $_self.cache.map_or(true, |name| name != $a.name()) {
                             arg_post_processing!($_self, $a, $m);
@@ -1749,6 +1952,8 @@ impl<'a, 'b> Parser<'a, 'b>
                 }
             };
             ($_self:ident, $a:ident, $m:ident) => {
+                add_val!(@env $_self, $a, $m);
+                add_val!(@config $_self, $a, $m);
                 if let Some(ref vm) = $a.v.default_vals_ifs {
                     let mut done = false;
                     if $m.get($a.b.name).is_none() {
@@ -1764,6 +1969,7 @@ impl<'a, 'b> Parser<'a, 'b>
                             };
                             if add {
                                 try!($_self.add_val_to_arg($a, OsStr::new(default), $m));
+                                $m.set_source($a.b.name, ValueSource::ConditionalDefault);
                                 if $_self.cache.map_or(true, |name| name != $a.name()) {
                                     arg_post_processing!($_self, $a, $m);
                                     $_self.cache = Some($a.name());
@@ -1782,6 +1988,23 @@ impl<'a, 'b> Parser<'a, 'b>
             };
         }
 
+        for f in &self.flags {
+            if matcher.get(f.b.name).is_some() {
+                continue;
+            }
+            if let Some(entry) = self.config_entry(f) {
+                for _ in 0..try!(self.flag_occurrences(f, &entry)) {
+                    matcher.inc_occurrence_of(f.b.name);
+                    self.groups_for_arg(f.b.name)
+                        .and_then(|vec| Some(matcher.inc_occurrences_of(&*vec)));
+                }
+                matcher.set_source(f.b.name, ValueSource::Config);
+                if matcher.contains(f.b.name) && self.cache.map_or(true, |name| name != f.name()) {
+                    arg_post_processing!(self, f, matcher);
+                    self.cache = Some(f.name());
+                }
+            }
+        }
         for o in &self.opts {
             add_val!(self, o, matcher);
         }
@@ -1791,6 +2014,91 @@ impl<'a, 'b> Parser<'a, 'b>
         Ok(())
     }
 
+    // Reads the config sources given to App::config_source, making sure every section and key
+    // in them belongs to a subcommand and argument
+    pub fn load_config(&mut self) -> ClapResult<()> {
+        debugln!("Parser::load_config;");
+        if self.config_sources.is_empty() {
+            return Ok(());
+        }
+        let config = try!(Config::load(&*self.config_sources, self.color()));
+        for (section, entries) in &config.sections {
+            let mut p: &Parser = self;
+            for name in section.split('.').filter(|s| !s.is_empty()) {
+                p = match p.subcommands.iter().find(|sc| sc.p.meta.name == name) {
+                    Some(sc) => &sc.p,
+                    None => {
+                        let e = &entries[0];
+                        return Err(Error::config_entry(format!("Found config section '[{}]' \
+                                                                which doesn't match any \
+                                                                subcommand",
+                                                               section),
+                                                       ErrorKind::UnrecognizedSubcommand,
+                                                       &*e.source,
+                                                       e.line,
+                                                       self.color()));
+                    }
+                };
+            }
+            for e in entries {
+                let key = &*e.key;
+                let found = p.flags.iter().any(|f| f.s.long == Some(key) || f.b.name == key) ||
+                            p.opts.iter().any(|o| o.s.long == Some(key) || o.b.name == key) ||
+                            p.positionals.values().any(|pos| pos.b.name == key);
+                if !found {
+                    return Err(Error::config_entry(format!("Found config key '{}' which doesn't \
+                                                            match any argument",
+                                                           key),
+                                                   ErrorKind::UnknownArgument,
+                                                   &*e.source,
+                                                   e.line,
+                                                   self.color()));
+                }
+            }
+        }
+        self.config = Some(Rc::new(config));
+        self.config_section = String::new();
+        Ok(())
+    }
+
+    // The config entry for an argument, keyed by its long if it has one, otherwise by its name
+    pub fn config_entry<A>(&self, a: &A) -> Option<Entry>
+        where A: AnyArg<'a, 'b>
+    {
+        let config = match self.config {
+            Some(ref c) => c,
+            None => return None,
+        };
+        a.long()
+            .and_then(|l| config.get(&*self.config_section, l))
+            .or_else(|| config.get(&*self.config_section, a.name()))
+            .cloned()
+    }
+
+    // Flags take `true`, `false` or a number of occurrences from a config file
+    fn flag_occurrences(&self, f: &FlagBuilder<'a, 'b>, entry: &Entry) -> ClapResult<u64> {
+        let val = if entry.vals.len() == 1 {
+            &*entry.vals[0]
+        } else {
+            ""
+        };
+        match val {
+            "true" => Ok(1),
+            "false" => Ok(0),
+            v => {
+                v.parse::<u64>().map_err(|_| {
+                    Error::config_entry(format!("Invalid value for '{}' in config file, \
+                                                 expected true, false or a number",
+                                                f),
+                                        ErrorKind::InvalidValue,
+                                        &*entry.source,
+                                        entry.line,
+                                        self.color())
+                })
+            }
+        }
+    }
+
     pub fn flags(&self) -> Iter<FlagBuilder<'a, 'b>> { self.flags.iter() }
 
     pub fn opts(&self) -> Iter<OptBuilder<'a, 'b>> { self.opts.iter() }
@@ -1799,6 +2107,15 @@ impl<'a, 'b> Parser<'a, 'b>
 
     pub fn subcommands(&self) -> Iter<App> { self.subcommands.iter() }
 
+    // The headings and error messages set with App::messages, or English ones
+    #[doc(hidden)]
+    pub fn messages(&self) -> &Messages {
+        match self.meta.messages {
+            Some(ref m) => &**m,
+            None => &English,
+        }
+    }
+
     // Should we color the output? None=determined by output location, true=yes, false=no
     #[doc(hidden)]
     pub fn color(&self) -> ColorWhen {
@@ -1862,3 +2179,10 @@ impl<'a, 'b> Parser<'a, 'b>
     #[inline]
     fn contains_short(&self, s: char) -> bool { shorts!(self).any(|arg_s| arg_s == &s) }
 }
+
+// Removes one `name` from `v`, leaving any others
+fn remove_one(v: &mut Vec<&str>, name: &str) {
+    if let Some(i) = v.iter().position(|&n| n == name) {
+        v.swap_remove(i);
+    }
+}
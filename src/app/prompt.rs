@@ -0,0 +1,158 @@
+// Std
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+#[cfg(unix)]
+use std::process::{Command, Stdio};
+
+// Internal
+use args::AnyArg;
+use args::settings::ArgSettings;
+use messages::Messages;
+
+// Asks for the value of `arg` on `out` until `input` gives a valid one, telling the user what's
+// wrong with the ones that aren't. Returns `None` at the end of `input`.
+pub fn ask<'a, 'b, A, R, W>(arg: &A,
+                            m: &Messages,
+                            hide_input: bool,
+                            input: &mut R,
+                            out: &mut W)
+                            -> io::Result<Option<String>>
+    where A: AnyArg<'a, 'b> + Display,
+          R: BufRead,
+          W: Write
+{
+    debugln!("prompt::ask: arg={}", arg.name());
+    loop {
+        match arg.help() {
+            Some(h) => try!(write!(out, "{}", h)),
+            None => try!(write!(out, "{}", arg.name())),
+        }
+        if let Some(p_vals) = arg.possible_vals() {
+            try!(write!(out, " [{}]", p_vals.join(", ")));
+        }
+        try!(write!(out, ": "));
+        try!(out.flush());
+
+        let mut line = String::new();
+        if hide_input {
+            set_echo(false);
+        }
+        let read = input.read_line(&mut line);
+        if hide_input {
+            set_echo(true);
+            // The newline the user typed wasn't echoed either
+            try!(writeln!(out, ""));
+        }
+        if try!(read) == 0 {
+            return Ok(None);
+        }
+        let val = line.trim_right_matches(|c| c == '\n' || c == '\r');
+        match check(arg, m, val) {
+            Ok(()) => return Ok(Some(val.to_owned())),
+            Err(e) => try!(writeln!(out, "{}", e)),
+        }
+    }
+}
+
+// Checks `val` the way the validator checks values given on the command line
+fn check<'a, 'b, A>(arg: &A, m: &Messages, val: &str) -> Result<(), String>
+    where A: AnyArg<'a, 'b> + Display
+{
+    let name = arg.to_string();
+    if val.is_empty() && !arg.is_set(ArgSettings::EmptyValues) {
+        return Err(m.empty_value(&*name));
+    }
+    if let Some(p_vals) = arg.possible_vals() {
+        if !p_vals.contains(&val) {
+            return Err(m.invalid_value(val, &*name, &*p_vals.join(", ")));
+        }
+    }
+    if let Some(vtor) = arg.validator() {
+        try!(vtor(val.to_owned()).map_err(|e| m.value_validation(Some(&*name), &*e)));
+    }
+    if let Some(parser) = arg.value_parser() {
+        try!(parser(val).map_err(|e| m.value_validation(Some(&*name), &*e)));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_echo(on: bool) {
+    // Failing to hide the input isn't worth failing over, the value is still read
+    let _ = Command::new("stty")
+        .arg(if on { "echo" } else { "-echo" })
+        .stdin(Stdio::inherit())
+        .status();
+}
+
+#[cfg(not(unix))]
+fn set_echo(_: bool) {}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::ask;
+    use args::{Arg, OptBuilder};
+    use messages::English;
+
+    fn answer(arg: Arg, input: &str) -> (Option<String>, String) {
+        let o = OptBuilder::from(&arg);
+        let mut out = vec![];
+        let val = ask(&o, &English, false, &mut Cursor::new(input), &mut out).unwrap();
+        (val, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn prompt_with_help() {
+        let arg = Arg::with_name("user").long("user").help("The user");
+        assert_eq!(answer(arg, "kevin\n"),
+                   (Some("kevin".to_owned()), "The user: ".to_owned()));
+    }
+
+    #[test]
+    fn prompt_without_help() {
+        let arg = Arg::with_name("user").long("user");
+        assert_eq!(answer(arg, "kevin\r\n"),
+                   (Some("kevin".to_owned()), "user: ".to_owned()));
+    }
+
+    #[test]
+    fn asks_again_for_possible_values() {
+        let arg = Arg::with_name("color").long("color").possible_values(&["auto", "never"]);
+        let (val, out) = answer(arg, "alwys\nnever\n");
+        assert_eq!(val, Some("never".to_owned()));
+        assert_eq!(out,
+                   "color [auto, never]: \
+                    'alwys' isn't a valid value for '--color <color>'\n\
+                    \t[values: auto, never]\n\
+                    color [auto, never]: ");
+    }
+
+    #[test]
+    fn asks_again_for_validator() {
+        let arg = Arg::with_name("jobs")
+            .long("jobs")
+            .validator(|v| v.parse::<u8>().map(|_| ()).map_err(|e| e.to_string()));
+        let (val, out) = answer(arg, "four\n4\n");
+        assert_eq!(val, Some("4".to_owned()));
+        assert_eq!(out,
+                   "jobs: Invalid value for '--jobs <jobs>': invalid digit found in string\n\
+                    jobs: ");
+    }
+
+    #[test]
+    fn asks_again_for_empty_values() {
+        let arg = Arg::with_name("user").long("user").empty_values(false);
+        let (val, out) = answer(arg, "\nkevin\n");
+        assert_eq!(val, Some("kevin".to_owned()));
+        assert!(out.contains("requires a value but none was supplied"));
+    }
+
+    #[test]
+    fn end_of_input() {
+        let arg = Arg::with_name("color").long("color").possible_values(&["auto", "never"]);
+        assert_eq!(answer(arg, "alwys\n").0, None);
+        assert_eq!(answer(Arg::with_name("user").long("user"), "").0, None);
+    }
+}
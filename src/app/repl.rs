@@ -0,0 +1,146 @@
+// Std
+use std::io::{self, BufRead, Write};
+use std::iter;
+
+// Internal
+use app::{App, AppSettings};
+use app::shell_words;
+use args::ArgMatches;
+use errors::{ErrorKind, Result as ClapResult};
+
+/// Runs command lines read one per line through the same [`App`], such as for an interactive
+/// console. Created with [`App::repl`] or [`App::repl_from`].
+///
+/// Each line is split into arguments the way a POSIX shell would (see
+/// [`App::get_matches_from_str`]), so values can be quoted, and parsed as if it followed the
+/// binary name (unless [`AppSettings::NoBinaryName`] is used). Blank lines and comments are
+/// skipped. It's an [`Iterator`] over the results, which ends at the end of the input.
+///
+/// Errors are returned rather than exiting, so the caller can print them and carry on with the
+/// next line. That includes [`ErrorKind::HelpDisplayed`] for `--help`, whose message is the help,
+/// and [`ErrorKind::VersionDisplayed`] for `--version`, which has already been printed.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ErrorKind, SubCommand};
+/// # use std::io::Cursor;
+/// let input = Cursor::new("add 'a file.txt'\n\nadd --help\nrm -f a\n");
+/// let app = App::new("console")
+///     .subcommand(SubCommand::with_name("add").arg(Arg::with_name("file")))
+///     .subcommand(SubCommand::with_name("rm")
+///         .arg(Arg::with_name("force").short("f"))
+///         .arg(Arg::with_name("file")));
+///
+/// let mut repl = app.repl_from(input);
+///
+/// let m = repl.next().unwrap().unwrap();
+/// assert_eq!(m.subcommand_matches("add").unwrap().value_of("file"), Some("a file.txt"));
+///
+/// let err = repl.next().unwrap().unwrap_err();
+/// assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+///
+/// let m = repl.next().unwrap().unwrap();
+/// assert!(m.subcommand_matches("rm").unwrap().is_present("force"));
+///
+/// assert!(repl.next().is_none());
+/// ```
+/// [`App`]: ./struct.App.html
+/// [`App::repl`]: ./struct.App.html#method.repl
+/// [`App::repl_from`]: ./struct.App.html#method.repl_from
+/// [`App::get_matches_from_str`]: ./struct.App.html#method.get_matches_from_str
+/// [`AppSettings::NoBinaryName`]: ./enum.AppSettings.html#variant.NoBinaryName
+/// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+/// [`ErrorKind::HelpDisplayed`]: ./enum.ErrorKind.html#variant.HelpDisplayed
+/// [`ErrorKind::VersionDisplayed`]: ./enum.ErrorKind.html#variant.VersionDisplayed
+#[allow(missing_debug_implementations)]
+pub struct Repl<'a, 'b, R>
+    where 'a: 'b
+{
+    app: App<'a, 'b>,
+    input: R,
+    prompt: Option<String>,
+    done: bool,
+}
+
+impl<'a, 'b, R: BufRead> Repl<'a, 'b, R> {
+    #[doc(hidden)]
+    pub fn new(app: App<'a, 'b>, input: R) -> Self {
+        Repl {
+            app: app,
+            input: input,
+            prompt: None,
+            done: false,
+        }
+    }
+
+    /// Prints `prompt` to `stdout` before reading each line.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// for res in App::new("console").repl().prompt("> ") {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn prompt<S: Into<String>>(mut self, prompt: S) -> Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// The [`App`] the lines are parsed with, such as to print its help.
+    ///
+    /// [`App`]: ./struct.App.html
+    pub fn app(&mut self) -> &mut App<'a, 'b> { &mut self.app }
+
+    // Reads the next line which isn't blank (or only a comment), split into arguments
+    fn read_args(&mut self) -> ClapResult<Option<Vec<String>>> {
+        loop {
+            if let Some(ref p) = self.prompt {
+                let mut out = io::stdout();
+                try!(write!(out, "{}", p));
+                try!(out.flush());
+            }
+            let mut line = String::new();
+            if try!(self.input.read_line(&mut line)) == 0 {
+                return Ok(None);
+            }
+            let args = try!(shell_words::split(&*line, self.app.p.color()));
+            if !args.is_empty() {
+                return Ok(Some(args));
+            }
+        }
+    }
+}
+
+impl<'a, 'b, R: BufRead> Iterator for Repl<'a, 'b, R> {
+    type Item = ClapResult<ArgMatches<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let args = match self.read_args() {
+            Ok(Some(args)) => args,
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                // A line which couldn't be split is skipped, but reading may fail again
+                self.done = e.kind == ErrorKind::Io;
+                return Some(Err(self.app.finish_error(e)));
+            }
+        };
+        debugln!("Repl::next: args={:?}", args);
+        if self.app.p.is_set(AppSettings::NoBinaryName) {
+            Some(self.app.get_matches_from_safe_borrow(args))
+        } else {
+            let bin_name = self.app.p.meta.bin_name.clone().unwrap_or_else(|| {
+                self.app.p.meta.name.clone()
+            });
+            Some(self.app.get_matches_from_safe_borrow(iter::once(bin_name).chain(args)))
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Std
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// Internal
+use errors::{Error, Result as ClapResult};
+use fmt::ColorWhen;
+use osstringext::OsStrExt2;
+
+// Replaces every `@path` in `args` with the arguments found in that file, and every `@@literal`
+// with `@literal`
+pub fn expand<I, T>(args: I, color: ColorWhen) -> ClapResult<Vec<OsString>>
+    where I: Iterator<Item = T>,
+          T: Into<OsString>
+{
+    let mut ret = vec![];
+    let mut stack = vec![];
+    for arg in args {
+        try!(expand_arg(arg.into(), &mut stack, &mut ret, color));
+    }
+    Ok(ret)
+}
+
+// `stack` holds the response files currently being expanded, so one that ends up including
+// itself (directly or not) is reported rather than recursing forever
+fn expand_arg(arg: OsString,
+              stack: &mut Vec<PathBuf>,
+              ret: &mut Vec<OsString>,
+              color: ColorWhen)
+              -> ClapResult<()> {
+    // A lone `@` is left alone, it commonly means stdin
+    if !arg.starts_with(b"@") || arg.len_() == 1 {
+        ret.push(arg);
+        return Ok(());
+    }
+    let (_, rest) = arg.split_at(1);
+    if rest.starts_with(b"@") {
+        debugln!("response_files::expand_arg: escaped literal {:?}", rest);
+        ret.push(rest.to_os_string());
+        return Ok(());
+    }
+
+    let path = Path::new(rest);
+    debugln!("response_files::expand_arg: path={:?}", path);
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if stack.contains(&canonical) {
+        return Err(Error::response_file(path.display(), "it includes itself", color));
+    }
+    let mut contents = String::new();
+    if let Err(e) = File::open(path).and_then(|mut f| f.read_to_string(&mut contents)) {
+        return Err(Error::response_file(path.display(), e, color));
+    }
+
+    stack.push(canonical);
+    for word in split(&*contents) {
+        try!(expand_arg(word.into(), stack, ret, color));
+    }
+    stack.pop();
+    Ok(())
+}
+
+// Splits the contents of a response file into arguments the same way gcc does. Arguments are
+// separated by whitespace, which can be kept by quoting with `'` or `"`, or escaping with `\`.
+// Inside single quotes everything is literal, inside double quotes `\` only escapes `"` and `\`.
+// An unterminated quote runs to the end of the file.
+fn split(s: &str) -> Vec<String> {
+    let mut ret = vec![];
+    let mut cur = String::new();
+    // Whether an argument has been started, since `""` is an argument in its own right
+    let mut started = false;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if started {
+                    ret.push(cur);
+                    cur = String::new();
+                    started = false;
+                }
+            }
+            '\'' => {
+                started = true;
+                while let Some(c) = chars.next() {
+                    if c == '\'' {
+                        break;
+                    }
+                    cur.push(c);
+                }
+            }
+            '"' => {
+                started = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if chars.peek() == Some(&'"') || chars.peek() == Some(&'\\') => {
+                            cur.push(chars.next().unwrap());
+                        }
+                        c => cur.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                started = true;
+                if let Some(c) = chars.next() {
+                    cur.push(c);
+                }
+            }
+            c => {
+                started = true;
+                cur.push(c);
+            }
+        }
+    }
+    if started {
+        ret.push(cur);
+    }
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::split;
+
+    #[test]
+    fn split_whitespace() {
+        assert_eq!(split("  -a\tfoo\n\n--bar  baz "), &["-a", "foo", "--bar", "baz"]);
+        assert!(split(" \n ").is_empty());
+    }
+
+    #[test]
+    fn split_quotes() {
+        assert_eq!(split("'a b' \"c d\" e'f g'h \"\""), &["a b", "c d", "ef gh", ""]);
+        assert_eq!(split("'a\\b' \"c\\\"d\\\\e\\f\""), &["a\\b", "c\"d\\e\\f"]);
+        assert_eq!(split("\"unterminated arg"), &["unterminated arg"]);
+    }
+
+    #[test]
+    fn split_escapes() {
+        assert_eq!(split("a\\ b c\\'d"), &["a b", "c'd"]);
+    }
+}
@@ -45,6 +45,9 @@ bitflags! {
         const VALID_ARG_FOUND      = 1 << 37,
         const INFER_SUBCOMMANDS    = 1 << 38,
         const CONTAINS_LAST        = 1 << 39,
+        const RESPONSE_FILES       = 1 << 40,
+        const JSON_ERRORS          = 1 << 41,
+        const PROMPT_FOR_MISSING   = 1 << 42,
     }
 }
 
@@ -92,6 +95,7 @@ impl AppFlags {
         NeedsSubcommandHelp => NEEDS_SC_HELP,
         NoBinaryName => NO_BIN_NAME,
         PropagateGlobalValuesDown=> PROPAGATE_VALS_DOWN,
+        ResponseFiles => RESPONSE_FILES,
         StrictUtf8 => UTF8_STRICT,
         SubcommandsNegateReqs => SC_NEGATE_REQS,
         SubcommandRequired => SC_REQUIRED,
@@ -106,6 +110,8 @@ impl AppFlags {
         Propogated => PROPOGATED,
         ValidArgFound => VALID_ARG_FOUND,
         InferSubcommands => INFER_SUBCOMMANDS,
+        JsonErrors => JSON_ERRORS,
+        PromptForMissing => PROMPT_FOR_MISSING,
         ContainsLast => CONTAINS_LAST
     }
 }
@@ -558,6 +564,70 @@ pub enum AppSettings {
     /// [`AppSeettings::ArgsNegateSubcommands`]: ./enum.AppSettings.html#variant.ArgsNegateSubcommands
     InferSubcommands,
 
+    /// Makes [`Error::exit`] write errors to `stderr` as a single line of JSON instead of the
+    /// usual message, for when the program is driven by another program such as an IDE or a CI
+    /// bot. Help, version and completion output is still printed as usual.
+    ///
+    /// The object has the fields `kind`, `message` (without colors), `arg`, `value`,
+    /// `valid_values`, `suggestion` and `usage`, any of which are `null` when they don't apply to
+    /// the error. See [`Error::to_json`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// let m = App::new("prog")
+    ///     .setting(AppSettings::JsonErrors)
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .takes_value(true)
+    ///         .possible_values(&["always", "never"]))
+    ///     .get_matches_from(vec!["prog", "--color", "alwys"]);
+    /// // stderr:
+    /// // {"kind":"InvalidValue","message":"error: 'alwys' isn't a valid value for ...",
+    /// //  "arg":"--color <color>","value":"alwys","valid_values":["always","never"],
+    /// //  "suggestion":"always","usage":"prog --color <color>"}
+    /// ```
+    /// [`Error::exit`]: ./struct.Error.html#method.exit
+    /// [`Error::to_json`]: ./struct.Error.html#method.to_json
+    JsonErrors,
+
+    /// Asks for the value of a missing [required] argument on the terminal instead of failing
+    /// with [`ErrorKind::MissingRequiredArgument`], if `stdin` is a TTY. The prompt is the
+    /// argument's [`help`] (or its name) and its [possible values], and the answer is checked
+    /// against them and the argument's [`validator`], asking again until it's valid. The value
+    /// then shows up in the matches as if it had been used, with a [`ValueSource::Prompt`].
+    ///
+    /// Only arguments which take a value are prompted for. [`Arg::secret`] hides what is typed,
+    /// such as for passwords.
+    ///
+    /// **NOTE:** This requires the `color` feature, which detects whether `stdin` is a TTY.
+    /// Without it the setting has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// let m = App::new("prog")
+    ///     .setting(AppSettings::PromptForMissing)
+    ///     .arg(Arg::with_name("user")
+    ///         .long("user")
+    ///         .help("The user to log in as")
+    ///         .takes_value(true)
+    ///         .required(true))
+    ///     .get_matches_from(vec!["prog"]);
+    /// // stderr:
+    /// // The user to log in as:
+    /// ```
+    /// [required]: ./struct.Arg.html#method.required
+    /// [`ErrorKind::MissingRequiredArgument`]: ./enum.ErrorKind.html#variant.MissingRequiredArgument
+    /// [`help`]: ./struct.Arg.html#method.help
+    /// [possible values]: ./struct.Arg.html#method.possible_values
+    /// [`validator`]: ./struct.Arg.html#method.validator
+    /// [`ValueSource::Prompt`]: ./enum.ValueSource.html#variant.Prompt
+    /// [`Arg::secret`]: ./struct.Arg.html#method.secret
+    PromptForMissing,
+
     /// Specifies that the parser should not assume the first argument passed is the binary name.
     /// This is normally the case when using a "daemon" style mode, or an interactive CLI where one
     /// one would not normally type the binary or program name for each command.
@@ -628,6 +698,46 @@ pub enum AppSettings {
     /// ```
     PropagateGlobalValuesDown,
 
+    /// Expands any argument of the form `@path` into the arguments contained in the file at
+    /// `path` before parsing, the same convention used by gcc, javac and msbuild. This is useful
+    /// when the command line would otherwise exceed the operating system's length limits.
+    ///
+    /// The file's contents are split on whitespace. Whitespace can be kept in an argument by
+    /// quoting with `'` or `"`, or escaping it with `\`. Inside single quotes everything is
+    /// literal, inside double quotes `\` only escapes `"` and `\`.
+    ///
+    /// Response files may reference other response files, although one which ends up including
+    /// itself is an error. An argument which should start with a literal `@` can be escaped as
+    /// `@@`, so `@@foo` is passed along as `@foo`. A lone `@` is left untouched.
+    ///
+    /// If a response file can't be read, an [`ErrorKind::Io`] error naming the file is returned.
+    ///
+    /// **NOTE:** Any indices reported by [`ArgMatches::index_of`] count the arguments *after*
+    /// expansion.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::ResponseFiles)
+    ///     .arg(Arg::with_name("files").multiple(true))
+    ///     .get_matches_from_safe(vec!["myprog", "@does-not-exist.rsp"]);
+    ///
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::Io);
+    ///
+    /// let m = App::new("myprog")
+    ///     .setting(AppSettings::ResponseFiles)
+    ///     .arg(Arg::with_name("files").multiple(true))
+    ///     .get_matches_from(vec!["myprog", "@@handle"]);
+    ///
+    /// assert_eq!(m.value_of("files"), Some("@handle"));
+    /// ```
+    /// [`ErrorKind::Io`]: ./enum.ErrorKind.html#variant.Io
+    /// [`ArgMatches::index_of`]: ./struct.ArgMatches.html#method.index_of
+    ResponseFiles,
+
     /// Allows [`SubCommand`]s to override all requirements of the parent command.
     /// For example if you had a subcommand or top level application with a required argument
     /// that is only required as long as there is no subcommand present,
@@ -875,6 +985,7 @@ impl FromStr for AppSettings {
             "allowleadinghyphen" => Ok(AppSettings::AllowLeadingHyphen),
             "allowexternalsubcommands" => Ok(AppSettings::AllowExternalSubcommands),
             "allownegativenumbers" => Ok(AppSettings::AllowNegativeNumbers),
+            "allowmissingpositional" => Ok(AppSettings::AllowMissingPositional),
             "colorauto" => Ok(AppSettings::ColorAuto),
             "coloralways" => Ok(AppSettings::ColorAlways),
             "colornever" => Ok(AppSettings::ColorNever),
@@ -888,9 +999,13 @@ impl FromStr for AppSettings {
             "hidden" => Ok(AppSettings::Hidden),
             "hidepossiblevaluesinhelp" => Ok(AppSettings::HidePossibleValuesInHelp),
             "infersubcommands" => Ok(AppSettings::InferSubcommands),
+            "jsonerrors" => Ok(AppSettings::JsonErrors),
             "lowindexmultiplepositional" => Ok(AppSettings::LowIndexMultiplePositional),
             "nobinaryname" => Ok(AppSettings::NoBinaryName),
             "nextlinehelp" => Ok(AppSettings::NextLineHelp),
+            "promptformissing" => Ok(AppSettings::PromptForMissing),
+            "propagateglobalvaluesdown" => Ok(AppSettings::PropagateGlobalValuesDown),
+            "responsefiles" => Ok(AppSettings::ResponseFiles),
             "strictutf8" => Ok(AppSettings::StrictUtf8),
             "subcommandsnegatereqs" => Ok(AppSettings::SubcommandsNegateReqs),
             "subcommandrequired" => Ok(AppSettings::SubcommandRequired),
@@ -926,6 +1041,8 @@ mod test {
                    AppSettings::AllowLeadingHyphen);
         assert_eq!("allownegativenumbers".parse::<AppSettings>().unwrap(),
                    AppSettings::AllowNegativeNumbers);
+        assert_eq!("allowmissingpositional".parse::<AppSettings>().unwrap(),
+                   AppSettings::AllowMissingPositional);
         assert_eq!("coloredhelp".parse::<AppSettings>().unwrap(),
                    AppSettings::ColoredHelp);
         assert_eq!("colorauto".parse::<AppSettings>().unwrap(),
@@ -956,6 +1073,10 @@ mod test {
                    AppSettings::NoBinaryName);
         assert_eq!("nextlinehelp".parse::<AppSettings>().unwrap(),
                    AppSettings::NextLineHelp);
+        assert_eq!("propagateglobalvaluesdown".parse::<AppSettings>().unwrap(),
+                   AppSettings::PropagateGlobalValuesDown);
+        assert_eq!("responsefiles".parse::<AppSettings>().unwrap(),
+                   AppSettings::ResponseFiles);
         assert_eq!("subcommandsnegatereqs".parse::<AppSettings>().unwrap(),
                    AppSettings::SubcommandsNegateReqs);
         assert_eq!("subcommandrequired".parse::<AppSettings>().unwrap(),
@@ -982,6 +1103,10 @@ mod test {
                    AppSettings::TrailingValues);
         assert_eq!("infersubcommands".parse::<AppSettings>().unwrap(),
                    AppSettings::InferSubcommands);
+        assert_eq!("jsonerrors".parse::<AppSettings>().unwrap(),
+                   AppSettings::JsonErrors);
+        assert_eq!("promptformissing".parse::<AppSettings>().unwrap(),
+                   AppSettings::PromptForMissing);
         assert!("hahahaha".parse::<AppSettings>().is_err());
     }
 }
@@ -0,0 +1,145 @@
+// Internal
+use errors::{Error, Result as ClapResult};
+use fmt::ColorWhen;
+
+// Splits a command line into arguments the way a POSIX shell does, without any expansions:
+//
+// * arguments are separated by unquoted whitespace
+// * inside `'...'` everything is literal
+// * inside `"..."` a `\` only escapes `$`, `` ` ``, `"`, `\` and a newline
+// * elsewhere a `\` escapes any character
+// * a `\` followed by a newline joins the lines, outside of single quotes
+// * a `#` at the start of an argument comments out the rest of the line
+//
+// An unterminated quote, or a `\` at the very end, is an `ErrorKind::ShellSyntax` error at the
+// column (counting characters from 1) of the quote or backslash.
+pub fn split(s: &str, color: ColorWhen) -> ClapResult<Vec<String>> {
+    let err = |i: usize, reason: &str| Err(Error::shell_syntax(i + 1, reason, color));
+    let mut ret = vec![];
+    let mut cur = String::new();
+    // Whether an argument has been started, since `''` is an argument in its own right
+    let mut started = false;
+    let mut chars = s.chars().enumerate().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if started {
+                    ret.push(cur);
+                    cur = String::new();
+                    started = false;
+                }
+            }
+            '#' if !started => {
+                while chars.peek().map_or(false, |&(_, c)| c != '\n') {
+                    chars.next();
+                }
+            }
+            '\'' => {
+                started = true;
+                loop {
+                    match chars.next() {
+                        Some((_, '\'')) => break,
+                        Some((_, c)) => cur.push(c),
+                        None => return err(i, "unterminated single quote"),
+                    }
+                }
+            }
+            '"' => {
+                started = true;
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => {
+                            match chars.peek().map(|&(_, c)| c) {
+                                Some('\n') => {
+                                    chars.next();
+                                }
+                                Some(c @ '$') | Some(c @ '`') | Some(c @ '"') | Some(c @ '\\') => {
+                                    chars.next();
+                                    cur.push(c);
+                                }
+                                _ => cur.push('\\'),
+                            }
+                        }
+                        Some((_, c)) => cur.push(c),
+                        None => return err(i, "unterminated double quote"),
+                    }
+                }
+            }
+            '\\' => {
+                match chars.next() {
+                    Some((_, '\n')) => (),
+                    Some((_, c)) => {
+                        started = true;
+                        cur.push(c);
+                    }
+                    None => return err(i, "nothing to escape after '\\'"),
+                }
+            }
+            c => {
+                started = true;
+                cur.push(c);
+            }
+        }
+    }
+    if started {
+        ret.push(cur);
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod test {
+    use super::split;
+    use errors::{ContextKind, ContextValue, ErrorKind};
+    use fmt::ColorWhen;
+
+    fn words(s: &str) -> Vec<String> { split(s, ColorWhen::Never).unwrap() }
+
+    fn column(s: &str) -> Option<u64> {
+        let err = split(s, ColorWhen::Never).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ShellSyntax);
+        match err.context(ContextKind::Column) {
+            Some(&ContextValue::Number(n)) => Some(n),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(words("  tool\t--opt  a\nb "), ["tool", "--opt", "a", "b"]);
+        assert!(words("   ").is_empty());
+    }
+
+    #[test]
+    fn single_quotes() {
+        assert_eq!(words(r#"a 'b c' 'd\"e' '' x'y'z"#), ["a", "b c", r#"d\"e"#, "", "xyz"]);
+    }
+
+    #[test]
+    fn double_quotes() {
+        assert_eq!(words(r#""a b" "c\"d" "e\\f" "g\h" "$\$" """#),
+                   ["a b", "c\"d", "e\\f", "g\\h", "$$", ""]);
+        assert_eq!(words("\"a\\\nb\""), ["ab"]);
+    }
+
+    #[test]
+    fn backslashes() {
+        assert_eq!(words(r#"a\ b \'c \"d \\"#), ["a b", "'c", "\"d", "\\"]);
+        assert_eq!(words("a\\\nb c"), ["ab", "c"]);
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(words("a # b c\nd a#b '#'"), ["a", "d", "a#b", "#"]);
+        assert!(words("# just a comment").is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(column("tool 'a b"), Some(6));
+        assert_eq!(column("tool \"a b"), Some(6));
+        assert_eq!(column("tool a\\"), Some(7));
+        assert_eq!(column("é 'a"), Some(3));
+    }
+}
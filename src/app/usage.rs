@@ -13,7 +13,8 @@ use app::parser::Parser;
 pub fn create_usage_with_title(p: &Parser, used: &[&str]) -> String {
     debugln!("usage::create_usage_with_title;");
     let mut usage = String::with_capacity(75);
-    usage.push_str("USAGE:\n    ");
+    usage.push_str(p.messages().usage_heading());
+    usage.push_str("\n    ");
     usage.push_str(&*create_usage_no_title(p, used));
     usage
 }
@@ -1,18 +1,22 @@
 // std
+use std::any::Any;
+use std::ffi::OsStr;
 use std::fmt::Display;
+use std::io;
+use std::sync::Arc;
 
 // Internal
 use INTERNAL_ERROR_MSG;
 use INVALID_UTF8;
-use args::{AnyArg, ArgMatcher, MatchedArg};
+use args::{AnyArg, ArgMatcher, MatchedArg, ValueSource};
 use args::settings::ArgSettings;
 use errors::{Error, ErrorKind};
 use errors::Result as ClapResult;
 use osstringext::OsStrExt2;
 use app::settings::AppSettings as AS;
 use app::parser::{Parser, ParseResult};
-use fmt::{Colorizer, ColorizerOption};
-use app::usage;
+use app::{prompt, usage};
+use fmt;
 
 pub struct Validator<'a, 'b, 'z>(&'z mut Parser<'a, 'b>)
     where 'a: 'b,
@@ -54,11 +58,8 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
            self.0.is_set(AS::ArgRequiredElseHelp) {
             let mut out = vec![];
             try!(self.0.write_help_err(&mut out));
-            return Err(Error {
-                           message: String::from_utf8_lossy(&*out).into_owned(),
-                           kind: ErrorKind::MissingArgumentOrSubcommand,
-                           info: None,
-                       });
+            return Err(Error::raw(String::from_utf8_lossy(&*out).into_owned(),
+                                  ErrorKind::MissingArgumentOrSubcommand));
         }
         try!(self.validate_blacklist(matcher));
         if !(self.0.is_set(AS::SubcommandsNegateReqs) && subcmd_name.is_some()) && !reqs_validated {
@@ -74,10 +75,11 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                           arg: &A,
                           ma: &MatchedArg,
                           matcher: &ArgMatcher<'a>)
-                          -> ClapResult<()>
+                          -> ClapResult<Vec<Arc<Any + Send + Sync>>>
         where A: AnyArg<'a, 'b> + Display
     {
         debugln!("Validator::validate_values: arg={:?}", arg.name());
+        let mut parsed = vec![];
         for val in &ma.vals {
             if self.0.is_set(AS::StrictUtf8) && val.to_str().is_none() {
                 debugln!("Validator::validate_values: invalid UTF-8 found in val {:?}",
@@ -98,6 +100,35 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                                                     self.0.color()));
                 }
             }
+            if let Some(d) = arg.kv_delim() {
+                debugln!("Validator::validate_values: kv_delim={:?}", d);
+                let val_str = val.to_string_lossy();
+                let key = match val_str.find(d) {
+                    Some(i) => &val_str[..i],
+                    None => {
+                        return Err(Error::unparsable_value(&*val_str,
+                                                           format!("expected a key and value \
+                                                                    separated by '{}'",
+                                                                   d),
+                                                           arg,
+                                                           &*usage::create_error_usage(self.0,
+                                                                                       matcher,
+                                                                                       None),
+                                                           self.0.color()));
+                    }
+                };
+                if let Some(p_keys) = arg.possible_keys() {
+                    if !p_keys.contains(&key) {
+                        return Err(Error::invalid_key(key,
+                                                      p_keys,
+                                                      arg,
+                                                      &*usage::create_error_usage(self.0,
+                                                                                  matcher,
+                                                                                  None),
+                                                      self.0.color()));
+                    }
+                }
+            }
             if !arg.is_set(ArgSettings::EmptyValues) && val.is_empty_() &&
                matcher.contains(&*arg.name()) {
                 debugln!("Validator::validate_values: illegal empty val found");
@@ -125,8 +156,37 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                     sdebugln!("good");
                 }
             }
+            for check in arg.checks() {
+                debug!("Validator::validate_values: running check...");
+                if let Err(e) = check.run(val) {
+                    sdebugln!("error");
+                    return Err(Error::value_validation(Some(arg), e, self.0.color()));
+                } else {
+                    sdebugln!("good");
+                }
+            }
+            if let Some(parser) = arg.value_parser() {
+                debug!("Validator::validate_values: parsing...");
+                let val_str = val.to_string_lossy();
+                match parser(&*val_str) {
+                    Ok(v) => {
+                        sdebugln!("good");
+                        parsed.push(v);
+                    }
+                    Err(e) => {
+                        sdebugln!("error");
+                        return Err(Error::unparsable_value(val_str,
+                                                           e,
+                                                           arg,
+                                                           &*usage::create_error_usage(self.0,
+                                                                                       matcher,
+                                                                                       None),
+                                                           self.0.color()));
+                    }
+                }
+            }
         }
-        Ok(())
+        Ok(parsed)
     }
 
     fn validate_blacklist(&self, matcher: &mut ArgMatcher) -> ClapResult<()> {
@@ -187,22 +247,43 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
 
     fn validate_matched_args(&self, matcher: &mut ArgMatcher<'a>) -> ClapResult<()> {
         debugln!("Validator::validate_matched_args;");
+        // Values turned into their final types by Arg::value_parser, and the delimiter of
+        // Arg::key_value pairs, stored once the borrow of the matcher ends
+        let mut parsed = vec![];
         for (name, ma) in matcher.iter() {
             debugln!("Validator::validate_matched_args:iter:{}: vals={:#?}",
                      name,
                      ma.vals);
+            // Errors in values read from a config file point at the offending line
+            macro_rules! config_err {
+                ($a:ident, $e:expr) => {
+                    match $e {
+                        Ok(v) => v,
+                        Err(e) => {
+                            if ma.source == ValueSource::Config {
+                                if let Some(entry) = self.0.config_entry($a) {
+                                    return Err(entry.error(e));
+                                }
+                            }
+                            return Err(e);
+                        }
+                    }
+                };
+            }
             if let Some(opt) = find_by_name!(self.0, name, opts, iter) {
-                try!(self.validate_arg_num_vals(opt, ma, matcher));
-                try!(self.validate_values(opt, ma, matcher));
+                config_err!(opt, self.validate_arg_num_vals(opt, ma, matcher));
+                let vals = config_err!(opt, self.validate_values(opt, ma, matcher));
+                parsed.push((opt.b.name, vals, opt.kv_delim()));
                 try!(self.validate_arg_requires(opt, ma, matcher));
-                try!(self.validate_arg_num_occurs(opt, ma, matcher));
+                config_err!(opt, self.validate_arg_num_occurs(opt, ma, matcher));
             } else if let Some(flag) = find_by_name!(self.0, name, flags, iter) {
                 try!(self.validate_arg_requires(flag, ma, matcher));
-                try!(self.validate_arg_num_occurs(flag, ma, matcher));
+                config_err!(flag, self.validate_arg_num_occurs(flag, ma, matcher));
             } else if let Some(pos) = find_by_name!(self.0, name, positionals, values) {
-                try!(self.validate_arg_num_vals(pos, ma, matcher));
-                try!(self.validate_arg_num_occurs(pos, ma, matcher));
-                try!(self.validate_values(pos, ma, matcher));
+                config_err!(pos, self.validate_arg_num_vals(pos, ma, matcher));
+                config_err!(pos, self.validate_arg_num_occurs(pos, ma, matcher));
+                let vals = config_err!(pos, self.validate_values(pos, ma, matcher));
+                parsed.push((pos.b.name, vals, pos.kv_delim()));
                 try!(self.validate_arg_requires(pos, ma, matcher));
             } else {
                 let grp = self.0
@@ -217,6 +298,14 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                 }
             }
         }
+        for (name, vals, kv_delim) in parsed {
+            if let Some(ma) = matcher.get_mut(name) {
+                if !vals.is_empty() {
+                    ma.parsed = vals;
+                }
+                ma.kv_delim = kv_delim;
+            }
+        }
         Ok(())
     }
 
@@ -263,14 +352,6 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                                                          } else {
                                                              ma.vals.len()
                                                          },
-                                                         if ma.vals.len() == 1 ||
-                                                            (a.is_set(ArgSettings::Multiple) &&
-                                                             (ma.vals.len() % num as usize) ==
-                                                             1) {
-                                                             "as"
-                                                         } else {
-                                                             "ere"
-                                                         },
                                                          &*usage::create_error_usage(self.0,
                                                                                      matcher,
                                                                                      None),
@@ -336,7 +417,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         Ok(())
     }
 
-    fn validate_required(&self, matcher: &ArgMatcher) -> ClapResult<()> {
+    fn validate_required(&self, matcher: &mut ArgMatcher<'a>) -> ClapResult<()> {
         debugln!("Validator::validate_required: required={:?};",
                  self.0.required);
         'outer: for name in &self.0.required {
@@ -357,6 +438,9 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                     continue 'outer;
                 }
             }
+            if self.0.is_set(AS::PromptForMissing) && try!(self.prompt_for(name, matcher)) {
+                continue 'outer;
+            }
             return self.missing_required_error(matcher, None);
         }
 
@@ -371,6 +455,79 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         Ok(())
     }
 
+    // Asks for the value of the missing required argument `name` on the terminal, returning
+    // whether it got one
+    fn prompt_for(&self, name: &'a str, matcher: &mut ArgMatcher<'a>) -> ClapResult<bool> {
+        debugln!("Validator::prompt_for: name={}", name);
+        if !fmt::is_stdin_a_tty() {
+            return Ok(false);
+        }
+        macro_rules! ask {
+            ($a:expr) => {{
+                let stdin = io::stdin();
+                let val = try!(prompt::ask($a,
+                                           self.0.messages(),
+                                           $a.is_set(ArgSettings::Secret),
+                                           &mut stdin.lock(),
+                                           &mut io::stderr()));
+                if let Some(ref v) = val {
+                    self.add_prompted_val(name, &*v, matcher);
+                    // Conflicts were checked before the value was there
+                    if let Some(other) = self.prompted_conflict($a, matcher) {
+                        let usg = usage::create_error_usage(self.0, matcher, None);
+                        let color = self.0.color();
+                        return Err(Error::argument_conflict($a, Some(other), &*usg, color));
+                    }
+                    try!(self.validate_blacklist(matcher));
+                }
+                val.is_some()
+            }};
+        }
+        if let Some(o) = find_by_name!(self.0, &name, opts, iter) {
+            Ok(ask!(o))
+        } else if let Some(p) = find_by_name!(self.0, &name, positionals, values) {
+            Ok(ask!(p))
+        } else {
+            // Flags and groups have no value to ask for
+            Ok(false)
+        }
+    }
+
+    // Adds a value given at the prompt as if it had been on the command line
+    fn add_prompted_val(&self, name: &'a str, val: &str, matcher: &mut ArgMatcher<'a>) {
+        matcher.inc_occurrence_of(name);
+        matcher.add_val_to(name, OsStr::new(val));
+        matcher.set_source(name, ValueSource::Prompt);
+        if let Some(grps) = self.0.groups_for_arg(name) {
+            matcher.inc_occurrences_of(&*grps);
+            for grp in grps {
+                matcher.add_val_to(grp, OsStr::new(val));
+            }
+        }
+    }
+
+    // Finds an arg which was given that the prompted `a`, or a group of it, conflicts with
+    fn prompted_conflict<A>(&self, a: &A, matcher: &ArgMatcher) -> Option<String>
+        where A: AnyArg<'a, 'b>
+    {
+        let grp_conflicts = self.0
+            .groups
+            .iter()
+            .filter(|g| g.args.contains(&a.name()))
+            .flat_map(|g| g.conflicts.iter().flat_map(|c| c.iter()));
+        a.blacklist()
+            .into_iter()
+            .flat_map(|bl| bl.iter())
+            .chain(grp_conflicts)
+            .flat_map(|&c| match self.0.groups.iter().find(|g| g.name == c) {
+                Some(g) => g.args.clone(),
+                None => vec![c],
+            })
+            .find(|c| matcher.contains(c))
+            .and_then(|c| self.0.find_any_arg(c))
+            .map(|c| c.to_string())
+    }
+
     fn validate_conflicts<A>(&self, a: &A, matcher: &ArgMatcher) -> Option<bool>
         where A: AnyArg<'a, 'b>
     {
@@ -417,10 +574,6 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
 
     fn missing_required_error(&self, matcher: &ArgMatcher, extra: Option<&str>) -> ClapResult<()> {
         debugln!("Validator::missing_required_error: extra={:?}", extra);
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: self.0.color(),
-        });
         let mut reqs = self.0
             .required
             .iter()
@@ -434,12 +587,11 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         debugln!("Validator::missing_required_error: reqs={:#?}", reqs);
         let req_args =
             usage::get_required_usage_from(self.0, &reqs[..], Some(matcher), extra, true)
-                .iter()
-                .fold(String::new(),
-                      |acc, s| acc + &format!("\n    {}", c.error(s))[..]);
+                .into_iter()
+                .collect::<Vec<_>>();
         debugln!("Validator::missing_required_error: req_args={:#?}",
                  req_args);
-        Err(Error::missing_required_argument(&*req_args,
+        Err(Error::missing_required_argument(req_args,
                                              &*usage::create_error_usage(self.0, matcher, extra),
                                              self.0.color()))
     }
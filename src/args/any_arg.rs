@@ -1,5 +1,7 @@
 // Std
+use std::any::Any;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::fmt as std_fmt;
 use std::ffi::{OsStr, OsString};
 
@@ -8,6 +10,7 @@ use vec_map::{self, VecMap};
 
 // Internal
 use args::settings::ArgSettings;
+use validators::Check;
 
 #[doc(hidden)]
 pub trait AnyArg<'n, 'e>: std_fmt::Display {
@@ -24,17 +27,24 @@ pub trait AnyArg<'n, 'e>: std_fmt::Display {
     fn min_vals(&self) -> Option<u64>;
     fn num_vals(&self) -> Option<u64>;
     fn possible_vals(&self) -> Option<&[&'e str]>;
+    fn possible_keys(&self) -> Option<&[&'e str]>;
     fn validator(&self) -> Option<&Rc<Fn(String) -> Result<(), String>>>;
     fn validator_os(&self) -> Option<&Rc<Fn(&OsStr) -> Result<(), OsString>>>;
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>>;
+    fn value_parser(&self) -> Option<&Rc<Fn(&str) -> Result<Arc<Any + Send + Sync>, String>>>;
+    fn checks(&self) -> &[Check];
+    fn val_hint(&self) -> Option<&str>;
     fn short(&self) -> Option<char>;
     fn long(&self) -> Option<&'e str>;
     fn val_delim(&self) -> Option<char>;
+    fn kv_delim(&self) -> Option<char>;
     fn takes_value(&self) -> bool;
     fn val_names(&self) -> Option<&VecMap<&'e str>>;
     fn help(&self) -> Option<&'e str>;
     fn long_help(&self) -> Option<&'e str>;
     fn default_val(&self) -> Option<&'e OsStr>;
     fn default_vals_ifs(&self) -> Option<vec_map::Values<(&'n str, Option<&'e OsStr>, &'e OsStr)>>;
+    fn env(&self) -> Option<&'e OsStr>;
     fn longest_filter(&self) -> bool;
     fn val_terminator(&self) -> Option<&'e str>;
 }
@@ -1,6 +1,9 @@
 #[cfg(feature = "yaml")]
 use std::collections::BTreeMap;
+use std::any::Any;
+use std::fmt::Display;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::ffi::{OsString, OsStr};
 #[cfg(target_os="windows")]
 use osstringext::OsStrExt3;
@@ -12,7 +15,11 @@ use std::os::unix::ffi::OsStrExt;
 use yaml_rust::Yaml;
 use vec_map::VecMap;
 
+#[cfg(any(feature = "yaml", feature = "json", feature = "toml"))]
+use definition::Node;
+
 use usage_parser::UsageParser;
+use validators::Check;
 use args::settings::ArgSettings;
 use args::arg_builder::{Base, Valued, Switched};
 
@@ -91,17 +98,23 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// [`Arg`]: ./struct.Arg.html
     #[cfg(feature = "yaml")]
     pub fn from_yaml(y: &BTreeMap<Yaml, Yaml>) -> Arg {
-        // We WANT this to panic on error...so expect() is good.
         let name_yml = y.keys().nth(0).unwrap();
-        let name_str = name_yml.as_str().unwrap();
+        Arg::from_node(name_yml.as_str().unwrap(), y.get(name_yml).unwrap())
+    }
+
+    // Builds the arg from its settings in a YAML, JSON or TOML document
+    #[doc(hidden)]
+    #[cfg(any(feature = "yaml", feature = "json", feature = "toml"))]
+    pub fn from_node<N: Node>(name_str: &'a str, arg_settings: &'a N) -> Arg<'a, 'a> {
+        // We WANT this to panic on error...so expect() is good.
         let mut a = Arg::with_name(name_str);
-        let arg_settings = y.get(name_yml).unwrap().as_hash().unwrap();
 
-        for (k, v) in arg_settings.iter() {
-            a = match k.as_str().unwrap() {
+        for (k, v) in arg_settings.entries().unwrap() {
+            a = match k {
                 "short" => yaml_to_str!(a, v, short),
                 "long" => yaml_to_str!(a, v, long),
                 "aliases" => yaml_vec_or_str!(v, a, alias),
+                "visible_aliases" => yaml_vec_or_str!(v, a, visible_alias),
                 "help" => yaml_to_str!(a, v, help),
                 "long_help" => yaml_to_str!(a, v, long_help),
                 "required" => yaml_to_bool!(a, v, required),
@@ -112,6 +125,7 @@ impl<'a, 'b> Arg<'a, 'b> {
                 "global" => yaml_to_bool!(a, v, global),
                 "multiple" => yaml_to_bool!(a, v, multiple),
                 "hidden" => yaml_to_bool!(a, v, hidden),
+                "negatable" => yaml_to_bool!(a, v, negatable),
                 "next_line_help" => yaml_to_bool!(a, v, next_line_help),
                 "empty_values" => yaml_to_bool!(a, v, empty_values),
                 "group" => yaml_to_str!(a, v, group),
@@ -128,6 +142,7 @@ impl<'a, 'b> Arg<'a, 'b> {
                 "default_value" => yaml_to_str!(a, v, default_value),
                 "default_value_if" => yaml_tuple3!(a, v, default_value_if),
                 "default_value_ifs" => yaml_tuple3!(a, v, default_value_if),
+                "env" => yaml_to_str!(a, v, env),
                 "value_names" => yaml_vec_or_str!(v, a, value_name),
                 "groups" => yaml_vec_or_str!(v, a, group),
                 "requires" => yaml_vec_or_str!(v, a, requires),
@@ -136,6 +151,10 @@ impl<'a, 'b> Arg<'a, 'b> {
                 "conflicts_with" => yaml_vec_or_str!(v, a, conflicts_with),
                 "overrides_with" => yaml_vec_or_str!(v, a, overrides_with),
                 "possible_values" => yaml_vec_or_str!(v, a, possible_value),
+                "key_value" => yaml_to_bool!(a, v, key_value),
+                "key_value_delimiter" => yaml_to_str!(a, v, key_value_delimiter),
+                "possible_keys" => yaml_vec_or_str!(v, a, possible_key),
+                "secret" => yaml_to_bool!(a, v, secret),
                 "required_unless_one" => yaml_vec_or_str!(v, a, required_unless),
                 "required_unless_all" => {
                     a = yaml_vec_or_str!(v, a, required_unless);
@@ -143,7 +162,7 @@ impl<'a, 'b> Arg<'a, 'b> {
                     a
                 }
                 s => {
-                    panic!("Unknown Arg setting '{}' in YAML file for arg '{}'",
+                    panic!("Unknown Arg setting '{}' in app definition for arg '{}'",
                            s,
                            name_str)
                 }
@@ -1827,6 +1846,36 @@ impl<'a, 'b> Arg<'a, 'b> {
         }
     }
 
+    /// Specifies if the current value of the argument's [`Arg::env`] variable should be displayed
+    /// in the help text or not. Defaults to `false` (i.e. show the value), unless the argument is
+    /// [`Arg::secret`].
+    ///
+    /// This is useful when the variable may hold something like a token, which shouldn't end up
+    /// in a terminal's scrollback or a bug report just because someone ran `--help`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("connect")
+    ///     .arg(Arg::with_name("token")
+    ///         .long("token")
+    ///         .env("API_TOKEN")
+    ///         .hide_env_values(true));
+    /// ```
+    ///
+    /// If we were to run the above program with `--help` the help text for `--token` would end
+    /// with `[env: API_TOKEN]` instead of `[env: API_TOKEN=...]`.
+    /// [`Arg::env`]: ./struct.Arg.html#method.env
+    /// [`Arg::secret`]: ./struct.Arg.html#method.secret
+    pub fn hide_env_values(self, hide: bool) -> Self {
+        if hide {
+            self.set(ArgSettings::HideEnvValues)
+        } else {
+            self.unset(ArgSettings::HideEnvValues)
+        }
+    }
+
     /// Specifies the index of a positional argument **starting at** 1.
     ///
     /// **NOTE:** The index refers to position according to **other positional argument**. It does
@@ -2240,6 +2289,41 @@ impl<'a, 'b> Arg<'a, 'b> {
         }
     }
 
+    /// Allows a flag to be turned off again with `--no-<long>`, such as `--no-color` for a
+    /// `--color` flag. Whichever of the two comes last on the command line wins, so a shell alias
+    /// or wrapper script can set a flag which the user is still able to override. Use
+    /// [`ArgMatches::flag_state`] to tell whether the flag was turned off, or never mentioned at
+    /// all.
+    ///
+    /// The help message shows both forms on a single line, i.e. `--[no-]color`.
+    ///
+    /// **NOTE:** Only flags with a [`Arg::long`] may be negatable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .negatable(true))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--color", "--no-color"
+    ///     ]);
+    ///
+    /// assert!(!m.is_present("color"));
+    /// assert_eq!(m.flag_state("color"), Some(false));
+    /// ```
+    /// [`ArgMatches::flag_state`]: ./struct.ArgMatches.html#method.flag_state
+    /// [`Arg::long`]: ./struct.Arg.html#method.long
+    pub fn negatable(self, n: bool) -> Self {
+        if n {
+            self.set(ArgSettings::Negatable)
+        } else {
+            self.unset(ArgSettings::Negatable)
+        }
+    }
+
     /// Specifies a list of possible values for this argument. At runtime, `clap` verifies that
     /// only one of the specified values was used, or fails with an error message.
     ///
@@ -2360,6 +2444,187 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Treats every value of this argument as a `key=value` pair, such as `-D name=value` or
+    /// `--set a.b=c`. Each value is split on the first `=` (see [`Arg::key_value_delimiter`]), so
+    /// the value itself may still contain the delimiter. A value without the delimiter is rejected
+    /// with an [`ErrorKind::InvalidValue`] error. Use [`ArgMatches::values_of_map`] to read the
+    /// pairs back.
+    ///
+    /// **NOTE:** implicitly sets [`Arg::takes_value(true)`]
+    ///
+    /// **NOTE:** Does *not* imply [`Arg::multiple(true)`], which is usually wanted as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("define")
+    ///         .short("D")
+    ///         .multiple(true)
+    ///         .number_of_values(1)
+    ///         .key_value(true))
+    ///     .get_matches_from(vec![
+    ///         "prog", "-D", "name=value", "-D", "url=a?b=c"
+    ///     ]);
+    ///
+    /// let defines = m.values_of_map("define").unwrap();
+    /// assert_eq!(defines["name"], "value");
+    /// assert_eq!(defines["url"], "a?b=c");
+    /// ```
+    ///
+    /// Leaving off the delimiter is an error.
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("define")
+    ///         .short("D")
+    ///         .key_value(true))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "-D", "name"
+    ///     ]);
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::InvalidValue);
+    /// ```
+    /// [`Arg::key_value_delimiter`]: ./struct.Arg.html#method.key_value_delimiter
+    /// [`ErrorKind::InvalidValue`]: ./enum.ErrorKind.html#variant.InvalidValue
+    /// [`ArgMatches::values_of_map`]: ./struct.ArgMatches.html#method.values_of_map
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    /// [`Arg::multiple(true)`]: ./struct.Arg.html#method.multiple
+    pub fn key_value(self, kv: bool) -> Self {
+        if kv {
+            self.set(ArgSettings::TakesValue).set(ArgSettings::KeyValue)
+        } else {
+            self.unset(ArgSettings::KeyValue)
+        }
+    }
+
+    /// Specifies the separator between the key and the value of a [`Arg::key_value`] argument,
+    /// which defaults to `=`. Only the first character of `d` is used.
+    ///
+    /// **NOTE:** implicitly sets [`Arg::key_value(true)`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("header")
+    ///         .long("header")
+    ///         .key_value_delimiter(":"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--header", "Accept:text/html"
+    ///     ]);
+    ///
+    /// assert_eq!(m.values_of_map("header").unwrap()["Accept"], "text/html");
+    /// ```
+    /// [`Arg::key_value`]: ./struct.Arg.html#method.key_value
+    /// [`Arg::key_value(true)`]: ./struct.Arg.html#method.key_value
+    pub fn key_value_delimiter(mut self, d: &str) -> Self {
+        self.v.kv_delim = Some(d.chars()
+            .nth(0)
+            .expect("Failed to get key_value_delimiter from arg"));
+        self.key_value(true)
+    }
+
+    /// Specifies the keys a [`Arg::key_value`] argument accepts. At runtime, `clap` verifies the
+    /// key of every value is one of them, or fails with an [`ErrorKind::InvalidValue`] error
+    /// suggesting the closest match, i.e. "Did you mean 'user.name'?" below. Values are not
+    /// restricted.
+    ///
+    /// **NOTE:** implicitly sets [`Arg::key_value(true)`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("set")
+    ///         .long("set")
+    ///         .possible_keys(&["user.name", "user.email"]))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "--set", "user.nmae=kevin"
+    ///     ]);
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::InvalidValue);
+    /// assert_eq!(err.info, Some(vec!["set".to_owned(), "user.nmae".to_owned()]));
+    /// ```
+    /// [`Arg::key_value`]: ./struct.Arg.html#method.key_value
+    /// [`ErrorKind::InvalidValue`]: ./enum.ErrorKind.html#variant.InvalidValue
+    /// [`Arg::key_value(true)`]: ./struct.Arg.html#method.key_value
+    pub fn possible_keys(mut self, names: &[&'b str]) -> Self {
+        if let Some(ref mut vec) = self.v.possible_keys {
+            for s in names {
+                vec.push(s);
+            }
+        } else {
+            self.v.possible_keys = Some(names.iter().map(|s| *s).collect::<Vec<_>>());
+        }
+        self.key_value(true)
+    }
+
+    /// Specifies a possible key for a [`Arg::key_value`] argument, one at a time. See
+    /// [`Arg::possible_keys`] for details.
+    ///
+    /// **NOTE:** implicitly sets [`Arg::key_value(true)`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("set")
+    ///         .long("set")
+    ///         .possible_key("user.name")
+    ///         .possible_key("user.email"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--set", "user.email=kevin@example.com"
+    ///     ]);
+    /// assert_eq!(m.values_of_map("set").unwrap()["user.email"], "kevin@example.com");
+    /// ```
+    /// [`Arg::key_value`]: ./struct.Arg.html#method.key_value
+    /// [`Arg::possible_keys`]: ./struct.Arg.html#method.possible_keys
+    /// [`Arg::key_value(true)`]: ./struct.Arg.html#method.key_value
+    pub fn possible_key(mut self, name: &'b str) -> Self {
+        if let Some(ref mut vec) = self.v.possible_keys {
+            vec.push(name);
+        } else {
+            self.v.possible_keys = Some(vec![name]);
+        }
+        self.key_value(true)
+    }
+
+    /// Hides what is typed when the value of this argument is prompted for because of
+    /// [`AppSettings::PromptForMissing`], such as for passwords. It has no effect otherwise.
+    ///
+    /// **NOTE:** Hiding the input uses `stty`, so on platforms without it the value is still
+    /// echoed.
+    ///
+    /// **NOTE:** implicitly sets [`Arg::takes_value(true)`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// let m = App::new("prog")
+    ///     .setting(AppSettings::PromptForMissing)
+    ///     .arg(Arg::with_name("password")
+    ///         .long("password")
+    ///         .help("The password")
+    ///         .required(true)
+    ///         .secret(true))
+    ///     .get_matches_from(vec!["prog"]);
+    /// ```
+    /// [`AppSettings::PromptForMissing`]: ./enum.AppSettings.html#variant.PromptForMissing
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    pub fn secret(self, s: bool) -> Self {
+        if s {
+            self.set(ArgSettings::TakesValue).set(ArgSettings::Secret)
+        } else {
+            self.unset(ArgSettings::Secret)
+        }
+    }
+
     /// Specifies the name of the [`ArgGroup`] the argument belongs to.
     ///
     /// # Examples
@@ -2563,6 +2828,166 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Parses each value of this argument into a `T` as it's validated, so that the typed values
+    /// can later be retrieved with [`ArgMatches::get`] rather than parsing them again with
+    /// [`value_t!`]. The function is given each value (including those coming from
+    /// [`Arg::env`], [`Arg::default_value`] and config files) and returns either the parsed value,
+    /// or an error describing what was wrong with it.
+    ///
+    /// When parsing fails the user sees an [`ErrorKind::InvalidValue`] error which includes the
+    /// reason along with the usage string, just like a value which isn't one of the
+    /// [possible values]. This also means there's no need for a separate [`Arg::validator`] which
+    /// parses the value only to throw it away.
+    ///
+    /// **NOTE:** This implicitly sets [`Arg::takes_value(true)`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .value_parser(str::parse::<u16>))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--port", "8080"
+    ///     ]);
+    ///
+    /// assert_eq!(m.get::<u16>("port"), Some(&8080));
+    /// ```
+    ///
+    /// Values which fail to parse are reported with the reason
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .value_parser(str::parse::<u16>))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "--port", "80000"
+    ///     ]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::InvalidValue);
+    /// assert!(err.message.contains("number too large to fit in target type"));
+    /// ```
+    /// [`ArgMatches::get`]: ./struct.ArgMatches.html#method.get
+    /// [`value_t!`]: ./macro.value_t!.html
+    /// [`Arg::env`]: ./struct.Arg.html#method.env
+    /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
+    /// [`ErrorKind::InvalidValue`]: ./enum.ErrorKind.html#variant.InvalidValue
+    /// [possible values]: ./struct.Arg.html#method.possible_values
+    /// [`Arg::validator`]: ./struct.Arg.html#method.validator
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    pub fn value_parser<F, T, E>(mut self, f: F) -> Self
+        where F: Fn(&str) -> Result<T, E> + 'static,
+              T: Any + Send + Sync,
+              E: Display
+    {
+        self.setb(ArgSettings::TakesValue);
+        self.v.value_parser = Some(Rc::new(move |s| {
+            let v: Arc<Any + Send + Sync> = Arc::new(try!(f(s).map_err(|e| e.to_string())));
+            Ok(v)
+        }));
+        self
+    }
+
+    /// Adds one of the ready made checks from the [`validators`] module (or a [`Check`] of your
+    /// own) to this argument's values. Like [`Arg::validator`], a value which fails the check is
+    /// reported as an [`ErrorKind::ValueValidation`] error. Unlike it, the check can also add a
+    /// hint to the value's placeholder in the help message, i.e. `--port <port: 1-65535>`.
+    ///
+    /// This method may be called more than once, in which case every check must pass and the
+    /// hints are separated by commas.
+    ///
+    /// **NOTE:** This implicitly sets [`Arg::takes_value(true)`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// use clap::validators;
+    ///
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .check(validators::range(1, 65535)))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "--port", "http"
+    ///     ]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::ValueValidation);
+    /// assert!(err.message.contains("'http' isn't a number"));
+    /// ```
+    /// [`validators`]: ./validators/index.html
+    /// [`Check`]: ./validators/struct.Check.html
+    /// [`Arg::validator`]: ./struct.Arg.html#method.validator
+    /// [`ErrorKind::ValueValidation`]: ./enum.ErrorKind.html#variant.ValueValidation
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    pub fn check(mut self, c: Check) -> Self {
+        self.setb(ArgSettings::TakesValue);
+        if let Some(hint) = c.get_hint() {
+            self.v.val_hint = Some(match self.v.val_hint.take() {
+                                       Some(prev) => format!("{}, {}", prev, hint),
+                                       None => hint.to_owned(),
+                                   });
+        }
+        self.v.checks.push(c);
+        self
+    }
+
+    /// Provides completion candidates for this argument's value at runtime, for things which
+    /// can't be known when the completion script is generated such as git branch names, remote
+    /// hosts or database tables. You provide a closure which accepts the (possibly empty) word
+    /// being completed and returns the candidates. The shell will filter them against what's
+    /// already been typed, so returning more than strictly matches is fine.
+    ///
+    /// The bash and zsh scripts from [`App::gen_completions`] call back into the program by
+    /// running it as `<bin> __complete <words...>`, where the words are everything on the command
+    /// line after the binary name up to and including the one being completed. When clap sees
+    /// such an invocation (and at least one argument has a completer) it works out which
+    /// argument is being completed, prints the candidates one per line, and returns an
+    /// [`ErrorKind::CompletionsDisplayed`] error which, like `--help`, exits successfully when
+    /// using [`App::get_matches`].
+    ///
+    /// Arguments without a completer are completed from their [possible values], if any.
+    ///
+    /// **NOTE:** This implicitly sets [`Arg::takes_value(true)`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// fn branches(_cur: &str) -> Vec<String> {
+    ///     vec!["master".into(), "feature".into()]
+    /// }
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("branch")
+    ///         .long("branch")
+    ///         .completer(branches))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "__complete", "--branch", ""
+    ///     ]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::CompletionsDisplayed);
+    /// assert_eq!(err.message, "master\nfeature");
+    /// ```
+    /// [`App::gen_completions`]: ./struct.App.html#method.gen_completions
+    /// [`ErrorKind::CompletionsDisplayed`]: ./enum.ErrorKind.html#variant.CompletionsDisplayed
+    /// [`App::get_matches`]: ./struct.App.html#method.get_matches
+    /// [possible values]: ./struct.Arg.html#method.possible_values
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    pub fn completer<F>(mut self, f: F) -> Self
+        where F: Fn(&str) -> Vec<String> + 'static
+    {
+        self.setb(ArgSettings::TakesValue);
+        self.v.completer = Some(Rc::new(f));
+        self
+    }
+
     /// Specifies the *maximum* number of values are for this argument. For example, if you had a
     /// `-f <file>` argument where you wanted up to 3 'files' you would set `.max_values(3)`, and
     /// this argument would be satisfied if the user provided, 1, 2, or 3 values.
@@ -3311,6 +3736,108 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Specifies that if the value is not passed in as an argument, that it should be retrieved
+    /// from the environment, if available. If it is not present in the environment, then default
+    /// rules will apply.
+    ///
+    /// Values read from the environment go through the exact same checks as values provided at
+    /// runtime, such as [`Arg::possible_values`] and [`Arg::validator`]. The value is split on
+    /// the [`Arg::value_delimiter`] just as it would be if it were supplied on the command line.
+    ///
+    /// **NOTE:** An environment variable takes precedence over both [`Arg::default_value`] and
+    /// [`Arg::default_value_if`], but a value supplied at runtime always wins.
+    ///
+    /// **NOTE:** If the value is taken from the environment, [`ArgMatches::occurrences_of`] will
+    /// return `0` just as it does for default values.
+    ///
+    /// **NOTE:** This implicitly sets [`Arg::takes_value(true)`].
+    ///
+    /// # Examples
+    ///
+    /// In this example, we show the variable coming from the environment:
+    ///
+    /// ```rust
+    /// # use std::env;
+    /// # use clap::{App, Arg};
+    ///
+    /// env::set_var("MY_FLAG", "env");
+    ///
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("flag")
+    ///         .long("flag")
+    ///         .env("MY_FLAG"))
+    ///     .get_matches_from(vec![
+    ///         "prog"
+    ///     ]);
+    ///
+    /// assert_eq!(m.value_of("flag"), Some("env"));
+    /// assert_eq!(m.occurrences_of("flag"), 0);
+    /// ```
+    ///
+    /// In this example, we show the variable coming from an option on the CLI:
+    ///
+    /// ```rust
+    /// # use std::env;
+    /// # use clap::{App, Arg};
+    ///
+    /// env::set_var("MY_FLAG_OPT", "env");
+    ///
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("flag")
+    ///         .long("flag")
+    ///         .env("MY_FLAG_OPT"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--flag", "opt"
+    ///     ]);
+    ///
+    /// assert_eq!(m.value_of("flag"), Some("opt"));
+    /// ```
+    ///
+    /// In this example, we show the variable coming from the environment even with the
+    /// presence of a default:
+    ///
+    /// ```rust
+    /// # use std::env;
+    /// # use clap::{App, Arg};
+    ///
+    /// env::set_var("MY_FLAG_DEF", "env");
+    ///
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("flag")
+    ///         .long("flag")
+    ///         .env("MY_FLAG_DEF")
+    ///         .default_value("default"))
+    ///     .get_matches_from(vec![
+    ///         "prog"
+    ///     ]);
+    ///
+    /// assert_eq!(m.value_of("flag"), Some("env"));
+    /// ```
+    ///
+    /// If we were to run the above program with `--help` the help text for `--flag` would end
+    /// with `[env: MY_FLAG_DEF=env] [default: default]`. Use [`Arg::hide_env_values`] to leave
+    /// out the `=env` part.
+    /// [`ArgMatches::occurrences_of`]: ./struct.ArgMatches.html#method.occurrences_of
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
+    /// [`Arg::default_value_if`]: ./struct.Arg.html#method.default_value_if
+    /// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+    /// [`Arg::validator`]: ./struct.Arg.html#method.validator
+    /// [`Arg::value_delimiter`]: ./struct.Arg.html#method.value_delimiter
+    /// [`Arg::hide_env_values`]: ./struct.Arg.html#method.hide_env_values
+    pub fn env(self, name: &'a str) -> Self { self.env_os(OsStr::new(name)) }
+
+    /// Specifies that if the value is not passed in as an argument, that it should be retrieved
+    /// from the environment if available in the exact same manner as [`Arg::env`] only using
+    /// [`OsStr`]s instead.
+    /// [`Arg::env`]: ./struct.Arg.html#method.env
+    /// [`OsStr`]: https://doc.rust-lang.org/std/ffi/struct.OsStr.html
+    pub fn env_os(mut self, name: &'a OsStr) -> Self {
+        self.setb(ArgSettings::TakesValue);
+        self.v.env = Some(name);
+        self
+    }
+
     /// When set to `true` the help string will be displayed on the line after the argument and
     /// indented once. This can be helpful for arguments with very long or complex help messages.
     /// This can also be helpful for arguments with very long flag names, or many/long value names.
@@ -1,7 +1,9 @@
 // Std
 use std::convert::From;
 use std::fmt::{Display, Formatter, Result};
+use std::any::Any;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::result::Result as StdResult;
 use std::ffi::{OsStr, OsString};
 use std::mem;
@@ -12,6 +14,7 @@ use vec_map::{self, VecMap};
 // Internal
 use Arg;
 use args::{ArgSettings, Base, Switched, AnyArg, DispOrder};
+use validators::Check;
 
 #[derive(Default, Clone, Debug)]
 #[doc(hidden)]
@@ -47,7 +50,11 @@ impl<'a, 'b> From<Arg<'a, 'b>> for FlagBuilder<'a, 'b> {
 impl<'n, 'e> Display for FlagBuilder<'n, 'e> {
     fn fmt(&self, f: &mut Formatter) -> Result {
         if let Some(l) = self.s.long {
-            try!(write!(f, "--{}", l));
+            if self.b.is_set(ArgSettings::Negatable) {
+                try!(write!(f, "--[no-]{}", l));
+            } else {
+                try!(write!(f, "--{}", l));
+            }
         } else {
             try!(write!(f, "-{}", self.s.short.unwrap()));
         }
@@ -74,10 +81,18 @@ impl<'n, 'e> AnyArg<'n, 'e> for FlagBuilder<'n, 'e> {
     fn possible_vals(&self) -> Option<&[&'e str]> { None }
     fn validator(&self) -> Option<&Rc<Fn(String) -> StdResult<(), String>>> { None }
     fn validator_os(&self) -> Option<&Rc<Fn(&OsStr) -> StdResult<(), OsString>>> { None }
+    fn possible_keys(&self) -> Option<&[&'e str]> { None }
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>> { None }
+    fn value_parser(&self) -> Option<&Rc<Fn(&str) -> StdResult<Arc<Any + Send + Sync>, String>>> {
+        None
+    }
     fn min_vals(&self) -> Option<u64> { None }
+    fn checks(&self) -> &[Check] { &[] }
+    fn val_hint(&self) -> Option<&str> { None }
     fn short(&self) -> Option<char> { self.s.short }
     fn long(&self) -> Option<&'e str> { self.s.long }
     fn val_delim(&self) -> Option<char> { None }
+    fn kv_delim(&self) -> Option<char> { None }
     fn help(&self) -> Option<&'e str> { self.b.help }
     fn long_help(&self) -> Option<&'e str> { self.b.long_help }
     fn val_terminator(&self) -> Option<&'e str> { None }
@@ -85,6 +100,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for FlagBuilder<'n, 'e> {
     fn default_vals_ifs(&self) -> Option<vec_map::Values<(&'n str, Option<&'e OsStr>, &'e OsStr)>> {
         None
     }
+    fn env(&self) -> Option<&'e OsStr> { None }
     fn longest_filter(&self) -> bool { self.s.long.is_some() }
     fn aliases(&self) -> Option<Vec<&'e str>> {
         if let Some(ref aliases) = self.s.aliases {
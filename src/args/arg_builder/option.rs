@@ -1,6 +1,8 @@
 // Std
 use std::fmt::{Display, Formatter, Result};
+use std::any::Any;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::result::Result as StdResult;
 use std::ffi::{OsStr, OsString};
 use std::mem;
@@ -10,6 +12,7 @@ use vec_map::{self, VecMap};
 
 // Internal
 use args::{ArgSettings, AnyArg, Base, Switched, Valued, Arg, DispOrder};
+use validators::Check;
 
 #[allow(missing_debug_implementations)]
 #[doc(hidden)]
@@ -66,7 +69,7 @@ impl<'n, 'e> Display for OptBuilder<'n, 'e> {
         if let Some(ref vec) = self.v.val_names {
             let mut it = vec.iter().peekable();
             while let Some((_, val)) = it.next() {
-                try!(write!(f, "<{}>", val));
+                try!(write!(f, "<{}{}>", val, self.v.hint_suffix()));
                 if it.peek().is_some() {
                     try!(write!(f, " "));
                 }
@@ -78,7 +81,7 @@ impl<'n, 'e> Display for OptBuilder<'n, 'e> {
         } else if let Some(num) = self.v.num_vals {
             let mut it = (0..num).peekable();
             while let Some(_) = it.next() {
-                try!(write!(f, "<{}>", self.b.name));
+                try!(write!(f, "<{}{}>", self.b.name, self.v.hint_suffix()));
                 if it.peek().is_some() {
                     try!(write!(f, " "));
                 }
@@ -88,8 +91,9 @@ impl<'n, 'e> Display for OptBuilder<'n, 'e> {
             }
         } else {
             try!(write!(f,
-                        "<{}>{}",
+                        "<{}{}>{}",
                         self.b.name,
+                        self.v.hint_suffix(),
                         if self.is_set(ArgSettings::Multiple) {
                             "..."
                         } else {
@@ -117,16 +121,32 @@ impl<'n, 'e> AnyArg<'n, 'e> for OptBuilder<'n, 'e> {
     fn val_terminator(&self) -> Option<&'e str> { self.v.terminator }
     fn num_vals(&self) -> Option<u64> { self.v.num_vals }
     fn possible_vals(&self) -> Option<&[&'e str]> { self.v.possible_vals.as_ref().map(|o| &o[..]) }
+    fn possible_keys(&self) -> Option<&[&'e str]> {
+        self.v.possible_keys.as_ref().map(|o| &o[..])
+    }
     fn validator(&self) -> Option<&Rc<Fn(String) -> StdResult<(), String>>> {
         self.v.validator.as_ref()
     }
     fn validator_os(&self) -> Option<&Rc<Fn(&OsStr) -> StdResult<(), OsString>>> {
         self.v.validator_os.as_ref()
     }
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>> { self.v.completer.as_ref() }
+    fn value_parser(&self) -> Option<&Rc<Fn(&str) -> StdResult<Arc<Any + Send + Sync>, String>>> {
+        self.v.value_parser.as_ref()
+    }
     fn min_vals(&self) -> Option<u64> { self.v.min_vals }
+    fn checks(&self) -> &[Check] { &self.v.checks[..] }
+    fn val_hint(&self) -> Option<&str> { self.v.val_hint.as_ref().map(|s| &s[..]) }
     fn short(&self) -> Option<char> { self.s.short }
     fn long(&self) -> Option<&'e str> { self.s.long }
     fn val_delim(&self) -> Option<char> { self.v.val_delim }
+    fn kv_delim(&self) -> Option<char> {
+        if self.b.is_set(ArgSettings::KeyValue) {
+            Some(self.v.kv_delim.unwrap_or('='))
+        } else {
+            None
+        }
+    }
     fn takes_value(&self) -> bool { true }
     fn help(&self) -> Option<&'e str> { self.b.help }
     fn long_help(&self) -> Option<&'e str> { self.b.long_help }
@@ -134,6 +154,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for OptBuilder<'n, 'e> {
     fn default_vals_ifs(&self) -> Option<vec_map::Values<(&'n str, Option<&'e OsStr>, &'e OsStr)>> {
         self.v.default_vals_ifs.as_ref().map(|vm| vm.values())
     }
+    fn env(&self) -> Option<&'e OsStr> { self.v.env }
     fn longest_filter(&self) -> bool { true }
     fn aliases(&self) -> Option<Vec<&'e str>> {
         if let Some(ref aliases) = self.s.aliases {
@@ -1,7 +1,9 @@
 // Std
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter, Result};
+use std::any::Any;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::result::Result as StdResult;
 use std::ffi::{OsStr, OsString};
 use std::mem;
@@ -13,6 +15,7 @@ use vec_map::{self, VecMap};
 use Arg;
 use args::{ArgSettings, Base, Valued, AnyArg, DispOrder};
 use INTERNAL_ERROR_MSG;
+use validators::Check;
 
 #[allow(missing_debug_implementations)]
 #[doc(hidden)]
@@ -98,11 +101,11 @@ impl<'n, 'e> Display for PosBuilder<'n, 'e> {
                         "{}",
                         names
                             .values()
-                            .map(|n| format!("<{}>", n))
+                            .map(|n| format!("<{}{}>", n, self.v.hint_suffix()))
                             .collect::<Vec<_>>()
                             .join(" ")));
         } else {
-            try!(write!(f, "<{}>", self.b.name));
+            try!(write!(f, "<{}{}>", self.b.name, self.v.hint_suffix()));
         }
         if self.b.settings.is_set(ArgSettings::Multiple) && (self.v.val_names.is_none() || self.v.val_names.as_ref().unwrap().len() == 1) {
             try!(write!(f, "..."));
@@ -128,16 +131,32 @@ impl<'n, 'e> AnyArg<'n, 'e> for PosBuilder<'n, 'e> {
     fn val_terminator(&self) -> Option<&'e str> { self.v.terminator }
     fn num_vals(&self) -> Option<u64> { self.v.num_vals }
     fn possible_vals(&self) -> Option<&[&'e str]> { self.v.possible_vals.as_ref().map(|o| &o[..]) }
+    fn possible_keys(&self) -> Option<&[&'e str]> {
+        self.v.possible_keys.as_ref().map(|o| &o[..])
+    }
     fn validator(&self) -> Option<&Rc<Fn(String) -> StdResult<(), String>>> {
         self.v.validator.as_ref()
     }
     fn validator_os(&self) -> Option<&Rc<Fn(&OsStr) -> StdResult<(), OsString>>> {
         self.v.validator_os.as_ref()
     }
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>> { self.v.completer.as_ref() }
+    fn value_parser(&self) -> Option<&Rc<Fn(&str) -> StdResult<Arc<Any + Send + Sync>, String>>> {
+        self.v.value_parser.as_ref()
+    }
     fn min_vals(&self) -> Option<u64> { self.v.min_vals }
+    fn checks(&self) -> &[Check] { &self.v.checks[..] }
+    fn val_hint(&self) -> Option<&str> { self.v.val_hint.as_ref().map(|s| &s[..]) }
     fn short(&self) -> Option<char> { None }
     fn long(&self) -> Option<&'e str> { None }
     fn val_delim(&self) -> Option<char> { self.v.val_delim }
+    fn kv_delim(&self) -> Option<char> {
+        if self.b.is_set(ArgSettings::KeyValue) {
+            Some(self.v.kv_delim.unwrap_or('='))
+        } else {
+            None
+        }
+    }
     fn takes_value(&self) -> bool { true }
     fn help(&self) -> Option<&'e str> { self.b.help }
     fn long_help(&self) -> Option<&'e str> { self.b.long_help }
@@ -145,6 +164,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for PosBuilder<'n, 'e> {
         self.v.default_vals_ifs.as_ref().map(|vm| vm.values())
     }
     fn default_val(&self) -> Option<&'e OsStr> { self.v.default_val }
+    fn env(&self) -> Option<&'e OsStr> { self.v.env }
     fn longest_filter(&self) -> bool { true }
     fn aliases(&self) -> Option<Vec<&'e str>> { None }
 }
@@ -1,9 +1,12 @@
+use std::any::Any;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::ffi::{OsStr, OsString};
 
 use vec_map::VecMap;
 
 use Arg;
+use validators::Check;
 
 #[allow(missing_debug_implementations)]
 #[derive(Clone)]
@@ -11,15 +14,22 @@ pub struct Valued<'a, 'b>
     where 'a: 'b
 {
     pub possible_vals: Option<Vec<&'b str>>,
+    pub possible_keys: Option<Vec<&'b str>>,
     pub val_names: Option<VecMap<&'b str>>,
     pub num_vals: Option<u64>,
     pub max_vals: Option<u64>,
     pub min_vals: Option<u64>,
     pub validator: Option<Rc<Fn(String) -> Result<(), String>>>,
     pub validator_os: Option<Rc<Fn(&OsStr) -> Result<(), OsString>>>,
+    pub completer: Option<Rc<Fn(&str) -> Vec<String>>>,
+    pub value_parser: Option<Rc<Fn(&str) -> Result<Arc<Any + Send + Sync>, String>>>,
+    pub checks: Vec<Check>,
+    pub val_hint: Option<String>,
     pub val_delim: Option<char>,
+    pub kv_delim: Option<char>,
     pub default_val: Option<&'b OsStr>,
     pub default_vals_ifs: Option<VecMap<(&'a str, Option<&'b OsStr>, &'b OsStr)>>,
+    pub env: Option<&'b OsStr>,
     pub terminator: Option<&'b str>,
 }
 
@@ -27,21 +37,33 @@ impl<'n, 'e> Default for Valued<'n, 'e> {
     fn default() -> Self {
         Valued {
             possible_vals: None,
+            possible_keys: None,
             num_vals: None,
             min_vals: None,
             max_vals: None,
             val_names: None,
             validator: None,
             validator_os: None,
+            completer: None,
+            value_parser: None,
+            checks: vec![],
+            val_hint: None,
             val_delim: None,
+            kv_delim: None,
             default_val: None,
             default_vals_ifs: None,
+            env: None,
             terminator: None,
         }
     }
 }
 
 impl<'n, 'e> Valued<'n, 'e> {
+    // The `: hint` following the name in each `<name>` placeholder, if any
+    pub fn hint_suffix(&self) -> String {
+        self.val_hint.as_ref().map_or(String::new(), |h| format!(": {}", h))
+    }
+
     pub fn fill_in(&mut self) {
         if let Some(ref vec) = self.val_names {
             if vec.len() > 1 {
@@ -5,7 +5,7 @@ use std::ops::Deref;
 use std::mem;
 
 // Internal
-use args::{ArgMatches, MatchedArg, SubCommand};
+use args::{ArgMatches, MatchedArg, SubCommand, ValueSource};
 use args::AnyArg;
 use args::settings::ArgSettings;
 
@@ -22,8 +22,8 @@ impl<'a> ArgMatcher<'a> {
 
     pub fn propagate(&mut self, arg: &'a str) {
         debugln!("ArgMatcher::propagate: arg={}", arg);
-        let vals: Vec<_> = if let Some(ma) = self.get(arg) {
-            ma.vals.clone()
+        let (vals, indices, source, parsed, kv_delim) = if let Some(ma) = self.get(arg) {
+            (ma.vals.clone(), ma.indices.clone(), ma.source, ma.parsed.clone(), ma.kv_delim)
         } else {
             debugln!("ArgMatcher::propagate: arg wasn't used");
             return;
@@ -34,10 +34,18 @@ impl<'a> ArgMatcher<'a> {
                     let mut gma = MatchedArg::new();
                     gma.occurs += 1;
                     gma.vals = vals.clone();
+                    gma.indices = indices.clone();
+                    gma.source = source;
+                    gma.parsed = parsed.clone();
+                    gma.kv_delim = kv_delim;
                     gma
                 });
                 if sma.vals.is_empty() {
                     sma.vals = vals.clone();
+                    sma.indices = indices.clone();
+                    sma.source = source;
+                    sma.parsed = parsed.clone();
+                    sma.kv_delim = kv_delim;
                 }
             }
             let mut am = ArgMatcher(mem::replace(&mut sc.matches, ArgMatches::new()));
@@ -54,6 +62,15 @@ impl<'a> ArgMatcher<'a> {
 
     pub fn remove(&mut self, arg: &str) { self.0.args.remove(arg); }
 
+    // Records that a negatable flag was turned off with `--no-<long>`
+    pub fn negate(&mut self, arg: &'a str) {
+        if !self.0.negated.contains(&arg) {
+            self.0.negated.push(arg);
+        }
+    }
+
+    pub fn unnegate(&mut self, arg: &str) { self.0.negated.retain(|&n| n != arg); }
+
     pub fn remove_all(&mut self, args: &[&str]) {
         for &arg in args {
             self.0.args.remove(arg);
@@ -99,11 +116,37 @@ impl<'a> ArgMatcher<'a> {
         let ma = self.entry(arg).or_insert(MatchedArg {
             occurs: 0,
             vals: Vec::with_capacity(1),
+            indices: Vec::with_capacity(1),
+            source: ValueSource::CommandLine,
+            parsed: vec![],
+            kv_delim: None,
         });
         // let len = ma.vals.len() + 1;
         ma.vals.push(val.to_owned());
     }
 
+    pub fn add_index_to(&mut self, arg: &'a str, idx: usize) {
+        let ma = self.entry(arg).or_insert(MatchedArg {
+            occurs: 0,
+            vals: Vec::new(),
+            indices: Vec::with_capacity(1),
+            source: ValueSource::CommandLine,
+            parsed: vec![],
+            kv_delim: None,
+        });
+        ma.indices.push(idx);
+    }
+
+    pub fn set_source(&mut self, arg: &str, source: ValueSource) {
+        if let Some(ma) = self.get_mut(arg) {
+            ma.source = source;
+            if source != ValueSource::CommandLine {
+                // Values which weren't supplied at runtime don't have a position in argv
+                ma.indices.clear();
+            }
+        }
+    }
+
     pub fn needs_more_vals<'b, A>(&self, o: &A) -> bool
         where A: AnyArg<'a, 'b>
     {
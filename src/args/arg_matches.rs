@@ -1,13 +1,14 @@
 // Std
+use std::any::Any;
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::{OsStr, OsString};
-use std::iter::Map;
+use std::iter::{Cloned, Map};
 use std::slice::Iter;
 
 // Internal
 use INVALID_UTF8;
-use args::MatchedArg;
+use args::{MatchedArg, ValueSource};
 use args::SubCommand;
 
 /// Used to get information about the arguments that where supplied to the program at runtime by
@@ -65,6 +66,8 @@ pub struct ArgMatches<'a> {
     pub subcommand: Option<Box<SubCommand<'a>>>,
     #[doc(hidden)]
     pub usage: Option<String>,
+    #[doc(hidden)]
+    pub negated: Vec<&'a str>,
 }
 
 impl<'a> Default for ArgMatches<'a> {
@@ -73,6 +76,7 @@ impl<'a> Default for ArgMatches<'a> {
             args: HashMap::new(),
             subcommand: None,
             usage: None,
+            negated: vec![],
         }
     }
 }
@@ -216,6 +220,47 @@ impl<'a> ArgMatches<'a> {
         None
     }
 
+    /// Gets the `key=value` pairs of an argument set with [`Arg::key_value`] as a map ordered by
+    /// key. When a key was given more than once, the last value wins. If the argument wasn't
+    /// present at runtime it returns `None`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if any of the values contain invalid UTF-8 code points.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("define")
+    ///         .short("D")
+    ///         .multiple(true)
+    ///         .number_of_values(1)
+    ///         .key_value(true))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "-D", "opt=2", "-D", "debug=", "-D", "opt=3"
+    ///     ]);
+    /// let defines = m.values_of_map("define").unwrap();
+    /// assert_eq!(defines.into_iter().collect::<Vec<_>>(), [("debug", ""), ("opt", "3")]);
+    /// ```
+    /// [`Arg::key_value`]: ./struct.Arg.html#method.key_value
+    pub fn values_of_map<S: AsRef<str>>(&self, name: S) -> Option<BTreeMap<&str, &str>> {
+        self.args.get(name.as_ref()).map(|arg| {
+            let d = arg.kv_delim.unwrap_or('=');
+            arg.vals
+                .iter()
+                .map(|v| {
+                    let v = v.to_str().expect(INVALID_UTF8);
+                    match v.find(d) {
+                        Some(i) => (&v[..i], &v[i + d.len_utf8()..]),
+                        None => (v, ""),
+                    }
+                })
+                .collect()
+        })
+    }
+
     /// Gets the lossy values of a specific argument. If the option wasn't present at runtime
     /// it returns `None`. A lossy value is one where if it contains invalid UTF-8 code points,
     /// those invalid points will be replaced with `\u{FFFD}`
@@ -290,6 +335,77 @@ impl<'a> ArgMatches<'a> {
         None
     }
 
+    /// Gets the typed value of an argument which has an [`Arg::value_parser`], i.e. the result of
+    /// parsing the string returned by [`ArgMatches::value_of`]. If the argument wasn't present at
+    /// runtime, or has no value parser, returns `None`.
+    ///
+    /// *NOTE:* If getting a value for an option or positional argument that allows multiples,
+    /// prefer [`ArgMatches::get_all`] as this method will only return the *first* value.
+    ///
+    /// # Panics
+    ///
+    /// This method will [`panic!`] if `T` isn't the type returned by the argument's
+    /// [`Arg::value_parser`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("jobs")
+    ///         .short("j")
+    ///         .value_parser(str::parse::<usize>))
+    ///     .get_matches_from(vec!["myapp", "-j", "4"]);
+    ///
+    /// assert_eq!(m.get::<usize>("jobs"), Some(&4));
+    /// ```
+    /// [`Arg::value_parser`]: ./struct.Arg.html#method.value_parser
+    /// [`ArgMatches::value_of`]: ./struct.ArgMatches.html#method.value_of
+    /// [`ArgMatches::get_all`]: ./struct.ArgMatches.html#method.get_all
+    /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
+    pub fn get<T: Any>(&self, name: &str) -> Option<&T> {
+        self.get_all(name).and_then(|vals| vals.into_iter().next())
+    }
+
+    /// Gets all the typed values of an argument which has an [`Arg::value_parser`], in the same
+    /// order as [`ArgMatches::values_of`]. If the argument wasn't present at runtime, or has no
+    /// value parser, returns `None`.
+    ///
+    /// # Panics
+    ///
+    /// This method will [`panic!`] if `T` isn't the type returned by the argument's
+    /// [`Arg::value_parser`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("sizes")
+    ///         .multiple(true)
+    ///         .value_parser(str::parse::<u32>))
+    ///     .get_matches_from(vec!["myapp", "1", "2", "3"]);
+    ///
+    /// assert_eq!(m.get_all::<u32>("sizes"), Some(vec![&1, &2, &3]));
+    /// ```
+    /// [`Arg::value_parser`]: ./struct.Arg.html#method.value_parser
+    /// [`ArgMatches::values_of`]: ./struct.ArgMatches.html#method.values_of
+    /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
+    pub fn get_all<T: Any>(&self, name: &str) -> Option<Vec<&T>> {
+        let arg = match self.args.get(name) {
+            Some(arg) if !arg.parsed.is_empty() => arg,
+            _ => return None,
+        };
+        Some(arg.parsed
+            .iter()
+            .map(|v| {
+                (**v).downcast_ref::<T>().unwrap_or_else(|| {
+                    panic!("The values of '{}' weren't parsed into the requested type", name)
+                })
+            })
+            .collect())
+    }
+
     /// Returns `true` if an argument was present at runtime, otherwise `false`.
     ///
     /// # Examples
@@ -357,6 +473,188 @@ impl<'a> ArgMatches<'a> {
         self.args.get(name.as_ref()).map_or(0, |a| a.occurs)
     }
 
+    /// Gets the state of a flag which may have been turned off with `--no-<long>`, as set by
+    /// [`Arg::negatable`]. Returns `Some(true)` if the flag was present, `Some(false)` if it was
+    /// turned off, or `None` if neither form was used. For flags which aren't negatable this is
+    /// equivalent to `Some(true)` when [`ArgMatches::is_present`] is `true`, otherwise `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .negatable(true));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["myprog", "--no-color", "--color"]);
+    /// assert_eq!(m.flag_state("color"), Some(true));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["myprog", "--no-color"]);
+    /// assert_eq!(m.flag_state("color"), Some(false));
+    ///
+    /// let m = app.get_matches_from(vec!["myprog"]);
+    /// assert_eq!(m.flag_state("color"), None);
+    /// ```
+    /// [`Arg::negatable`]: ./struct.Arg.html#method.negatable
+    /// [`ArgMatches::is_present`]: ./struct.ArgMatches.html#method.is_present
+    pub fn flag_state<S: AsRef<str>>(&self, name: S) -> Option<bool> {
+        if self.is_present(name.as_ref()) {
+            Some(true)
+        } else if self.negated.contains(&name.as_ref()) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns where the value(s) of an argument came from, or `None` if the argument isn't
+    /// present at all. This is useful when merging command line arguments with other sources of
+    /// configuration, such as a config file, which should override a default value but never a
+    /// value the user supplied explicitly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ValueSource};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("output")
+    ///         .long("output")
+    ///         .default_value("out.txt"))
+    ///     .arg(Arg::with_name("input")
+    ///         .long("input")
+    ///         .default_value("in.txt"))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "--input", "data.txt"
+    ///     ]);
+    ///
+    /// assert_eq!(m.value_source("input"), Some(ValueSource::CommandLine));
+    /// assert_eq!(m.value_source("output"), Some(ValueSource::DefaultValue));
+    /// assert_eq!(m.value_source("other"), None);
+    /// ```
+    /// [`ValueSource`]: ./enum.ValueSource.html
+    pub fn value_source<S: AsRef<str>>(&self, name: S) -> Option<ValueSource> {
+        self.args.get(name.as_ref()).map(|a| a.source)
+    }
+
+    /// Gets the index of the first value of an argument (or of the flag itself, for arguments
+    /// which don't take a value) as it appeared in the list of arguments the program was given.
+    /// Returns `None` if the argument wasn't used at runtime, or if its only values came from a
+    /// default value or an environment variable.
+    ///
+    /// Index `0` is always the binary name, so the first argument proper is index `1`. This
+    /// holds even with [`AppSettings::NoBinaryName`].
+    ///
+    /// **NOTE:** These are "clap indices" rather than strict positions in the `argv` vector,
+    /// because clap may split a single element into several pieces. Each piece is given its own
+    /// index and every element after it is shifted along by one. That happens for combined short
+    /// flags (`-fzF` is three indices), for values attached to an option (`--opt=val` and `-oval`
+    /// are two indices each) and for values split on a [delimiter] (`-o=1,2,3` is four indices).
+    /// Indices are always increasing in the order things appeared, which is enough to tell which
+    /// of two arguments came first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("flag")
+    ///         .short("f"))
+    ///     .arg(Arg::with_name("option")
+    ///         .short("o")
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec!["myapp", "-f", "-o", "val"]);
+    ///             // ARGV indices: ^0       ^1    ^2    ^3
+    ///             // clap indices:          ^1          ^3
+    ///
+    /// assert_eq!(m.index_of("flag"), Some(1));
+    /// assert_eq!(m.index_of("option"), Some(3));
+    /// ```
+    ///
+    /// Combined flags and attached values shift everything after them:
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("flag")
+    ///         .short("f"))
+    ///     .arg(Arg::with_name("other")
+    ///         .short("z"))
+    ///     .arg(Arg::with_name("option")
+    ///         .short("o")
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec!["myapp", "-fz", "-o=val"]);
+    ///             // ARGV indices: ^0      ^1     ^2
+    ///             // clap indices:         ^1,2   ^3,4
+    ///
+    /// assert_eq!(m.index_of("flag"), Some(1));
+    /// assert_eq!(m.index_of("other"), Some(2));
+    /// assert_eq!(m.index_of("option"), Some(4));
+    /// ```
+    /// [`AppSettings::NoBinaryName`]: ./enum.AppSettings.html#variant.NoBinaryName
+    /// [delimiter]: ./struct.Arg.html#method.value_delimiter
+    pub fn index_of<S: AsRef<str>>(&self, name: S) -> Option<usize> {
+        if let Some(arg) = self.args.get(name.as_ref()) {
+            if let Some(i) = arg.indices.get(0) {
+                return Some(*i);
+            }
+        }
+        None
+    }
+
+    /// Gets all indices of an argument, in the order they appeared. For arguments that take
+    /// values these are the indices of each value, for flags they're the index of each
+    /// occurrence. See [`ArgMatches::index_of`] for how indices are counted.
+    ///
+    /// Returns `None` if the argument wasn't used at runtime, or if its only values came from a
+    /// default value or an environment variable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("option")
+    ///         .short("o")
+    ///         .use_delimiter(true)
+    ///         .multiple(true))
+    ///     .get_matches_from(vec!["myapp", "-o=val1,val2,val3"]);
+    ///             // ARGV indices: ^0       ^1
+    ///             // clap indices:             ^2   ^3   ^4
+    ///
+    /// assert_eq!(m.indices_of("option").unwrap().collect::<Vec<_>>(), &[2, 3, 4]);
+    /// ```
+    ///
+    /// Indices make it possible to interleave the values of separate arguments:
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("include")
+    ///         .short("i")
+    ///         .takes_value(true)
+    ///         .multiple(true)
+    ///         .number_of_values(1))
+    ///     .arg(Arg::with_name("exclude")
+    ///         .short("x")
+    ///         .takes_value(true)
+    ///         .multiple(true)
+    ///         .number_of_values(1))
+    ///     .get_matches_from(vec!["myapp", "-i", "a", "-x", "b", "-i", "c"]);
+    ///
+    /// assert_eq!(m.indices_of("include").unwrap().collect::<Vec<_>>(), &[2, 6]);
+    /// assert_eq!(m.indices_of("exclude").unwrap().collect::<Vec<_>>(), &[4]);
+    /// ```
+    /// [`ArgMatches::index_of`]: ./struct.ArgMatches.html#method.index_of
+    pub fn indices_of<S: AsRef<str>>(&'a self, name: S) -> Option<Indices<'a>> {
+        if let Some(arg) = self.args.get(name.as_ref()) {
+            if !arg.indices.is_empty() {
+                return Some(Indices { iter: arg.indices.iter().cloned() });
+            }
+        }
+        None
+    }
+
     /// Because [`Subcommand`]s are essentially "sub-[`App`]s" they have their own [`ArgMatches`]
     /// as well. This method returns the [`ArgMatches`] for a particular subcommand or `None` if
     /// the subcommand wasn't present at runtime.
@@ -636,3 +934,53 @@ fn test_default_osvalues() {
     let mut values: OsValues = OsValues::default();
     assert_eq!(values.next(), None);
 }
+
+/// An iterator for getting the indices of an argument via the [`ArgMatches::indices_of`] method.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg};
+/// let m = App::new("myapp")
+///     .arg(Arg::with_name("output")
+///         .short("o")
+///         .takes_value(true))
+///     .get_matches_from(vec!["myapp", "-o", "val"]);
+///
+/// let mut indices = m.indices_of("output").unwrap();
+/// assert_eq!(indices.next(), Some(2));
+/// assert_eq!(indices.next(), None);
+/// ```
+/// [`ArgMatches::indices_of`]: ./struct.ArgMatches.html#method.indices_of
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct Indices<'a> {
+    iter: Cloned<Iter<'a, usize>>,
+}
+
+impl<'a> Iterator for Indices<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> { self.iter.next() }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<'a> DoubleEndedIterator for Indices<'a> {
+    fn next_back(&mut self) -> Option<usize> { self.iter.next_back() }
+}
+
+impl<'a> ExactSizeIterator for Indices<'a> {}
+
+/// Creates an empty iterator.
+impl Default for Indices<'static> {
+    fn default() -> Self {
+        static EMPTY: [usize; 0] = [];
+        Indices { iter: EMPTY[..].iter().cloned() }
+    }
+}
+
+#[test]
+fn test_default_indices() {
+    let mut indices: Indices = Indices::default();
+    assert_eq!(indices.next(), None);
+}
@@ -5,6 +5,9 @@ use std::fmt::{Debug, Formatter, Result};
 #[cfg(feature = "yaml")]
 use yaml_rust::Yaml;
 
+#[cfg(any(feature = "yaml", feature = "json", feature = "toml"))]
+use definition::Node;
+
 /// `ArgGroup`s are a family of related [arguments] and way for you to express, "Any of these
 /// arguments". By placing arguments in a logical group, you can create easier requirement and
 /// exclusion rules instead of having to list each argument individually, or when you want a rule
@@ -458,22 +461,28 @@ impl<'a, 'z> From<&'z ArgGroup<'a>> for ArgGroup<'a> {
 #[cfg(feature = "yaml")]
 impl<'a> From<&'a BTreeMap<Yaml, Yaml>> for ArgGroup<'a> {
     fn from(b: &'a BTreeMap<Yaml, Yaml>) -> Self {
+        ArgGroup::from_entries(b.iter().map(|(k, v)| (k.as_str().unwrap(), v)).collect())
+    }
+}
+
+impl<'a> ArgGroup<'a> {
+    // Builds the group from the keys of its map in a YAML, JSON or TOML document, which either
+    // are its settings or a single key naming it with the settings as value
+    #[doc(hidden)]
+    #[cfg(any(feature = "yaml", feature = "json", feature = "toml"))]
+    pub fn from_entries<N: Node>(b: Vec<(&'a str, &'a N)>) -> Self {
         // We WANT this to panic on error...so expect() is good.
         let mut a = ArgGroup::default();
         let group_settings = if b.len() == 1 {
-            let name_yml = b.keys().nth(0).expect("failed to get name");
-            let name_str = name_yml.as_str().expect("failed to convert arg YAML name to str");
+            let (name_str, settings) = b[0];
             a.name = name_str;
-            b.get(name_yml)
-                .expect("failed to get name_str")
-                .as_hash()
-                .expect("failed to convert to a hash")
+            settings.entries().expect("failed to convert to a hash")
         } else {
             b
         };
 
-        for (k, v) in group_settings.iter() {
-            a = match k.as_str().unwrap() {
+        for (k, v) in group_settings {
+            a = match k {
                 "required" => a.required(v.as_bool().unwrap()),
                 "multiple" => a.multiple(v.as_bool().unwrap()),
                 "args" => yaml_vec_or_str!(v, a, arg),
@@ -492,7 +501,7 @@ impl<'a> From<&'a BTreeMap<Yaml, Yaml>> for ArgGroup<'a> {
                     a
                 }
                 s => {
-                    panic!("Unknown ArgGroup setting '{}' in YAML file for \
+                    panic!("Unknown ArgGroup setting '{}' in app definition for \
                              ArgGroup '{}'",
                            s,
                            a.name)
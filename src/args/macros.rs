@@ -23,8 +23,15 @@ macro_rules! yaml_tuple3 {
             if let Some(vec) = $v.as_vec() {
                 for ys in vec {
                     if let Some(tup) = ys.as_vec() {
-                        debug_assert_eq!(3, tup.len());
-                        $a = $a.$c(yaml_str!(tup[0]), yaml_opt_str!(tup[1]), yaml_str!(tup[2]));
+                        // `[arg, default]` is short for `[arg, null, default]` (TOML has no null)
+                        if tup.len() == 2 {
+                            $a = $a.$c(yaml_str!(tup[0]), None, yaml_str!(tup[1]));
+                        } else {
+                            debug_assert_eq!(3, tup.len());
+                            $a = $a.$c(yaml_str!(tup[0]),
+                                       yaml_opt_str!(tup[1]),
+                                       yaml_str!(tup[2]));
+                        }
                     } else {
                         panic!("Failed to convert YAML value to vec");
                     }
@@ -60,12 +67,14 @@ macro_rules! yaml_vec_or_str {
     };
 }
 
+// A string, or `None` for a YAML `null` (or `~`)
 macro_rules! yaml_opt_str {
     ($v:expr) => {{
         if $v.is_null() {
-            Some($v.as_str().unwrap_or_else(|| panic!("failed to convert YAML {:?} value to a string", $v)))
-        } else {
             None
+        } else {
+            Some($v.as_str()
+                .unwrap_or_else(|| panic!("failed to convert YAML {:?} value to a string", $v)))
         }
     }};
 }
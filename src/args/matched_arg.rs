@@ -1,5 +1,44 @@
 // Std
+use std::any::Any;
 use std::ffi::OsString;
+use std::sync::Arc;
+
+/// Describes where the value(s) of a matched argument came from. Obtained with
+/// [`ArgMatches::value_source`].
+///
+/// [`ArgMatches::value_source`]: ./struct.ArgMatches.html#method.value_source
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ValueSource {
+    /// The argument was used at runtime, i.e. the user typed it on the command line
+    CommandLine,
+    /// The value was taken from the environment variable set with [`Arg::env`]
+    ///
+    /// [`Arg::env`]: ./struct.Arg.html#method.env
+    Environment,
+    /// The value was read from a config file given to [`App::config_file`] or
+    /// [`App::config_source`]
+    ///
+    /// [`App::config_file`]: ./struct.App.html#method.config_file
+    /// [`App::config_source`]: ./struct.App.html#method.config_source
+    Config,
+    /// The value was filled in by [`Arg::default_value_if`] because its condition was met
+    ///
+    /// [`Arg::default_value_if`]: ./struct.Arg.html#method.default_value_if
+    ConditionalDefault,
+    /// The value was filled in by [`Arg::default_value`]
+    ///
+    /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
+    DefaultValue,
+    /// The value was typed in at a prompt because the argument was missing, see
+    /// [`AppSettings::PromptForMissing`]
+    ///
+    /// [`AppSettings::PromptForMissing`]: ./enum.AppSettings.html#variant.PromptForMissing
+    Prompt,
+}
+
+impl Default for ValueSource {
+    fn default() -> Self { ValueSource::CommandLine }
+}
 
 #[doc(hidden)]
 #[derive(Debug, Clone)]
@@ -8,6 +47,14 @@ pub struct MatchedArg {
     pub occurs: u64,
     #[doc(hidden)]
     pub vals: Vec<OsString>,
+    #[doc(hidden)]
+    pub indices: Vec<usize>,
+    #[doc(hidden)]
+    pub source: ValueSource,
+    #[doc(hidden)]
+    pub parsed: Vec<Arc<Any + Send + Sync>>,
+    #[doc(hidden)]
+    pub kv_delim: Option<char>,
 }
 
 impl Default for MatchedArg {
@@ -15,6 +62,10 @@ impl Default for MatchedArg {
         MatchedArg {
             occurs: 1,
             vals: Vec::with_capacity(1),
+            indices: Vec::with_capacity(1),
+            source: ValueSource::default(),
+            parsed: vec![],
+            kv_delim: None,
         }
     }
 }
@@ -2,9 +2,9 @@ pub use self::any_arg::{AnyArg, DispOrder};
 pub use self::arg::Arg;
 pub use self::arg_builder::{Base, Switched, Valued, FlagBuilder, OptBuilder, PosBuilder};
 pub use self::arg_matcher::ArgMatcher;
-pub use self::arg_matches::{Values, OsValues, ArgMatches};
+pub use self::arg_matches::{Values, OsValues, Indices, ArgMatches};
 pub use self::group::ArgGroup;
-pub use self::matched_arg::MatchedArg;
+pub use self::matched_arg::{MatchedArg, ValueSource};
 pub use self::settings::{ArgFlags, ArgSettings};
 pub use self::subcommand::SubCommand;
 
@@ -3,7 +3,7 @@ use std::ascii::AsciiExt;
 use std::str::FromStr;
 
 bitflags! {
-    flags Flags: u16 {
+    flags Flags: u32 {
         const REQUIRED         = 1 << 0,
         const MULTIPLE         = 1 << 1,
         const EMPTY_VALS       = 1 << 2,
@@ -20,6 +20,10 @@ bitflags! {
         const REQUIRE_EQUALS   = 1 << 13,
         const LAST             = 1 << 14,
         const HIDE_DEFAULT_VAL = 1 << 15,
+        const NEGATABLE        = 1 << 16,
+        const KEY_VALUE        = 1 << 17,
+        const SECRET           = 1 << 18,
+        const HIDE_ENV_VALS    = 1 << 19,
     }
 }
 
@@ -46,7 +50,11 @@ impl ArgFlags {
         AllowLeadingHyphen => ALLOW_TAC_VALS,
         RequireEquals => REQUIRE_EQUALS,
         Last => LAST,
-        HideDefaultValue => HIDE_DEFAULT_VAL
+        HideDefaultValue => HIDE_DEFAULT_VAL,
+        Negatable => NEGATABLE,
+        KeyValue => KEY_VALUE,
+        Secret => SECRET,
+        HideEnvValues => HIDE_ENV_VALS
     }
 }
 
@@ -91,6 +99,14 @@ pub enum ArgSettings {
     Last,
     /// Hides the default value from the help string
     HideDefaultValue,
+    /// The flag may be turned off again with `--no-<long>`
+    Negatable,
+    /// Each value is a `key=value` pair, such as `-D name=value`
+    KeyValue,
+    /// The value isn't echoed when it's prompted for
+    Secret,
+    /// Hides the value of the environment variable from the help string
+    HideEnvValues,
     #[doc(hidden)]
     RequiredUnlessAll,
     #[doc(hidden)]
@@ -117,6 +133,10 @@ impl FromStr for ArgSettings {
             "requireequals" => Ok(ArgSettings::RequireEquals),
             "last" => Ok(ArgSettings::Last),
             "hidedefaultvalue" => Ok(ArgSettings::HideDefaultValue),
+            "negatable" => Ok(ArgSettings::Negatable),
+            "keyvalue" => Ok(ArgSettings::KeyValue),
+            "secret" => Ok(ArgSettings::Secret),
+            "hideenvvalues" => Ok(ArgSettings::HideEnvValues),
             _ => Err("unknown ArgSetting, cannot convert from str".to_owned()),
         }
     }
@@ -160,6 +180,14 @@ mod test {
                    ArgSettings::Last);
         assert_eq!("hidedefaultvalue".parse::<ArgSettings>().unwrap(),
                    ArgSettings::HideDefaultValue);
+        assert_eq!("negatable".parse::<ArgSettings>().unwrap(),
+                   ArgSettings::Negatable);
+        assert_eq!("keyvalue".parse::<ArgSettings>().unwrap(),
+                   ArgSettings::KeyValue);
+        assert_eq!("secret".parse::<ArgSettings>().unwrap(),
+                   ArgSettings::Secret);
+        assert_eq!("hideenvvalues".parse::<ArgSettings>().unwrap(),
+                   ArgSettings::HideEnvValues);
         assert!("hahahaha".parse::<ArgSettings>().is_err());
     }
 }
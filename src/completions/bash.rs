@@ -41,7 +41,7 @@ impl<'a, 'b> BashGen<'a, 'b> {
     case \"${{cmd}}\" in
         {name})
             opts=\"{name_opts}\"
-            if [[ ${{cur}} == -* || ${{COMP_CWORD}} -eq 1 ]] ; then
+            if [[ ${{cur}} == -*{name_first_word} ]] ; then
                 COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}) )
                 return 0
             fi
@@ -51,7 +51,7 @@ impl<'a, 'b> BashGen<'a, 'b> {
                     COMPREPLY=()
                     ;;
             esac
-            COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}) )
+            COMPREPLY=( $(compgen -W {name_rest} -- ${{cur}}) )
             return 0
             ;;
         {subcmd_details}
@@ -64,6 +64,9 @@ complete -F _{name} -o bashdefault -o default {name}
                    name_opts = self.all_options_for_path(self.p.meta.bin_name.as_ref().unwrap()),
                    name_opts_details =
                        self.option_details_for_path(self.p.meta.bin_name.as_ref().unwrap()),
+                   name_first_word =
+                       self.first_word_for_path(self.p.meta.bin_name.as_ref().unwrap(), 1),
+                   name_rest = self.rest_for_path(self.p.meta.bin_name.as_ref().unwrap()),
                    subcmds = self.all_subcommands(),
                    subcmd_details = self.subcommand_details())
                .as_bytes());
@@ -97,7 +100,7 @@ complete -F _{name} -o bashdefault -o default {name}
             subcmd_dets = format!("{}
         {subcmd})
             opts=\"{sc_opts}\"
-            if [[ ${{cur}} == -* || ${{COMP_CWORD}} -eq {level} ]] ; then
+            if [[ ${{cur}} == -*{first_word} ]] ; then
                 COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}) )
                 return 0
             fi
@@ -107,13 +110,15 @@ complete -F _{name} -o bashdefault -o default {name}
                     COMPREPLY=()
                     ;;
             esac
-            COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}) )
+            COMPREPLY=( $(compgen -W {rest} -- ${{cur}}) )
             return 0
             ;;",
                                   subcmd_dets,
                                   subcmd = sc.replace("-", "__"),
                                   sc_opts = self.all_options_for_path(&*sc),
-                                  level = sc.split("__").map(|_| 1).fold(0, |acc, n| acc + n),
+                                  first_word =
+                                      self.first_word_for_path(&*sc, sc.split("__").count()),
+                                  rest = self.rest_for_path(&*sc),
                                   opts_details = self.option_details_for_path(&*sc));
         }
 
@@ -153,12 +158,45 @@ complete -F _{name} -o bashdefault -o default {name}
         opts
     }
 
+    fn parser_for_path(&self, path: &str) -> &Parser<'a, 'b> {
+        let mut p = self.p;
+        for sc in path.split("__").skip(1) {
+            p = &find_subcmd!(p, sc).unwrap().p;
+        }
+        p
+    }
+
+    // Positionals with a runtime completer are completed by the program, so rather than offering
+    // the static list for the first word after the command we must fall through to the callback
+    fn first_word_for_path(&self, path: &str, level: usize) -> String {
+        if self.has_pos_completer(path) {
+            String::new()
+        } else {
+            format!(" || ${{COMP_CWORD}} -eq {}", level)
+        }
+    }
+
+    fn rest_for_path(&self, path: &str) -> String {
+        if self.has_pos_completer(path) {
+            format!("\"{}\"", callback())
+        } else {
+            String::from("\"${opts}\"")
+        }
+    }
+
+    fn has_pos_completer(&self, path: &str) -> bool {
+        self.parser_for_path(path).positionals().any(|p| p.v.completer.is_some())
+    }
+
     fn vals_for(&self, o: &OptBuilder) -> String {
         debugln!("BashGen::vals_for: o={}", o.b.name);
         use args::AnyArg;
         let mut ret = String::new();
         let mut needs_quotes = true;
-        if let Some(vals) = o.possible_vals() {
+        if o.completer().is_some() {
+            needs_quotes = false;
+            ret = format!("$(compgen -W \"{}\" -- \"${{cur}}\")", callback());
+        } else if let Some(vals) = o.possible_vals() {
             needs_quotes = false;
             ret = format!("$(compgen -W \"{}\" -- ${{cur}})", vals.join(" "));
         } else if let Some(vec) = o.val_names() {
@@ -205,7 +243,10 @@ complete -F _{name} -o bashdefault -o default {name}
         let mut opts = shorts!(p).fold(String::new(), |acc, s| format!("{} -{}", acc, s));
         opts = format!("{} {}",
                        opts,
-                       longs!(p).fold(String::new(), |acc, l| format!("{} --{}", acc, l)));
+                       longs!(p)
+                           .map(|l| l.to_string())
+                           .chain(negated_longs!(p))
+                           .fold(String::new(), |acc, l| format!("{} --{}", acc, l)));
         opts = format!("{} {}",
                        opts,
                        p.positionals
@@ -228,3 +269,9 @@ complete -F _{name} -o bashdefault -o default {name}
         opts
     }
 }
+
+// Runs the program itself to get the candidates from an `Arg::completer`
+fn callback() -> String {
+    format!("$(\"${{COMP_WORDS[0]}}\" {} \"${{COMP_WORDS[@]:1:${{COMP_CWORD}}}}\" 2>/dev/null)",
+            completions::COMPLETE_CMD)
+}
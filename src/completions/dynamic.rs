@@ -0,0 +1,132 @@
+// Std
+use std::ffi::OsString;
+
+// Internal
+use app::parser::Parser;
+use args::{AnyArg, OptBuilder};
+use args::settings::ArgSettings;
+
+// Does this command, or any of its subcommands, have an argument with a runtime completer?
+pub fn has_completers(p: &Parser) -> bool {
+    p.opts().any(|o| o.v.completer.is_some()) ||
+    p.positionals().any(|pos| pos.v.completer.is_some()) ||
+    p.subcommands().any(|sc| has_completers(&sc.p))
+}
+
+// Works out which argument the last of `words` belongs to and returns the candidates for it.
+//
+// `words` is everything on the command line after the binary name, up to and including the
+// (possibly empty) word being completed. The words before that are walked in the same manner as
+// the parser would, but leniently, since the line is unfinished and may well be invalid.
+pub fn complete(p: &Parser, words: &[OsString]) -> Vec<String> {
+    debugln!("dynamic::complete: words={:?}", words);
+    let words: Vec<String> = words.iter().map(|w| w.to_string_lossy().into_owned()).collect();
+    let (cur, prev) = match words.split_last() {
+        Some((cur, prev)) => (&**cur, prev),
+        None => ("", &[][..]),
+    };
+
+    let mut p = p;
+    let mut pos_counter = 1;
+    let mut trailing = false;
+    // The option still waiting for values, and how many (0 means "as many as are given")
+    let mut pending: Option<(&OptBuilder, u64)> = None;
+    for w in prev {
+        if let Some((o, rem)) = pending {
+            if rem == 0 {
+                if !w.starts_with('-') || w == "-" {
+                    continue;
+                }
+                pending = None;
+            } else {
+                pending = if rem > 1 { Some((o, rem - 1)) } else { None };
+                continue;
+            }
+        }
+
+        if !trailing && w == "--" {
+            trailing = true;
+        } else if !trailing && w.starts_with("--") {
+            let mut parts = w[2..].splitn(2, '=');
+            let long = parts.next().unwrap_or("");
+            if let Some(o) = find_opt_by_long!(p, &long) {
+                if parts.next().is_none() {
+                    pending = Some((o, vals_wanted(o)));
+                }
+            }
+        } else if !trailing && w.starts_with('-') && w.len() > 1 {
+            for (i, c) in w[1..].char_indices() {
+                if let Some(o) = find_opt_by_short!(p, c) {
+                    if i + c.len_utf8() == w.len() - 1 {
+                        pending = Some((o, vals_wanted(o)));
+                    }
+                    break;
+                }
+            }
+        } else if let Some(sc) = if trailing { None } else { find_subcmd!(p, &**w) } {
+            debugln!("dynamic::complete: descending into {}", sc.p.meta.name);
+            p = &sc.p;
+            pos_counter = 1;
+        } else if let Some(pos) = p.positionals.get(pos_counter) {
+            if !pos.is_set(ArgSettings::Multiple) {
+                pos_counter += 1;
+            }
+        }
+    }
+
+    if let Some((o, rem)) = pending {
+        if rem != 0 || !cur.starts_with('-') {
+            return values_for(o, cur);
+        }
+    }
+    if !trailing && cur.starts_with("--") && cur.contains('=') {
+        let mut parts = cur[2..].splitn(2, '=');
+        let long = parts.next().unwrap_or("");
+        let val = parts.next().unwrap_or("");
+        return find_opt_by_long!(p, &long).map_or(vec![], |o| {
+            values_for(o, val).into_iter().map(|v| format!("--{}={}", long, v)).collect()
+        });
+    }
+    if !trailing && cur.starts_with('-') {
+        let mut names: Vec<String> = shorts!(p)
+            .map(|s| format!("-{}", s))
+            .chain(longs!(p).map(|l| format!("--{}", l)))
+            .chain(negated_longs!(p).map(|l| format!("--{}", l)))
+            .filter(|n| n.starts_with(cur))
+            .collect();
+        names.sort();
+        return names;
+    }
+
+    let mut ret = vec![];
+    if !trailing {
+        for sc in p.subcommands().filter(|sc| sc.p.meta.name.starts_with(cur)) {
+            ret.push(sc.p.meta.name.clone());
+        }
+    }
+    if let Some(pos) = p.positionals.get(pos_counter) {
+        ret.extend(values_for(pos, cur));
+    }
+    ret
+}
+
+fn vals_wanted(o: &OptBuilder) -> u64 {
+    match o.num_vals() {
+        Some(n) => n,
+        None if o.is_set(ArgSettings::Multiple) => 0,
+        None => 1,
+    }
+}
+
+fn values_for<'n, 'e, A>(a: &A, cur: &str) -> Vec<String>
+    where A: AnyArg<'n, 'e>
+{
+    debugln!("dynamic::values_for: arg={}, cur={}", a.name(), cur);
+    if let Some(f) = a.completer() {
+        f(cur)
+    } else if let Some(pv) = a.possible_vals() {
+        pv.iter().filter(|v| v.starts_with(cur)).map(|v| v.to_string()).collect()
+    } else {
+        vec![]
+    }
+}
@@ -0,0 +1,140 @@
+// Std
+use std::io::Write;
+
+// Internal
+use app::parser::Parser;
+use args::AnyArg;
+
+pub struct ElvishGen<'a, 'b>
+    where 'a: 'b
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> ElvishGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { ElvishGen { p: p } }
+
+    pub fn generate_to<W: Write>(&self, buf: &mut W) {
+        let bin_name = self.p.meta.bin_name.as_ref().unwrap();
+
+        let mut cmds = vec![];
+        let mut vals = vec![];
+        gen_elvish_inner(bin_name, self.p, &mut cmds, &mut vals);
+        // An empty `[]` would be a list rather than a map, which `has-key` can't be asked about
+        let values = if vals.is_empty() {
+            String::from("[&]")
+        } else {
+            format!("[\n{}\n    ]", vals.join("\n"))
+        };
+
+        let result = format!(r#"
+use str;
+
+set edit:completion:arg-completer[{bin_name}] = {{|@words|
+    fn spaces {{|n|
+        if (> $n 0) {{
+            repeat $n ' ' | str:join ''
+        }} else {{
+            put ' '
+        }}
+    }}
+    fn cand {{|text desc|
+        edit:complex-candidate $text &display=$text(spaces (- 16 (wcswidth $text)))$desc
+    }}
+    var command = '{bin_name}'
+    for word $words[1..-1] {{
+        if (str:has-prefix $word '-') {{
+            break
+        }}
+        set command = $command';'$word
+    }}
+    var values = {values}
+    var prev = $command';'$words[-2]
+    if (has-key $values $prev) {{
+        for value $values[$prev] {{
+            edit:complex-candidate $value
+        }}
+        return
+    }}
+    var completions = [
+{commands}
+    ]
+    if (has-key $completions $command) {{
+        $completions[$command]
+    }}
+}}
+"#,
+                             bin_name = bin_name,
+                             values = values,
+                             commands = cmds.join("\n"));
+
+        w!(buf, result.as_bytes());
+    }
+}
+
+// Escape string inside single quotes
+fn escape_string(string: &str) -> String { string.replace("'", "''") }
+
+fn get_tooltip<T: ToString>(help: Option<&str>, data: T) -> String {
+    match help {
+        Some(help) => escape_string(help),
+        _ => data.to_string(),
+    }
+}
+
+// Walks the command tree, adding a `&'<path>'= { cand ... }` entry for every (sub)command to `cmds`
+// and a `&'<path>;<switch>'= [ ... ]` entry to `vals` for every option with possible values
+fn gen_elvish_inner(path: &str, p: &Parser, cmds: &mut Vec<String>, vals: &mut Vec<String>) {
+    debugln!("ElvishGen::gen_elvish_inner: path={}", path);
+    let mut completions = String::new();
+    let preamble = String::from("\n            cand ");
+
+    for option in p.opts() {
+        let mut switches = vec![];
+        if let Some(data) = option.short() {
+            let tooltip = get_tooltip(option.help(), data);
+            completions.push_str(&*format!("{}-{} '{}'", preamble, data, tooltip));
+            switches.push(format!("-{}", data));
+        }
+        if let Some(data) = option.long() {
+            let tooltip = get_tooltip(option.help(), data);
+            completions.push_str(&*format!("{}--{} '{}'", preamble, data, tooltip));
+            switches.push(format!("--{}", data));
+        }
+        if let Some(pv) = option.possible_vals() {
+            let pv: Vec<_> = pv.iter().map(|v| format!("'{}'", escape_string(v))).collect();
+            for sw in switches {
+                vals.push(format!("        &'{}'= [{}]",
+                                  escape_string(&*format!("{};{}", path, sw)),
+                                  pv.join(" ")));
+            }
+        }
+    }
+
+    for flag in p.flags() {
+        if let Some(data) = flag.short() {
+            let tooltip = get_tooltip(flag.help(), data);
+            completions.push_str(&*format!("{}-{} '{}'", preamble, data, tooltip));
+        }
+        if let Some(data) = flag.long() {
+            let tooltip = get_tooltip(flag.help(), data);
+            completions.push_str(&*format!("{}--{} '{}'", preamble, data, tooltip));
+        }
+    }
+    for long in negated_longs!(p) {
+        completions.push_str(&*format!("{}--{} '--{}'", preamble, long, long));
+    }
+
+    for sc in p.subcommands() {
+        let data = &sc.p.meta.name;
+        let tooltip = get_tooltip(sc.p.meta.about, data);
+        completions.push_str(&*format!("{}{} '{}'", preamble, data, tooltip));
+    }
+
+    cmds.push(format!("        &'{}'= {{{}\n        }}", escape_string(path), completions));
+
+    for sc in p.subcommands() {
+        let sc_path = format!("{};{}", path, sc.p.meta.name);
+        gen_elvish_inner(&*sc_path, &sc.p, cmds, vals);
+    }
+}
@@ -91,6 +91,11 @@ fn gen_fish_inner(root_command: &str, comp_gen: &FishGen, parent_cmds: &str, buf
         buffer.push_str("\n");
     }
 
+    let p = comp_gen.p;
+    for long in negated_longs!(p) {
+        buffer.push_str(&*format!("{} -l {}\n", basic_template, long));
+    }
+
     for subcommand in &comp_gen.p.subcommands {
         let mut template = basic_template.clone();
         template.push_str(" -f");
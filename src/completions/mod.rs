@@ -4,7 +4,10 @@ mod bash;
 mod fish;
 mod zsh;
 mod powershell;
+mod elvish;
+mod nushell;
 mod shell;
+pub mod dynamic;
 
 // Std
 use std::io::Write;
@@ -15,8 +18,13 @@ use self::bash::BashGen;
 use self::fish::FishGen;
 use self::zsh::ZshGen;
 use self::powershell::PowerShellGen;
+use self::elvish::ElvishGen;
+use self::nushell::NushellGen;
 pub use self::shell::Shell;
 
+// The hidden subcommand generated scripts use to ask the program for runtime completions
+pub static COMPLETE_CMD: &'static str = "__complete";
+
 pub struct ComplGen<'a, 'b>
     where 'a: 'b
 {
@@ -32,6 +40,8 @@ impl<'a, 'b> ComplGen<'a, 'b> {
             Shell::Fish => FishGen::new(self.p).generate_to(buf),
             Shell::Zsh => ZshGen::new(self.p).generate_to(buf),
             Shell::PowerShell => PowerShellGen::new(self.p).generate_to(buf),
+            Shell::Elvish => ElvishGen::new(self.p).generate_to(buf),
+            Shell::Nushell => NushellGen::new(self.p).generate_to(buf),
         }
     }
 }
@@ -0,0 +1,140 @@
+// Std
+use std::io::Write;
+
+// Internal
+use app::parser::Parser;
+use args::{AnyArg, ArgSettings};
+
+pub struct NushellGen<'a, 'b>
+    where 'a: 'b
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> NushellGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { NushellGen { p: p } }
+
+    pub fn generate_to<W: Write>(&self, buf: &mut W) {
+        debugln!("NushellGen::generate_to;");
+        let bin_name = self.p.meta.bin_name.as_ref().unwrap();
+
+        let mut ret = vec![];
+        signatures_of(self.p, bin_name, &mut ret);
+
+        w!(buf, format!("module completions {{\n\n{}\n}}\n\nuse completions *\n", ret.join("\n"))
+            .as_bytes());
+    }
+}
+
+// Adds the signature of this command, then those of all the children, grandchildren, etc. Each
+// alias gets a signature of its own since Nushell matches externs by name.
+fn signatures_of(p: &Parser, bin_name: &str, ret: &mut Vec<String>) {
+    debugln!("NushellGen::signatures_of: bin_name={}", bin_name);
+    ret.push(signature_of(p, bin_name));
+    for sc in p.subcommands() {
+        signatures_of(&sc.p, &*format!("{} {}", bin_name, sc.p.meta.name), ret);
+        if let Some(ref aliases) = sc.p.meta.aliases {
+            for &(alias, _) in aliases {
+                signatures_of(&sc.p, &*format!("{} {}", bin_name, alias), ret);
+            }
+        }
+    }
+}
+
+// Writes the completion functions for any arguments with possible values, followed by the
+// `extern` itself. This looks roughly like:
+//
+//   def "nu-complete myapp color" [] {
+//     [ "always" "auto" "never" ]
+//   }
+//
+//   # Tests completions
+//   export extern "myapp" [
+//     --color: string@"nu-complete myapp color" # when to use color
+//     --help(-h)                                 # Prints help information
+//     file?: string                              # some input file
+//   ]
+fn signature_of(p: &Parser, bin_name: &str) -> String {
+    let mut completers = String::new();
+    let mut params = vec![];
+
+    for o in p.opts() {
+        let ty = format!(": string{}", completer_of(o, bin_name, &mut completers));
+        params.push((format!("{}{}", switch_of(o), ty), o.help()));
+    }
+    for f in p.flags() {
+        params.push((switch_of(f), f.help()));
+    }
+    for long in negated_longs!(p) {
+        params.push((format!("--{}", long), None));
+    }
+    for pos in p.positionals() {
+        let name = pos.name().replace(|c: char| !c.is_alphanumeric(), "_");
+        let ty = format!(": string{}", completer_of(pos, bin_name, &mut completers));
+        let param = if pos.is_set(ArgSettings::Multiple) {
+            format!("...{}{}", name, ty)
+        } else if pos.is_set(ArgSettings::Required) {
+            format!("{}{}", name, ty)
+        } else {
+            format!("{}?{}", name, ty)
+        };
+        params.push((param, pos.help()));
+    }
+
+    // Line the help comments up in a single column
+    let width = params.iter().map(|&(ref p, _)| p.len()).max().unwrap_or(0);
+    let params: Vec<_> = params.into_iter()
+        .map(|(param, help)| match help {
+            Some(help) => {
+                format!("    {:width$} # {}",
+                        param,
+                        help.replace("{n}", " ").replace('\n', " "),
+                        width = width)
+            }
+            None => format!("    {}", param),
+        })
+        .collect();
+
+    let about = match p.meta.about {
+        Some(about) => format!("  # {}\n", about.replace("{n}", " ").replace('\n', " ")),
+        None => String::new(),
+    };
+
+    format!("{completers}{about}  export extern {name} [\n{params}\n  ]\n",
+            completers = completers,
+            about = about,
+            name = quote(bin_name),
+            params = params.join("\n"))
+}
+
+// `--long(-s)`, or just whichever of the two the argument has
+fn switch_of<'n, 'e, A>(a: &A) -> String
+    where A: AnyArg<'n, 'e>
+{
+    match (a.long(), a.short()) {
+        (Some(l), Some(s)) => format!("--{}(-{})", l, s),
+        (Some(l), None) => format!("--{}", l),
+        (None, Some(s)) => format!("-{}", s),
+        (None, None) => unreachable!(),
+    }
+}
+
+// Adds a completion function to `completers` when the argument has possible values, returning the
+// `@"nu-complete ..."` suffix for the parameter's type
+fn completer_of<'n, 'e, A>(a: &A, bin_name: &str, completers: &mut String) -> String
+    where A: AnyArg<'n, 'e>
+{
+    match a.possible_vals() {
+        Some(pv) => {
+            let name = quote(&*format!("nu-complete {} {}", bin_name, a.name()));
+            let vals: Vec<_> = pv.iter().map(|v| quote(v)).collect();
+            completers.push_str(&*format!("  def {} [] {{\n    [ {} ]\n  }}\n\n",
+                                          name,
+                                          vals.join(" ")));
+            format!("@{}", name)
+        }
+        None => String::new(),
+    }
+}
+
+fn quote(s: &str) -> String { format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")) }
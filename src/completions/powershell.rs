@@ -107,6 +107,9 @@ fn generate_inner<'a, 'b, 'p>(p: &'p Parser<'a, 'b>, previous_command_name: &str
     for long in longs!(p) {
         completions.push_str(&format!("'--{}', ", long));
     }
+    for long in negated_longs!(p) {
+        completions.push_str(&format!("'--{}', ", long));
+    }
 
     let mut subcommands_cases = format!(r"
             '{}' {{
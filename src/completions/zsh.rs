@@ -38,16 +38,43 @@ _{name}() {{
 }}
 
 {subcommand_details}
-
+{dynamic}
 _{name} \"$@\"",
                    name = self.p.meta.bin_name.as_ref().unwrap(),
                    initial_args = get_args_of(self.p),
                    subcommands = get_subcommands_of(self.p),
-                   subcommand_details = subcommand_details(self.p)).as_bytes());
+                   subcommand_details = subcommand_details(self.p),
+                   dynamic = dynamic_of(self.p)).as_bytes());
 
     }
 }
 
+// Only when some argument has an `Arg::completer`, a function which calls back into the program
+// for the candidates. The words are taken from the buffer since `_arguments` rewrites $words when
+// it moves into a subcommand.
+fn dynamic_of(p: &Parser) -> String {
+    if !completions::dynamic::has_completers(p) {
+        return String::new();
+    }
+    format!("\
+(( $+functions[{func}] )) ||
+{func}() {{
+    local -a line_words candidates
+    line_words=(${{(z)LBUFFER}})
+    [[ $LBUFFER == *' ' ]] && line_words+=('')
+    candidates=(${{(f)\"$(${{(Q)line_words[1]}} {cmd} \"${{(@Q)line_words[2,-1]}}\" 2>/dev/null)\"}})
+    compadd -a candidates
+}}
+",
+            func = dynamic_fn(p),
+            cmd = completions::COMPLETE_CMD)
+}
+
+fn dynamic_fn(p: &Parser) -> String {
+    let root = p.meta.bin_name.as_ref().unwrap().split(' ').next().unwrap_or("").to_owned();
+    format!("_{}__complete", root)
+}
+
 // Displays the positional args and commands of a subcommand
 // (( $+functions[_[bin_name_underscore]_commands] )) ||
 // _[bin_name_underscore]_commands() {
@@ -84,11 +111,12 @@ _{bin_name_underscore}_commands() {{
     local commands; commands=(
         {subcommands_and_args}
     )
-    _describe -t commands '{bin_name} commands' commands \"$@\"
+    _describe -t commands '{bin_name} commands' commands \"$@\"{dynamic}
 }}",
             bin_name_underscore = p.meta.bin_name.as_ref().unwrap().replace(" ", "__"),
             bin_name = p.meta.bin_name.as_ref().unwrap(),
-            subcommands_and_args = subcommands_and_args_of(p))];
+            subcommands_and_args = subcommands_and_args_of(p),
+            dynamic = dynamic_pos_of(p))];
 
     // Next we start looping through all the children, grandchildren, etc.
     let mut all_subcommands = completions::all_subcommands(p);
@@ -102,16 +130,26 @@ _{bin_name_underscore}_commands() {{
     local commands; commands=(
         {subcommands_and_args}
     )
-    _describe -t commands '{bin_name} commands' commands \"$@\"
+    _describe -t commands '{bin_name} commands' commands \"$@\"{dynamic}
 }}",
             bin_name_underscore = bin_name.replace(" ", "__"),
             bin_name = bin_name,
-            subcommands_and_args = subcommands_and_args_of(parser_of(p, bin_name))));
+            subcommands_and_args = subcommands_and_args_of(parser_of(p, bin_name)),
+            dynamic = dynamic_pos_of(parser_of(p, bin_name))));
     }
 
     ret.join("\n")
 }
 
+// Positionals with an `Arg::completer` also get the program's candidates
+fn dynamic_pos_of(p: &Parser) -> String {
+    if p.positionals().any(|pos| pos.v.completer.is_some()) {
+        format!("\n    {}", dynamic_fn(p))
+    } else {
+        String::new()
+    }
+}
+
 // Generates subcommand and positional argument completions in form of
 //
 // 		'[arg_name]:[arg_help]'
@@ -310,7 +348,9 @@ fn write_opts_of(p: &Parser) -> String {
         } else {
             ""
         };
-        let pv = if let Some(pv_vec) = o.possible_vals() {
+        let pv = if o.completer().is_some() {
+            format!(": :{}", dynamic_fn(p))
+        } else if let Some(pv_vec) = o.possible_vals() {
             format!(": :({})", pv_vec.join(" "))
         } else {
             String::new()
@@ -382,6 +422,9 @@ fn write_flags_of(p: &Parser) -> String {
             ret.push(l);
         }
     }
+    for long in negated_longs!(p) {
+        ret.push(format!("\"--{}\" \\", long));
+    }
 
     ret.join("\n")
 }
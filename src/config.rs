@@ -0,0 +1,274 @@
+// Std
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+// Internal
+use errors::{Error, ErrorKind};
+use errors::Result as ClapResult;
+use fmt::ColorWhen;
+
+/// A source of configuration values for [`App::config_source`], such as a file on disk.
+///
+/// The contents use a small TOML/INI style syntax of `key = value` lines, where each key is the
+/// [`Arg::long`] (or, failing that, the [`Arg::with_name`]) of an argument, and each
+/// `[section]` is the name of a [`SubCommand`]. Nested subcommands are separated by dots, i.e.
+/// `[remote.add]`. Lines starting with `#` or `;` are comments.
+///
+/// Values may be bare words, `"double quoted"` strings with `\` escapes, `'single quoted'`
+/// literal strings, or `[arrays, "of", 'values']` for arguments which accept more than one
+/// value. Flags accept `true`, `false` or a number of occurrences.
+///
+/// ```toml
+/// # Used when no --color is given on the command line
+/// color = "never"
+/// verbose = 2
+///
+/// [remote.add]
+/// tags = ["nightly", "beta"]
+/// ```
+/// [`App::config_source`]: ./struct.App.html#method.config_source
+/// [`Arg::long`]: ./struct.Arg.html#method.long
+/// [`Arg::with_name`]: ./struct.Arg.html#method.with_name
+/// [`SubCommand`]: ./struct.SubCommand.html
+pub trait ConfigSource {
+    /// A name for the source to use in error messages, such as the path of the file
+    fn name(&self) -> String;
+
+    /// Reads the contents of the source, or returns `Ok(None)` if there is nothing to read, i.e.
+    /// the file doesn't exist
+    fn read(&self) -> io::Result<Option<String>>;
+}
+
+impl ConfigSource for PathBuf {
+    fn name(&self) -> String { self.display().to_string() }
+
+    fn read(&self) -> io::Result<Option<String>> {
+        let mut f = match File::open(self) {
+            Ok(f) => f,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let mut s = String::new();
+        try!(f.read_to_string(&mut s));
+        Ok(Some(s))
+    }
+}
+
+// A single `key = value` line, and where it came from
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub key: String,
+    pub vals: Vec<String>,
+    pub source: String,
+    pub line: usize,
+}
+
+impl Entry {
+    // Wraps an error about this entry's values so it points at the offending line
+    pub fn error(&self, e: Error) -> Error { e.in_config(&*self.source, self.line) }
+}
+
+// The merged contents of every source, keyed by section ("" being the top level)
+#[doc(hidden)]
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub sections: BTreeMap<String, Vec<Entry>>,
+}
+
+impl Config {
+    // Reads each source in turn, with keys from later sources replacing those of earlier ones
+    pub fn load(sources: &[Rc<ConfigSource>], color: ColorWhen) -> ClapResult<Config> {
+        let mut config = Config::default();
+        for src in sources {
+            debugln!("Config::load: source={}", src.name());
+            let contents = match src.read() {
+                Ok(Some(c)) => c,
+                Ok(None) => continue,
+                Err(e) => return Err(Error::config_unreadable(src.name(), e, color)),
+            };
+            try!(config.parse(&*src.name(), &*contents, color));
+        }
+        Ok(config)
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&Entry> {
+        self.sections.get(section).and_then(|es| es.iter().find(|e| e.key == key))
+    }
+
+    fn parse(&mut self, source: &str, contents: &str, color: ColorWhen) -> ClapResult<()> {
+        let mut section = String::new();
+        for (i, line) in contents.lines().enumerate() {
+            let line_no = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            let err = |desc: &str| {
+                Error::config_entry(desc, ErrorKind::InvalidValue, source, line_no, color)
+            };
+            if line.starts_with('[') {
+                let end = match line.find(']') {
+                    Some(end) => end,
+                    None => return Err(err("Expected a ']' to close the section name")),
+                };
+                if !is_comment(&line[end + 1..]) {
+                    return Err(err("Unexpected characters after the section name"));
+                }
+                section = line[1..end].trim().to_owned();
+                debugln!("Config::parse: section={}", section);
+                continue;
+            }
+            let eq = match line.find('=') {
+                Some(eq) => eq,
+                None => return Err(err("Expected a 'key = value' pair")),
+            };
+            let key = line[..eq].trim();
+            if key.is_empty() {
+                return Err(err("Expected a key before the '='"));
+            }
+            let vals = match parse_value(line[eq + 1..].trim()) {
+                Ok(vals) => vals,
+                Err(e) => return Err(err(&*e)),
+            };
+            debugln!("Config::parse: key={}, vals={:?}", key, vals);
+            let entry = Entry {
+                key: key.to_owned(),
+                vals: vals,
+                source: source.to_owned(),
+                line: line_no,
+            };
+            let entries = self.sections.entry(section.clone()).or_insert_with(Vec::new);
+            if let Some(pos) = entries.iter().position(|e| e.key == entry.key) {
+                entries[pos] = entry;
+            } else {
+                entries.push(entry);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_comment(s: &str) -> bool {
+    let s = s.trim();
+    s.is_empty() || s.starts_with('#') || s.starts_with(';')
+}
+
+// Parses everything after the `=`, either an array of values or a single one
+#[allow(deprecated)] // trim_start is newer than Rust 1.11
+fn parse_value(s: &str) -> Result<Vec<String>, String> {
+    if s.starts_with('[') {
+        let mut vals = vec![];
+        let mut rest = s[1..].trim_left();
+        loop {
+            if rest.starts_with(']') {
+                break;
+            }
+            let (val, r) = try!(parse_single(rest, true));
+            vals.push(val);
+            rest = r.trim_left();
+            if rest.starts_with(',') {
+                rest = rest[1..].trim_left();
+            } else if !rest.starts_with(']') {
+                return Err(String::from("Expected a ',' or ']' in the array"));
+            }
+        }
+        if !is_comment(&rest[1..]) {
+            return Err(String::from("Unexpected characters after the array"));
+        }
+        Ok(vals)
+    } else {
+        let (val, rest) = try!(parse_single(s, false));
+        if !is_comment(rest) {
+            return Err(String::from("Unexpected characters after the value"));
+        }
+        Ok(vec![val])
+    }
+}
+
+// Parses a single (possibly quoted) value from the start of `s`, returning it and whatever
+// follows it
+fn parse_single(s: &str, in_array: bool) -> Result<(String, &str), String> {
+    let mut chars = s.char_indices();
+    match chars.next() {
+        Some((_, '\'')) => {
+            match s[1..].find('\'') {
+                Some(end) => Ok((s[1..end + 1].to_owned(), &s[end + 2..])),
+                None => Err(String::from("Expected a closing '")),
+            }
+        }
+        Some((_, '"')) => {
+            let mut val = String::new();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '"' => return Ok((val, &s[i + 1..])),
+                    '\\' => {
+                        match chars.next() {
+                            Some((_, 'n')) => val.push('\n'),
+                            Some((_, 't')) => val.push('\t'),
+                            Some((_, '\\')) => val.push('\\'),
+                            Some((_, '"')) => val.push('"'),
+                            Some((_, c)) => {
+                                return Err(format!("Unknown escape sequence '\\{}'", c))
+                            }
+                            None => break,
+                        }
+                    }
+                    c => val.push(c),
+                }
+            }
+            Err(String::from("Expected a closing \""))
+        }
+        _ => {
+            // Bare values run until a comment, or the end of the array item
+            let end = s.char_indices()
+                .find(|&(i, c)| {
+                    (in_array && (c == ',' || c == ']')) ||
+                    ((c == '#' || c == ';') && s[..i].ends_with(char::is_whitespace))
+                })
+                .map_or(s.len(), |(i, _)| i);
+            Ok((s[..end].trim().to_owned(), &s[end..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Config, parse_value};
+    use fmt::ColorWhen;
+
+    #[test]
+    fn values() {
+        assert_eq!(parse_value("fast").unwrap(), &["fast"]);
+        assert_eq!(parse_value("two words # comment").unwrap(), &["two words"]);
+        assert_eq!(parse_value("a#b").unwrap(), &["a#b"]);
+        assert_eq!(parse_value("\"a \\\"b\\\"\\n\" ; comment").unwrap(), &["a \"b\"\n"]);
+        assert_eq!(parse_value("'C:\\dir'").unwrap(), &["C:\\dir"]);
+        assert_eq!(parse_value("").unwrap(), &[""]);
+        assert_eq!(parse_value("[a, \"b, c\", 'd' ] # comment").unwrap(), &["a", "b, c", "d"]);
+        assert!(parse_value("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn bad_values() {
+        assert!(parse_value("\"open").is_err());
+        assert!(parse_value("'open").is_err());
+        assert!(parse_value("[a, b").is_err());
+        assert!(parse_value("\"a\" b").is_err());
+        assert!(parse_value("\"\\q\"").is_err());
+    }
+
+    #[test]
+    fn sections_and_overrides() {
+        let mut c = Config::default();
+        c.parse("a", "x = 1\n[sub]\ny = 2\n", ColorWhen::Never).unwrap();
+        c.parse("b", "\n\nx = 3", ColorWhen::Never).unwrap();
+        let x = c.get("", "x").unwrap();
+        assert_eq!((&*x.vals, &*x.source, x.line), (&["3".to_owned()][..], "b", 3));
+        assert_eq!(c.get("sub", "y").unwrap().vals, &["2"]);
+        assert!(c.get("sub", "x").is_none());
+    }
+}
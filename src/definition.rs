@@ -0,0 +1,546 @@
+// Std
+use std::ffi::OsStr;
+#[cfg(any(feature = "yaml", feature = "json", feature = "toml"))]
+use std::fmt::Debug;
+
+// Third Party
+#[cfg(feature = "yaml")]
+use yaml_rust::Yaml;
+#[cfg(feature = "json")]
+use serde_json::Value as Json;
+#[cfg(feature = "toml")]
+use toml::Value as Toml;
+
+// Internal
+use app::parser::Parser;
+use app::AppSettings as AS;
+use args::{ArgGroup, Base, Switched, Valued};
+use args::settings::ArgSettings;
+use errors::json_string;
+
+// The settings a user may set, i.e. all but those clap uses to keep track of the parser's state,
+// by the name `from_yaml` parses
+const SETTINGS: &'static [(AS, &'static str)] = &[
+    (AS::AllowExternalSubcommands, "AllowExternalSubcommands"),
+    (AS::AllowInvalidUtf8, "AllowInvalidUtf8"),
+    (AS::AllowLeadingHyphen, "AllowLeadingHyphen"),
+    (AS::AllowMissingPositional, "AllowMissingPositional"),
+    (AS::AllowNegativeNumbers, "AllowNegativeNumbers"),
+    (AS::ArgRequiredElseHelp, "ArgRequiredElseHelp"),
+    (AS::ArgsNegateSubcommands, "ArgsNegateSubcommands"),
+    (AS::ColorAlways, "ColorAlways"),
+    (AS::ColorAuto, "ColorAuto"),
+    (AS::ColorNever, "ColorNever"),
+    (AS::ColoredHelp, "ColoredHelp"),
+    (AS::DeriveDisplayOrder, "DeriveDisplayOrder"),
+    (AS::DisableHelpSubcommand, "DisableHelpSubcommand"),
+    (AS::DisableVersion, "DisableVersion"),
+    (AS::DontCollapseArgsInUsage, "DontCollapseArgsInUsage"),
+    (AS::DontDelimitTrailingValues, "DontDelimitTrailingValues"),
+    (AS::GlobalVersion, "GlobalVersion"),
+    (AS::Hidden, "Hidden"),
+    (AS::HidePossibleValuesInHelp, "HidePossibleValuesInHelp"),
+    (AS::InferSubcommands, "InferSubcommands"),
+    (AS::JsonErrors, "JsonErrors"),
+    (AS::LowIndexMultiplePositional, "LowIndexMultiplePositional"),
+    (AS::NextLineHelp, "NextLineHelp"),
+    (AS::NoBinaryName, "NoBinaryName"),
+    (AS::PropagateGlobalValuesDown, "PropagateGlobalValuesDown"),
+    (AS::ResponseFiles, "ResponseFiles"),
+    (AS::StrictUtf8, "StrictUtf8"),
+    (AS::SubcommandRequired, "SubcommandRequired"),
+    (AS::SubcommandRequiredElseHelp, "SubcommandRequiredElseHelp"),
+    (AS::SubcommandsNegateReqs, "SubcommandsNegateReqs"),
+    (AS::TrailingVarArg, "TrailingVarArg"),
+    (AS::UnifiedHelpMessage, "UnifiedHelpMessage"),
+    (AS::VersionlessSubcommands, "VersionlessSubcommands"),
+    (AS::WaitOnError, "WaitOnError"),
+];
+
+// A format neutral tree of an `App` definition, using the same keys as `App::from_yaml`
+pub enum Value {
+    Bool(bool),
+    Int(u64),
+    Str(String),
+    Null,
+    List(Vec<Value>),
+    Map(Vec<(String, Value)>),
+}
+
+impl Value {
+    // Whether the value is written on a single line, like `[a, b]`
+    fn is_flow(&self) -> bool {
+        match *self {
+            Value::List(ref l) => l.iter().all(Value::is_flow),
+            Value::Map(ref m) => m.is_empty(),
+            _ => true,
+        }
+    }
+}
+
+// Collects the keys of a map, leaving out anything which is unset or has its default value
+struct Map(Vec<(String, Value)>);
+
+impl Map {
+    fn new() -> Self { Map(vec![]) }
+
+    fn put(&mut self, key: &str, v: Value) { self.0.push((key.to_owned(), v)); }
+
+    fn string<S: AsRef<str>>(&mut self, key: &str, v: Option<S>) {
+        if let Some(s) = v {
+            self.put(key, Value::Str(s.as_ref().to_owned()));
+        }
+    }
+
+    fn os_string(&mut self, key: &str, v: Option<&OsStr>) {
+        self.string(key, v.map(OsStr::to_string_lossy));
+    }
+
+    fn flag(&mut self, key: &str, v: bool) {
+        if v {
+            self.put(key, Value::Bool(true));
+        }
+    }
+
+    fn int(&mut self, key: &str, v: Option<u64>) {
+        if let Some(n) = v {
+            self.put(key, Value::Int(n));
+        }
+    }
+
+    fn strings<'s, I: IntoIterator<Item = &'s str>>(&mut self, key: &str, v: I) {
+        let l: Vec<_> = v.into_iter().map(|s| Value::Str(s.to_owned())).collect();
+        self.list(key, l);
+    }
+
+    fn list(&mut self, key: &str, l: Vec<Value>) {
+        if !l.is_empty() {
+            self.put(key, Value::List(l));
+        }
+    }
+
+    // A map with the single key `name`, the way `from_yaml` expects args, groups and subcommands
+    fn named(self, name: &str) -> Value { Value::Map(vec![(name.to_owned(), Value::Map(self.0))]) }
+}
+
+pub fn app(p: &Parser) -> Value {
+    let mut m = Map::new();
+    m.string("name", Some(&*p.meta.name));
+    m.0.extend(cmd(p, None).0);
+    Value::Map(m.0)
+}
+
+fn cmd(p: &Parser, parent: Option<&Parser>) -> Map {
+    debugln!("definition::cmd: name={}", p.meta.name);
+    let mut m = Map::new();
+    m.string("version", p.meta.version);
+    m.string("author", p.meta.author);
+    m.string("bin_name", p.meta.bin_name.as_ref());
+    m.string("about", p.meta.about);
+    m.string("before_help", p.meta.pre_help);
+    m.string("after_help", p.meta.more_help);
+    m.string("template", p.meta.template);
+    m.string("usage", p.meta.usage_str);
+    m.string("help", p.meta.help_str);
+    // Parsing fills in the default shorts when they're free
+    m.string("help_short",
+             p.help_short.and_then(|c| if c != 'h' { Some(c.to_string()) } else { None }));
+    m.string("version_short",
+             p.version_short.and_then(|c| if c != 'V' { Some(c.to_string()) } else { None }));
+    m.string("help_message", p.help_message);
+    m.string("version_message", p.version_message);
+    if let Some(ref aliases) = p.meta.aliases {
+        m.strings("aliases", aliases.iter().filter(|a| !a.1).map(|a| a.0));
+        m.strings("visible_aliases", aliases.iter().filter(|a| a.1).map(|a| a.0));
+    }
+    if p.meta.disp_ord != 999 {
+        m.int("display_order", Some(p.meta.disp_ord as u64));
+    }
+
+    // Settings a parent passes down with `global_setting` are its to write
+    let fresh = Parser::default();
+    let inherited = |s: AS| parent.map_or(false, |pp| pp.g_settings.is_set(s));
+    m.strings("settings",
+              SETTINGS.iter()
+                  .filter(|&&(s, _)| p.is_set(s) && !fresh.is_set(s))
+                  .filter(|&&(s, _)| !inherited(s) && !p.g_settings.is_set(s))
+                  .map(|&(_, name)| name));
+    m.strings("global_settings",
+              SETTINGS.iter()
+                  .filter(|&&(s, _)| p.g_settings.is_set(s) && !fresh.g_settings.is_set(s))
+                  .filter(|&&(s, _)| !inherited(s))
+                  .map(|&(_, name)| name));
+
+    // Global args a parent propagated down are written by the parent
+    let propagated = |name: &str| {
+        parent.map_or(false, |pp| pp.global_args.iter().any(|a| a.b.name == name))
+    };
+    let mut switches: Vec<_> = p.flags
+        .iter()
+        .filter(|f| f.b.name != "hclap_help" && f.b.name != "vclap_version")
+        .map(|f| (f.s.unified_ord, arg(p, &f.b, Some(&f.s), None, None)))
+        .chain(p.opts
+            .iter()
+            .map(|o| (o.s.unified_ord, arg(p, &o.b, Some(&o.s), Some(&o.v), None))))
+        .filter(|&(_, (name, _))| !propagated(name))
+        .collect();
+    switches.sort_by_key(|&(ord, _)| ord);
+    let args = switches.into_iter()
+        .map(|(_, a)| a)
+        .chain(p.positionals
+            .values()
+            .map(|pos| arg(p, &pos.b, None, Some(&pos.v), Some(pos.index)))
+            .filter(|&(name, _)| !propagated(name)))
+        .map(|(name, a)| a.named(name))
+        .collect();
+    m.list("args", args);
+
+    // The `help` subcommand clap adds itself
+    let scs = p.subcommands
+        .iter()
+        .filter(|sc| !(p.is_set(AS::NeedsSubcommandHelp) && sc.p.meta.name == "help"))
+        .map(|sc| cmd(&sc.p, Some(p)).named(&*sc.p.meta.name))
+        .collect();
+    m.list("subcommands", scs);
+    m.list("groups", p.groups.iter().filter_map(|g| group(p, g)).collect());
+    m
+}
+
+fn arg<'a, 'b>(p: &Parser,
+               b: &Base<'a, 'b>,
+               s: Option<&Switched>,
+               v: Option<&Valued>,
+               index: Option<u64>)
+               -> (&'a str, Map) {
+    debugln!("definition::arg: name={}", b.name);
+    let mut m = Map::new();
+    if let Some(s) = s {
+        m.string("short", s.short.map(|c| c.to_string()));
+        m.string("long", s.long);
+        if let Some(ref aliases) = s.aliases {
+            m.strings("aliases", aliases.iter().filter(|a| !a.1).map(|a| a.0));
+            m.strings("visible_aliases", aliases.iter().filter(|a| a.1).map(|a| a.0));
+        }
+        if s.disp_ord != 999 {
+            m.int("display_order", Some(s.disp_ord as u64));
+        }
+    }
+    m.int("index", index);
+    m.string("help", b.help);
+    m.string("long_help", b.long_help);
+    m.flag("required", b.is_set(ArgSettings::Required));
+    if let Some(ref names) = b.r_unless {
+        if b.is_set(ArgSettings::RequiredUnlessAll) {
+            m.strings("required_unless_all", names.iter().cloned());
+        } else {
+            m.strings("required_unless_one", names.iter().cloned());
+        }
+    }
+    m.list("required_ifs",
+           p.r_ifs
+               .iter()
+               .filter(|&&(_, _, name)| name == b.name)
+               .map(|&(arg, val, _)| strs(&[arg, val]))
+               .collect());
+    m.flag("takes_value", b.is_set(ArgSettings::TakesValue));
+    m.flag("multiple", b.is_set(ArgSettings::Multiple));
+    m.flag("global", b.is_set(ArgSettings::Global));
+    m.flag("hidden", b.is_set(ArgSettings::Hidden));
+    m.flag("negatable", b.is_set(ArgSettings::Negatable));
+    m.flag("next_line_help", b.is_set(ArgSettings::NextLineHelp));
+    m.flag("allow_hyphen_values", b.is_set(ArgSettings::AllowLeadingHyphen));
+    m.flag("secret", b.is_set(ArgSettings::Secret));
+    if !b.is_set(ArgSettings::EmptyValues) {
+        m.put("empty_values", Value::Bool(false));
+    }
+    if let Some(ref groups) = b.groups {
+        m.strings("groups", groups.iter().cloned());
+    }
+    if let Some(ref reqs) = b.requires {
+        m.strings("requires", reqs.iter().filter(|r| r.0.is_none()).map(|r| r.1));
+        m.list("requires_ifs",
+               reqs.iter()
+                   .filter_map(|&(val, arg)| val.map(|val| strs(&[val, arg])))
+                   .collect());
+    }
+    if let Some(ref bl) = b.blacklist {
+        m.strings("conflicts_with", bl.iter().cloned());
+    }
+    if let Some(ref ovr) = b.overrides {
+        m.strings("overrides_with", ovr.iter().cloned());
+    }
+    if let Some(v) = v {
+        valued(b, v, &mut m);
+    }
+    (b.name, m)
+}
+
+fn valued(b: &Base, v: &Valued, m: &mut Map) {
+    m.int("number_of_values", v.num_vals);
+    m.int("min_values", v.min_vals);
+    m.int("max_values", v.max_vals);
+    if let Some(ref names) = v.val_names {
+        m.strings("value_names", names.values().cloned());
+    }
+    if b.is_set(ArgSettings::UseValueDelimiter) {
+        match v.val_delim {
+            Some(',') | None => m.flag("use_delimiter", true),
+            Some(c) => m.string("value_delimiter", Some(c.to_string())),
+        }
+    } else if !b.is_set(ArgSettings::ValueDelimiterNotSet) {
+        m.put("use_delimiter", Value::Bool(false));
+    }
+    m.flag("require_delimiter", b.is_set(ArgSettings::RequireDelimiter));
+    m.os_string("default_value", v.default_val);
+    if let Some(ref ifs) = v.default_vals_ifs {
+        let l = ifs.values()
+            .map(|&(arg, val, default)| {
+                Value::List(vec![Value::Str(arg.to_owned()),
+                                 val.map_or(Value::Null,
+                                            |v| Value::Str(v.to_string_lossy().into_owned())),
+                                 Value::Str(default.to_string_lossy().into_owned())])
+            })
+            .collect();
+        m.list("default_value_ifs", l);
+    }
+    m.os_string("env", v.env);
+    if let Some(ref pv) = v.possible_vals {
+        m.strings("possible_values", pv.iter().cloned());
+    }
+    m.flag("key_value", b.is_set(ArgSettings::KeyValue));
+    m.string("key_value_delimiter", v.kv_delim.map(|c| c.to_string()));
+    if let Some(ref pk) = v.possible_keys {
+        m.strings("possible_keys", pk.iter().cloned());
+    }
+}
+
+fn group(p: &Parser, g: &ArgGroup) -> Option<Value> {
+    // Args which name the group themselves already add themselves to it
+    let joined = |name: &str| {
+        p.flags
+            .iter()
+            .map(|f| &f.b)
+            .chain(p.opts.iter().map(|o| &o.b))
+            .chain(p.positionals.values().map(|pos| &pos.b))
+            .find(|b| b.name == name)
+            .and_then(|b| b.groups.as_ref())
+            .map_or(false, |gs| gs.contains(&g.name))
+    };
+    let mut m = Map::new();
+    m.strings("args", g.args.iter().cloned().filter(|a| !joined(a)));
+    m.flag("required", g.required);
+    m.flag("multiple", g.multiple);
+    if let Some(ref reqs) = g.requires {
+        m.strings("requires", reqs.iter().cloned());
+    }
+    if let Some(ref cfs) = g.conflicts {
+        m.strings("conflicts_with", cfs.iter().cloned());
+    }
+    if m.0.is_empty() {
+        None
+    } else {
+        Some(m.named(g.name))
+    }
+}
+
+fn strs(v: &[&str]) -> Value {
+    Value::List(v.iter().map(|s| Value::Str((*s).to_owned())).collect())
+}
+
+pub fn to_yaml(v: &Value) -> String {
+    let mut out = String::new();
+    match *v {
+        Value::Map(ref m) => yaml_map(&mut out, m, 0, false),
+        _ => unreachable!("definitions always have a map at the top"),
+    }
+    out
+}
+
+// Writes each `key: value` on its own line, except the first one when it follows a `- `
+fn yaml_map(out: &mut String, m: &[(String, Value)], indent: usize, after_dash: bool) {
+    for (i, &(ref k, ref v)) in m.iter().enumerate() {
+        if i > 0 || !after_dash {
+            push_indent(out, indent);
+        }
+        out.push_str(&*yaml_key(k));
+        out.push(':');
+        match *v {
+            ref v if v.is_flow() => {
+                out.push(' ');
+                flow(out, v);
+                out.push('\n');
+            }
+            Value::Map(ref m) => {
+                out.push('\n');
+                yaml_map(out, m, indent + 2, false);
+            }
+            Value::List(ref l) => {
+                out.push('\n');
+                for item in l {
+                    push_indent(out, indent + 2);
+                    out.push_str("- ");
+                    match *item {
+                        Value::Map(ref m) if !item.is_flow() => yaml_map(out, m, indent + 4, true),
+                        _ => {
+                            flow(out, item);
+                            out.push('\n');
+                        }
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+// Keys which YAML would read as something other than a string are quoted
+fn yaml_key(k: &str) -> String {
+    let plain = k.chars().next().map_or(false, |c| c.is_alphabetic() || c == '_') &&
+                k.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') &&
+                !["true", "false", "null", "yes", "no", "on", "off"]
+                    .contains(&&*k.to_lowercase());
+    if plain {
+        k.to_owned()
+    } else {
+        json_string(k)
+    }
+}
+
+pub fn to_json(v: &Value) -> String {
+    let mut out = String::new();
+    json(&mut out, v, 0);
+    out.push('\n');
+    out
+}
+
+fn json(out: &mut String, v: &Value, indent: usize) {
+    match *v {
+        ref v if v.is_flow() => flow(out, v),
+        Value::Map(ref m) => {
+            out.push_str("{\n");
+            for (i, &(ref k, ref v)) in m.iter().enumerate() {
+                push_indent(out, indent + 2);
+                out.push_str(&*json_string(k));
+                out.push_str(": ");
+                json(out, v, indent + 2);
+                out.push_str(if i + 1 < m.len() { ",\n" } else { "\n" });
+            }
+            push_indent(out, indent);
+            out.push('}');
+        }
+        Value::List(ref l) => {
+            out.push_str("[\n");
+            for (i, v) in l.iter().enumerate() {
+                push_indent(out, indent + 2);
+                json(out, v, indent + 2);
+                out.push_str(if i + 1 < l.len() { ",\n" } else { "\n" });
+            }
+            push_indent(out, indent);
+            out.push(']');
+        }
+        _ => unreachable!(),
+    }
+}
+
+// Writes a value on a single line, which is valid as both YAML and JSON
+fn flow(out: &mut String, v: &Value) {
+    match *v {
+        Value::Bool(b) => out.push_str(if b { "true" } else { "false" }),
+        Value::Int(n) => out.push_str(&*n.to_string()),
+        Value::Str(ref s) => out.push_str(&*json_string(s)),
+        Value::Null => out.push_str("null"),
+        Value::List(ref l) => {
+            out.push('[');
+            for (i, v) in l.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                flow(out, v);
+            }
+            out.push(']');
+        }
+        Value::Map(_) => out.push_str("{}"),
+    }
+}
+
+fn push_indent(out: &mut String, n: usize) {
+    for _ in 0..n {
+        out.push(' ');
+    }
+}
+
+// A parsed YAML, JSON or TOML document an `App` is loaded from. `App`, `Arg` and `ArgGroup` are
+// built from these through the same code no matter the format, so every format understands
+// exactly the same keys. The methods are named like yaml-rust's so the `yaml_*` macros work on
+// any of them.
+#[cfg(any(feature = "yaml", feature = "json", feature = "toml"))]
+pub trait Node: Debug + Sized {
+    fn as_str(&self) -> Option<&str>;
+    fn as_bool(&self) -> Option<bool>;
+    fn as_i64(&self) -> Option<i64>;
+    fn as_vec(&self) -> Option<&[Self]>;
+    fn is_null(&self) -> bool;
+    // The keys and values of a map, in the order they were written (or sorted, for formats whose
+    // maps don't keep it)
+    fn entries(&self) -> Option<Vec<(&str, &Self)>>;
+
+    fn get(&self, key: &str) -> Option<&Self> {
+        self.entries().and_then(|e| e.into_iter().find(|&(k, _)| k == key)).map(|(_, v)| v)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl Node for Yaml {
+    fn as_str(&self) -> Option<&str> { Yaml::as_str(self) }
+    fn as_bool(&self) -> Option<bool> { Yaml::as_bool(self) }
+    fn as_i64(&self) -> Option<i64> { Yaml::as_i64(self) }
+    fn as_vec(&self) -> Option<&[Yaml]> { Yaml::as_vec(self).map(|v| &v[..]) }
+    fn is_null(&self) -> bool { Yaml::is_null(self) }
+    fn entries(&self) -> Option<Vec<(&str, &Yaml)>> {
+        self.as_hash().map(|h| {
+            h.iter()
+                .map(|(k, v)| {
+                    (k.as_str().unwrap_or_else(|| panic!("YAML key {:?} isn't a string", k)), v)
+                })
+                .collect()
+        })
+    }
+}
+
+#[cfg(feature = "json")]
+impl Node for Json {
+    fn as_str(&self) -> Option<&str> { Json::as_str(self) }
+    fn as_bool(&self) -> Option<bool> { Json::as_bool(self) }
+    fn as_i64(&self) -> Option<i64> { Json::as_i64(self) }
+    fn as_vec(&self) -> Option<&[Json]> { self.as_array().map(|v| &v[..]) }
+    fn is_null(&self) -> bool { Json::is_null(self) }
+    fn entries(&self) -> Option<Vec<(&str, &Json)>> {
+        self.as_object().map(|o| o.iter().map(|(k, v)| (&**k, v)).collect())
+    }
+}
+
+// TOML has no null, so `default_value_ifs` entries without a value are written as `[arg, default]`
+#[cfg(feature = "toml")]
+impl Node for Toml {
+    fn as_str(&self) -> Option<&str> { Toml::as_str(self) }
+    fn as_bool(&self) -> Option<bool> { Toml::as_bool(self) }
+    fn as_i64(&self) -> Option<i64> { self.as_integer() }
+    fn as_vec(&self) -> Option<&[Toml]> { self.as_array().map(|v| &v[..]) }
+    fn is_null(&self) -> bool { false }
+    fn entries(&self) -> Option<Vec<(&str, &Toml)>> {
+        self.as_table().map(|t| t.iter().map(|(k, v)| (&**k, v)).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SETTINGS;
+    use app::AppSettings;
+
+    #[test]
+    fn setting_names_parse() {
+        for &(s, name) in SETTINGS {
+            assert_eq!(name.parse::<AppSettings>(), Ok(s));
+        }
+    }
+}
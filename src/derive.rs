@@ -0,0 +1,277 @@
+// Std
+use std::env;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// Internal
+use app::App;
+use args::ArgMatches;
+use errors::{Error, Result as ClapResult};
+
+/// Builds a value out of the [`ArgMatches`] of an [`App`], such as a struct with a field for each
+/// argument. Together with [`ClapApp`] this is what `#[derive(ClapApp)]` from the `clap_derive`
+/// crate implements.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ArgMatches, FromArgMatches};
+/// struct Opts {
+///     verbose: bool,
+///     name: Option<String>,
+/// }
+///
+/// impl FromArgMatches for Opts {
+///     fn from_matches(m: &ArgMatches) -> clap::Result<Self> {
+///         Ok(Opts {
+///             verbose: m.is_present("verbose"),
+///             name: m.value_of("name").map(String::from),
+///         })
+///     }
+/// }
+///
+/// let m = App::new("prog")
+///     .arg(Arg::with_name("verbose").short("v"))
+///     .arg(Arg::with_name("name"))
+///     .get_matches_from(vec!["prog", "-v", "bob"]);
+/// let opts = Opts::from_matches(&m).unwrap();
+/// assert!(opts.verbose);
+/// assert_eq!(opts.name, Some("bob".to_owned()));
+/// ```
+///
+/// An enum with a variant per subcommand can implement [`FromSubcommand`] instead, and its
+/// `from_matches` then only has to call [`from_subcommand_matches`]. The values of single
+/// arguments, such as the fields above, are read with [`FromArg`].
+///
+/// [`ArgMatches`]: ./struct.ArgMatches.html
+/// [`App`]: ./struct.App.html
+/// [`ClapApp`]: ./trait.ClapApp.html
+/// [`FromSubcommand`]: ./trait.FromSubcommand.html
+/// [`from_subcommand_matches`]: ./fn.from_subcommand_matches.html
+/// [`FromArg`]: ./trait.FromArg.html
+pub trait FromArgMatches: Sized {
+    /// Builds the value from `matches`, failing with an [`Error`] if they are missing something it
+    /// needs or a value is of the wrong type.
+    ///
+    /// [`Error`]: ./struct.Error.html
+    fn from_matches(matches: &ArgMatches) -> ClapResult<Self>;
+}
+
+/// A type which declares its own command line interface: [`ClapApp::clap`] builds the [`App`],
+/// and the [`FromArgMatches`] impl reads the result back into the type. Usually this is derived
+/// with `#[derive(ClapApp)]` from the `clap_derive` crate, in which case a struct's fields become
+/// arguments and an enum's variants become subcommands.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ArgMatches, ClapApp, FromArgMatches};
+/// struct Opts {
+///     jobs: Option<usize>,
+/// }
+///
+/// impl ClapApp for Opts {
+///     fn clap<'a, 'b>() -> App<'a, 'b> { Opts::augment_clap(App::new("prog")) }
+///
+///     fn augment_clap<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+///         app.arg(Arg::with_name("jobs").short("j").value_parser(str::parse::<usize>))
+///     }
+/// }
+///
+/// impl FromArgMatches for Opts {
+///     fn from_matches(m: &ArgMatches) -> clap::Result<Self> {
+///         Ok(Opts { jobs: m.get::<usize>("jobs").cloned() })
+///     }
+/// }
+///
+/// let opts = Opts::parse_from(vec!["prog", "-j", "4"]);
+/// assert_eq!(opts.jobs, Some(4));
+/// assert!(Opts::try_parse_from(vec!["prog", "-j", "four"]).is_err());
+/// ```
+/// [`ClapApp::clap`]: ./trait.ClapApp.html#tymethod.clap
+/// [`App`]: ./struct.App.html
+/// [`FromArgMatches`]: ./trait.FromArgMatches.html
+pub trait ClapApp: FromArgMatches {
+    /// Builds the [`App`] parsing this type.
+    ///
+    /// [`App`]: ./struct.App.html
+    fn clap<'a, 'b>() -> App<'a, 'b>;
+
+    /// Adds this type's arguments (or for an enum, its subcommands) to `app`. This is how a type
+    /// is used as a subcommand of another one.
+    fn augment_clap<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b>;
+
+    /// Parses [`env::args_os`], exiting on failure like [`App::get_matches`].
+    ///
+    /// [`env::args_os`]: https://doc.rust-lang.org/std/env/fn.args_os.html
+    /// [`App::get_matches`]: ./struct.App.html#method.get_matches
+    fn parse() -> Self { Self::parse_from(env::args_os()) }
+
+    /// Parses `itr`, exiting on failure like [`App::get_matches_from`].
+    ///
+    /// [`App::get_matches_from`]: ./struct.App.html#method.get_matches_from
+    fn parse_from<I, T>(itr: I) -> Self
+        where I: IntoIterator<Item = T>,
+              T: Into<OsString> + Clone
+    {
+        Self::from_matches(&Self::clap().get_matches_from(itr)).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Parses `itr`, returning an [`Error`] on failure like [`App::get_matches_from_safe`].
+    ///
+    /// [`Error`]: ./struct.Error.html
+    /// [`App::get_matches_from_safe`]: ./struct.App.html#method.get_matches_from_safe
+    fn try_parse_from<I, T>(itr: I) -> ClapResult<Self>
+        where I: IntoIterator<Item = T>,
+              T: Into<OsString> + Clone
+    {
+        Self::from_matches(&try!(Self::clap().get_matches_from_safe(itr)))
+    }
+}
+
+/// Reads the value of a single argument out of an [`ArgMatches`]. It's the building block of
+/// [`FromArgMatches`] impls.
+///
+/// `bool`, `u64`, `Option<T>` and `Vec<T>` can't implement [`FromArgMatches`] themselves, since
+/// `from_matches` is only handed the matches: a `bool` has no way of knowing which argument's
+/// presence it stands for. This trait is the same conversion with the name of the argument added,
+/// which is what a struct field has to go on.
+///
+/// * `bool` is whether the argument was present
+/// * `u64` is the number of times the argument was used
+/// * `Option<T>` is the argument's value parsed with `T`'s `FromStr` impl
+/// * `Vec<T>` is all of the argument's values, each parsed with `T`'s `FromStr` impl
+///
+/// A value which doesn't parse is an [`ErrorKind::InvalidValue`] error with the usage of the
+/// matches, just like when the parser rejects a value.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ErrorKind, FromArg};
+/// let m = App::new("prog")
+///     .arg(Arg::with_name("verbose").short("v").multiple(true))
+///     .arg(Arg::with_name("jobs").short("j").takes_value(true))
+///     .get_matches_from(vec!["prog", "-vv", "-j", "four"]);
+///
+/// assert_eq!(u64::from_arg(&m, "verbose").unwrap(), 2);
+/// assert_eq!(Option::<String>::from_arg(&m, "jobs").unwrap(), Some("four".to_owned()));
+/// assert_eq!(Option::<u8>::from_arg(&m, "jobs").unwrap_err().kind, ErrorKind::InvalidValue);
+/// ```
+/// [`ArgMatches`]: ./struct.ArgMatches.html
+/// [`FromArgMatches`]: ./trait.FromArgMatches.html
+/// [`ErrorKind::InvalidValue`]: ./enum.ErrorKind.html#variant.InvalidValue
+pub trait FromArg: Sized {
+    /// Reads the argument `name` from `matches`.
+    fn from_arg(matches: &ArgMatches, name: &str) -> ClapResult<Self>;
+}
+
+impl FromArg for bool {
+    fn from_arg(matches: &ArgMatches, name: &str) -> ClapResult<Self> {
+        Ok(matches.is_present(name))
+    }
+}
+
+impl FromArg for u64 {
+    fn from_arg(matches: &ArgMatches, name: &str) -> ClapResult<Self> {
+        Ok(matches.occurrences_of(name))
+    }
+}
+
+impl<T> FromArg for Option<T>
+    where T: FromStr,
+          T::Err: Display
+{
+    fn from_arg(matches: &ArgMatches, name: &str) -> ClapResult<Self> {
+        match matches.value_of(name) {
+            Some(v) => parse(matches, name, v).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T> FromArg for Vec<T>
+    where T: FromStr,
+          T::Err: Display
+{
+    fn from_arg(matches: &ArgMatches, name: &str) -> ClapResult<Self> {
+        match matches.values_of(name) {
+            Some(vals) => vals.map(|v| parse(matches, name, v)).collect(),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+fn parse<T>(matches: &ArgMatches, name: &str, val: &str) -> ClapResult<T>
+    where T: FromStr,
+          T::Err: Display
+{
+    val.parse().map_err(|e| Error::unconvertible_value(val, e, name, matches.usage()))
+}
+
+/// Builds an enum with a variant per subcommand from the matches of whichever subcommand was
+/// used. [`from_subcommand_matches`] does the lookup of that subcommand, so the enum's
+/// [`FromArgMatches`] impl can be a single call to it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, ArgMatches, ErrorKind, FromArg, FromArgMatches, FromSubcommand, SubCommand};
+/// #[derive(Debug, PartialEq)]
+/// enum Command {
+///     Add { force: bool },
+///     Remove,
+/// }
+///
+/// impl FromSubcommand for Command {
+///     fn from_subcommand(name: &str, m: &ArgMatches) -> clap::Result<Option<Self>> {
+///         Ok(match name {
+///             "add" => Some(Command::Add { force: try!(bool::from_arg(m, "force")) }),
+///             "remove" => Some(Command::Remove),
+///             _ => None,
+///         })
+///     }
+/// }
+///
+/// impl FromArgMatches for Command {
+///     fn from_matches(m: &ArgMatches) -> clap::Result<Self> { clap::from_subcommand_matches(m) }
+/// }
+///
+/// let app = App::new("prog")
+///     .subcommand(SubCommand::with_name("add").arg_from_usage("-f, --force"))
+///     .subcommand(SubCommand::with_name("remove"));
+///
+/// let m = app.clone().get_matches_from(vec!["prog", "add", "-f"]);
+/// assert_eq!(Command::from_matches(&m).unwrap(), Command::Add { force: true });
+///
+/// let m = app.get_matches_from(vec!["prog"]);
+/// assert_eq!(Command::from_matches(&m).unwrap_err().kind, ErrorKind::MissingSubcommand);
+/// ```
+/// [`from_subcommand_matches`]: ./fn.from_subcommand_matches.html
+/// [`FromArgMatches`]: ./trait.FromArgMatches.html
+pub trait FromSubcommand: Sized {
+    /// Builds the variant for the subcommand `name` from its `matches`, or returns `None` if
+    /// there's no variant for it.
+    fn from_subcommand(name: &str, matches: &ArgMatches) -> ClapResult<Option<Self>>;
+}
+
+/// Builds a [`FromSubcommand`] type out of the subcommand in `matches`, found with
+/// [`ArgMatches::subcommand`]. Fails with [`ErrorKind::MissingSubcommand`] if there was none, or
+/// [`ErrorKind::UnrecognizedSubcommand`] if `T::from_subcommand` doesn't know it.
+///
+/// [`FromSubcommand`]: ./trait.FromSubcommand.html
+/// [`ArgMatches::subcommand`]: ./struct.ArgMatches.html#method.subcommand
+/// [`ErrorKind::MissingSubcommand`]: ./enum.ErrorKind.html#variant.MissingSubcommand
+/// [`ErrorKind::UnrecognizedSubcommand`]: ./enum.ErrorKind.html#variant.UnrecognizedSubcommand
+pub fn from_subcommand_matches<T: FromSubcommand>(matches: &ArgMatches) -> ClapResult<T> {
+    match matches.subcommand() {
+        (name, Some(sub)) => {
+            match try!(T::from_subcommand(name, sub)) {
+                Some(t) => Ok(t),
+                None => Err(Error::unconvertible_subcommand(name, matches.usage())),
+            }
+        }
+        _ => Err(Error::subcommand_not_provided(matches.usage())),
+    }
+}
@@ -0,0 +1,288 @@
+// Std
+use std::collections::BTreeMap;
+use std::io::Write;
+
+// Internal
+use app::help;
+use app::parser::Parser;
+use app::AppSettings as AS;
+use app::usage;
+use args::{AnyArg, DispOrder};
+use args::settings::ArgSettings;
+use errors::Result as ClapResult;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+// Everything the reference documents show about a single argument
+struct ArgDoc {
+    short: Option<String>,
+    long: Option<String>,
+    vals: Option<String>,
+    default: Option<String>,
+    possible: Vec<String>,
+    env: Option<String>,
+    required: bool,
+    help: String,
+}
+
+// Everything the reference documents show about a single (sub)command
+struct CmdDoc {
+    name: String,
+    anchor: String,
+    depth: usize,
+    about: Option<String>,
+    usage: String,
+    args: Vec<ArgDoc>,
+    subcommands: Vec<(String, String, Option<String>)>, // (name, anchor, about)
+}
+
+pub struct DocGen<'a, 'b>
+    where 'a: 'b
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> DocGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { DocGen { p: p } }
+
+    pub fn generate_to<W: Write>(&self, format: DocFormat, buf: &mut W) -> ClapResult<()> {
+        debugln!("DocGen::generate_to: format={:?}", format);
+        let mut cmds = vec![];
+        collect(self.p, 0, &mut cmds);
+        for cmd in &cmds {
+            match format {
+                DocFormat::Markdown => try!(write_markdown(cmd, buf)),
+                DocFormat::Html => try!(write_html(cmd, buf)),
+            }
+        }
+        Ok(())
+    }
+}
+
+// Walks the command tree depth first, so each command is followed by all of its subcommands
+fn collect(p: &Parser, depth: usize, cmds: &mut Vec<CmdDoc>) {
+    debugln!("DocGen::collect: name={}", p.meta.name);
+    let scs = help::subcommands(p);
+    cmds.push(CmdDoc {
+                  name: bin_name(p),
+                  anchor: anchor(p),
+                  depth: depth,
+                  about: p.meta.long_about.or(p.meta.about).map(|s| s.replace("{n}", "\n")),
+                  usage: usage::create_usage_no_title(p, &[]),
+                  args: args(p),
+                  subcommands: scs.iter()
+                      .map(|s| (bin_name(&s.p), anchor(&s.p), s.p.meta.about.map(String::from)))
+                      .collect(),
+              });
+    for sc in scs {
+        collect(&sc.p, depth + 1, cmds);
+    }
+}
+
+fn bin_name(p: &Parser) -> String { p.meta.bin_name.as_ref().unwrap_or(&p.meta.name).clone() }
+
+fn anchor(p: &Parser) -> String { bin_name(p).replace(' ', "-") }
+
+// Gathers the arguments in the same order `--help` displays them: flags, options, then
+// positionals
+fn args(p: &Parser) -> Vec<ArgDoc> {
+    let unified = p.is_set(AS::UnifiedHelpMessage);
+    let mut flags = BTreeMap::new();
+    let mut opts = BTreeMap::new();
+    for f in p.flags().filter(|f| !f.is_set(ArgSettings::Hidden)) {
+        let btm = flags.entry(f.disp_ord()).or_insert_with(BTreeMap::new);
+        btm.insert(f.name(), arg(p, f));
+    }
+    for o in p.opts().filter(|o| !o.is_set(ArgSettings::Hidden)) {
+        let btm = if unified {
+            flags.entry(o.disp_ord()).or_insert_with(BTreeMap::new)
+        } else {
+            opts.entry(o.disp_ord()).or_insert_with(BTreeMap::new)
+        };
+        btm.insert(o.name(), arg(p, o));
+    }
+    let mut ret = vec![];
+    for btm in flags.into_iter().chain(opts.into_iter()).map(|(_, btm)| btm) {
+        ret.extend(btm.into_iter().map(|(_, a)| a));
+    }
+    for pos in p.positionals().filter(|p| !p.is_set(ArgSettings::Hidden)) {
+        ret.push(arg(p, pos));
+    }
+    ret
+}
+
+fn arg<'n, 'e, A>(p: &Parser, a: &A) -> ArgDoc
+    where A: AnyArg<'n, 'e>
+{
+    debugln!("DocGen::arg: name={}", a.name());
+    let vals = help::val_names(a).map(|(names, multiple)| {
+        format!("{}{}", names.join(" "), if multiple { "..." } else { "" })
+    });
+    let hide_pv = a.is_set(ArgSettings::HidePossibleValues) ||
+                  p.is_set(AS::HidePossibleValuesInHelp);
+    ArgDoc {
+        short: a.short().map(|s| format!("-{}", s)),
+        long: a.long().map(|l| format!("--{}", l)),
+        vals: vals,
+        default: if a.is_set(ArgSettings::HideDefaultValue) {
+            None
+        } else {
+            a.default_val().map(|d| d.to_string_lossy().into_owned())
+        },
+        possible: if hide_pv {
+            vec![]
+        } else {
+            a.possible_vals().map_or(vec![], |pv| pv.iter().map(|v| v.to_string()).collect())
+        },
+        env: a.env().map(|e| e.to_string_lossy().into_owned()),
+        required: a.is_set(ArgSettings::Required),
+        help: a.long_help()
+            .or_else(|| a.help())
+            .unwrap_or("")
+            .replace("{n}", "\n"),
+    }
+}
+
+fn write_markdown<W: Write>(cmd: &CmdDoc, w: &mut W) -> ClapResult<()> {
+    try!(write!(w,
+                "<a name=\"{}\"></a>\n{} {}\n\n",
+                cmd.anchor,
+                &"######"[..::std::cmp::min(cmd.depth + 1, 6)],
+                cmd.name));
+    if let Some(ref about) = cmd.about {
+        try!(write!(w, "{}\n\n", md_text(about)));
+    }
+    try!(write!(w, "```text\n{}\n```\n\n", cmd.usage));
+
+    if !cmd.args.is_empty() {
+        try!(write!(w,
+                    "| Short | Long | Values | Default | Possible values | Env | Required | \
+                     Description |\n"));
+        try!(write!(w,
+                    "|-------|------|--------|---------|-----------------|-----|----------|\
+                     -------------|\n"));
+        for a in &cmd.args {
+            let possible: Vec<_> = a.possible.iter().map(|v| md_code(v)).collect();
+            try!(write!(w,
+                        "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                        a.short.as_ref().map_or(String::new(), |s| md_code(s)),
+                        a.long.as_ref().map_or(String::new(), |l| md_code(l)),
+                        a.vals.as_ref().map_or(String::new(), |v| md_code(v)),
+                        a.default.as_ref().map_or(String::new(), |d| md_code(d)),
+                        possible.join(", "),
+                        a.env.as_ref().map_or(String::new(), |e| md_code(e)),
+                        if a.required { "yes" } else { "" },
+                        md_text(&*a.help).replace('\n', "<br>")));
+        }
+        try!(write!(w, "\n"));
+    }
+
+    if !cmd.subcommands.is_empty() {
+        try!(write!(w, "**Subcommands:**\n\n"));
+        for &(ref name, ref anchor, ref about) in &cmd.subcommands {
+            try!(write!(w, "* [`{}`](#{})", name, anchor));
+            if let Some(ref about) = *about {
+                try!(write!(w, ": {}", md_text(about).replace('\n', " ")));
+            }
+            try!(write!(w, "\n"));
+        }
+        try!(write!(w, "\n"));
+    }
+    Ok(())
+}
+
+fn write_html<W: Write>(cmd: &CmdDoc, w: &mut W) -> ClapResult<()> {
+    let h = ::std::cmp::min(cmd.depth + 1, 6);
+    try!(write!(w,
+                "<section id=\"{}\">\n<h{}>{}</h{}>\n",
+                html_escape(&*cmd.anchor),
+                h,
+                html_escape(&*cmd.name),
+                h));
+    if let Some(ref about) = cmd.about {
+        for para in about.split("\n\n") {
+            try!(write!(w, "<p>{}</p>\n", html_escape(para).replace('\n', "<br>")));
+        }
+    }
+    try!(write!(w, "<pre><code>{}</code></pre>\n", html_escape(&*cmd.usage)));
+
+    if !cmd.args.is_empty() {
+        try!(write!(w,
+                    "<table>\n<thead><tr><th>Short</th><th>Long</th><th>Values</th>\
+                     <th>Default</th><th>Possible values</th><th>Env</th><th>Required</th>\
+                     <th>Description</th></tr></thead>\n<tbody>\n"));
+        for a in &cmd.args {
+            let possible: Vec<_> = a.possible.iter().map(|v| html_code(v)).collect();
+            try!(write!(w,
+                        "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                         <td>{}</td><td>{}</td></tr>\n",
+                        a.short.as_ref().map_or(String::new(), |s| html_code(s)),
+                        a.long.as_ref().map_or(String::new(), |l| html_code(l)),
+                        a.vals.as_ref().map_or(String::new(), |v| html_code(v)),
+                        a.default.as_ref().map_or(String::new(), |d| html_code(d)),
+                        possible.join(", "),
+                        a.env.as_ref().map_or(String::new(), |e| html_code(e)),
+                        if a.required { "yes" } else { "" },
+                        html_escape(&*a.help).replace('\n', "<br>")));
+        }
+        try!(write!(w, "</tbody>\n</table>\n"));
+    }
+
+    if !cmd.subcommands.is_empty() {
+        try!(write!(w, "<p><strong>Subcommands:</strong></p>\n<ul>\n"));
+        for &(ref name, ref anchor, ref about) in &cmd.subcommands {
+            try!(write!(w,
+                        "<li><a href=\"#{}\">{}</a>",
+                        html_escape(anchor),
+                        html_code(name)));
+            if let Some(ref about) = *about {
+                try!(write!(w, ": {}", html_escape(about).replace('\n', " ")));
+            }
+            try!(write!(w, "</li>\n"));
+        }
+        try!(write!(w, "</ul>\n"));
+    }
+    try!(write!(w, "</section>\n"));
+    Ok(())
+}
+
+// Wraps a value in a code span, pipes still need escaping since they'd otherwise end the table
+// cell
+fn md_code(s: &str) -> String { format!("`{}`", s.replace('|', "\\|")) }
+
+fn md_text(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('|', "\\|")
+}
+
+fn html_code(s: &str) -> String { format!("<code>{}</code>", html_escape(s)) }
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{md_code, md_text, html_escape};
+
+    #[test]
+    fn md_escapes_table_pipes() {
+        assert_eq!(md_code("a|b"), "`a\\|b`");
+        assert_eq!(md_text("<a|b>"), "&lt;a\\|b&gt;");
+    }
+
+    #[test]
+    fn html_escapes() {
+        assert_eq!(html_escape("<a href=\"x\">&</a>"),
+                   "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+    }
+}
@@ -10,6 +10,7 @@ use std::result::Result as StdResult;
 // Internal
 use args::{FlagBuilder, AnyArg};
 use fmt::{Colorizer, ColorizerOption, ColorWhen};
+use messages::{English, Messages};
 use suggestions;
 
 /// Short hand for [`Result`] type
@@ -20,7 +21,9 @@ pub type Result<T> = StdResult<T, Error>;
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ErrorKind {
     /// Occurs when an [`Arg`] has a set of possible values,
-    /// and the user provides a value which isn't in that set.
+    /// and the user provides a value which isn't in that set, or when its [`Arg::value_parser`]
+    /// fails to parse the value. A [`Arg::key_value`] argument also reports a missing delimiter or
+    /// a key outside of its [`Arg::possible_keys`] with this kind.
     ///
     /// # Examples
     ///
@@ -35,6 +38,9 @@ pub enum ErrorKind {
     /// assert_eq!(result.unwrap_err().kind, ErrorKind::InvalidValue);
     /// ```
     /// [`Arg`]: ./struct.Arg.html
+    /// [`Arg::value_parser`]: ./struct.Arg.html#method.value_parser
+    /// [`Arg::key_value`]: ./struct.Arg.html#method.key_value
+    /// [`Arg::possible_keys`]: ./struct.Arg.html#method.possible_keys
     InvalidValue,
 
     /// Occurs when a user provides a flag, option, argument or subcommand which isn't defined.
@@ -350,6 +356,24 @@ pub enum ErrorKind {
     /// ```
     VersionDisplayed,
 
+    /// Not a true "error" as it means the program was run by a completion script to get the
+    /// candidates for an [`Arg::completer`]. The candidates will be sent to `stdout`, one per
+    /// line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let result = App::new("prog")
+    ///     .arg(Arg::with_name("host")
+    ///         .completer(|_| vec![String::from("localhost")]))
+    ///     .get_matches_from_safe(vec!["prog", "__complete", "lo"]);
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::CompletionsDisplayed);
+    /// ```
+    /// [`Arg::completer`]: ./struct.Arg.html#method.completer
+    CompletionsDisplayed,
+
     /// Occurs when using the [`value_t!`] and [`values_t!`] macros to convert an argument value
     /// into type `T`, but the argument you requested wasn't used. I.e. you asked for an argument
     /// with name `config` to be converted, but `config` wasn't used by the user.
@@ -357,8 +381,30 @@ pub enum ErrorKind {
     /// [`values_t!`]: ./macro.values_t!.html
     ArgumentNotFound,
 
+    /// Occurs when a command line given to [`App::get_matches_from_str`] (or read by a [`Repl`])
+    /// can't be split into arguments, because a quote isn't closed or it ends in a `\`. The
+    /// [`ContextKind::Column`] of the error is where the offending character is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ContextKind, ContextValue, ErrorKind};
+    /// let err = App::new("prog")
+    ///     .arg(Arg::with_name("msg").short("m").takes_value(true))
+    ///     .get_matches_from_str("prog -m 'oops")
+    ///     .unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::ShellSyntax);
+    /// assert_eq!(err.context(ContextKind::Column), Some(&ContextValue::Number(9)));
+    /// ```
+    /// [`App::get_matches_from_str`]: ./struct.App.html#method.get_matches_from_str
+    /// [`Repl`]: ./struct.Repl.html
+    /// [`ContextKind::Column`]: ./enum.ContextKind.html#variant.Column
+    ShellSyntax,
+
     /// Represents an [I/O error].
-    /// Can occur when writing to `stderr` or `stdout` or reading a configuration file.
+    /// Can occur when writing to `stderr` or `stdout`, reading a configuration file, or expanding
+    /// a response file with [`AppSettings::ResponseFiles`].
+    /// [`AppSettings::ResponseFiles`]: ./enum.AppSettings.html#variant.ResponseFiles
     /// [I/O error]: https://doc.rust-lang.org/std/io/struct.Error.html
     Io,
 
@@ -369,6 +415,104 @@ pub enum ErrorKind {
     Format,
 }
 
+/// The kind of a piece of information attached to an [`Error`], see [`Error::context`]
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ContextKind, ContextValue};
+/// let err = App::new("prog")
+///     .arg(Arg::with_name("color")
+///         .long("color")
+///         .takes_value(true)
+///         .possible_values(&["always", "never"]))
+///     .get_matches_from_safe(vec!["prog", "--color", "purple"])
+///     .unwrap_err();
+/// assert_eq!(err.context(ContextKind::InvalidValue),
+///            Some(&ContextValue::String("purple".into())));
+/// assert_eq!(err.context(ContextKind::ValidValues),
+///            Some(&ContextValue::Strings(vec!["always".into(), "never".into()])));
+/// ```
+/// [`Error`]: ./struct.Error.html
+/// [`Error::context`]: ./struct.Error.html#method.context
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ContextKind {
+    /// The argument which caused the error as shown in the usage, such as `--port <port>`, or a
+    /// list of all the required arguments which weren't given
+    InvalidArg,
+    /// The argument which [`ContextKind::InvalidArg`] conflicts with
+    /// [`ContextKind::InvalidArg`]: ./enum.ContextKind.html#variant.InvalidArg
+    PriorArg,
+    /// The value which was rejected
+    InvalidValue,
+    /// The values the argument accepts
+    ValidValues,
+    /// The key of a [`Arg::key_value`] pair which was rejected
+    /// [`Arg::key_value`]: ./struct.Arg.html#method.key_value
+    InvalidKey,
+    /// The keys a [`Arg::key_value`] argument accepts
+    /// [`Arg::key_value`]: ./struct.Arg.html#method.key_value
+    ValidKeys,
+    /// The subcommand which wasn't recognized
+    InvalidSubcommand,
+    /// A known argument similar to the one which was used, such as `--color`
+    SuggestedArg,
+    /// A valid value or key similar to the one which was used
+    SuggestedValue,
+    /// A subcommand similar to the one which was used
+    SuggestedSubcommand,
+    /// The name of the command in which the error occurred
+    Command,
+    /// The number of values the argument expects
+    ExpectedNumValues,
+    /// The minimum number of values the argument expects
+    MinValues,
+    /// The number of values which were actually given
+    ActualNumValues,
+    /// Why a value or file was rejected, such as the message returned by a [`Arg::validator`]
+    /// [`Arg::validator`]: ./struct.Arg.html#method.validator
+    Reason,
+    /// The response file which couldn't be expanded
+    ResponseFile,
+    /// The config file the error came from
+    ConfigFile,
+    /// The line of the [`ContextKind::ConfigFile`] the error came from
+    /// [`ContextKind::ConfigFile`]: ./enum.ContextKind.html#variant.ConfigFile
+    ConfigLine,
+    /// The column of a command line which couldn't be split into arguments, counting characters
+    /// from 1
+    Column,
+    /// The usage string shown with the error
+    Usage,
+    /// A message which isn't broken down any further, such as the one given to
+    /// [`Error::with_description`]
+    /// [`Error::with_description`]: ./struct.Error.html#method.with_description
+    Custom,
+}
+
+/// A piece of information attached to an [`Error`], see [`ContextKind`]
+/// [`Error`]: ./struct.Error.html
+/// [`ContextKind`]: ./enum.ContextKind.html
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextValue {
+    /// A single string, such as an argument or value
+    String(String),
+    /// A list of strings, such as the valid values of an argument
+    Strings(Vec<String>),
+    /// A number, such as how many values were given
+    Number(u64),
+}
+
+impl Display for ContextValue {
+    fn fmt(&self, f: &mut std_fmt::Formatter) -> std_fmt::Result {
+        match *self {
+            ContextValue::String(ref s) => write!(f, "{}", s),
+            ContextValue::Strings(ref v) => write!(f, "{}", v.join(", ")),
+            ContextValue::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
 /// Command Line Argument Parser Error
 #[derive(Debug)]
 pub struct Error {
@@ -378,6 +522,10 @@ pub struct Error {
     pub kind: ErrorKind,
     /// Any additional information passed along, such as the argument name that caused the error
     pub info: Option<Vec<String>>,
+    context: Vec<(ContextKind, ContextValue)>,
+    color: ColorWhen,
+    json: bool,
+    code: Option<i32>,
 }
 
 impl Error {
@@ -385,25 +533,389 @@ impl Error {
     pub fn use_stderr(&self) -> bool {
         match self.kind {
             ErrorKind::HelpDisplayed |
-            ErrorKind::VersionDisplayed => false,
+            ErrorKind::VersionDisplayed |
+            ErrorKind::CompletionsDisplayed => false,
             _ => true,
         }
     }
 
-    /// Prints the error to `stderr` and exits with a status of `1`
+    /// Prints the error to `stderr` and exits with the [`Error::exit_code`]. With
+    /// [`AppSettings::JsonErrors`] the error is printed as JSON, see [`Error::to_json`].
+    /// [`Error::exit_code`]: ./struct.Error.html#method.exit_code
+    /// [`AppSettings::JsonErrors`]: ./enum.AppSettings.html#variant.JsonErrors
+    /// [`Error::to_json`]: ./struct.Error.html#method.to_json
     pub fn exit(&self) -> ! {
+        self.print();
+        process::exit(self.exit_code());
+    }
+
+    /// The status [`Error::exit`] exits with, which is the one set with [`App::exit_code`] for
+    /// this kind of error, or otherwise `0` for help, version and completions and `1` for
+    /// everything else.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let app = App::new("prog")
+    ///     .exit_code(ErrorKind::UnknownArgument, 64)
+    ///     .arg(Arg::with_name("input").required(true));
+    ///
+    /// let err = app.clone().get_matches_from_safe(vec!["prog", "-x"]).unwrap_err();
+    /// assert_eq!(err.exit_code(), 64);
+    ///
+    /// let err = app.get_matches_from_safe(vec!["prog"]).unwrap_err();
+    /// assert_eq!(err.exit_code(), 1);
+    /// ```
+    /// [`Error::exit`]: ./struct.Error.html#method.exit
+    /// [`App::exit_code`]: ./struct.App.html#method.exit_code
+    pub fn exit_code(&self) -> i32 {
+        match self.code {
+            Some(code) => code,
+            None if self.use_stderr() => 1,
+            None => 0,
+        }
+    }
+
+    // Writes the error to `stderr`, or help and similar to `stdout`
+    #[doc(hidden)]
+    pub fn print(&self) {
         if self.use_stderr() {
-            wlnerr!("{}", self.message);
-            process::exit(1);
+            if self.json {
+                wlnerr!("{}", self.to_json());
+            } else {
+                wlnerr!("{}", self.message);
+            }
+            return;
         }
         let out = io::stdout();
         writeln!(&mut out.lock(), "{}", self.message).expect("Error writing Error to stdout");
-        process::exit(0);
+    }
+
+    /// Gets a piece of information about what went wrong, such as the offending argument or the
+    /// values it would have accepted, without having to pick apart the [`Error::message`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind, ContextKind, ContextValue};
+    /// let err = App::new("prog")
+    ///     .arg(Arg::with_name("color").long("color"))
+    ///     .get_matches_from_safe(vec!["prog", "--size"])
+    ///     .unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    /// assert_eq!(err.context(ContextKind::InvalidArg),
+    ///            Some(&ContextValue::String("--size".into())));
+    /// assert_eq!(err.context(ContextKind::ValidValues), None);
+    /// ```
+    /// [`Error::message`]: ./struct.Error.html#structfield.message
+    pub fn context(&self, kind: ContextKind) -> Option<&ContextValue> {
+        self.context.iter().find(|&&(k, _)| k == kind).map(|&(_, ref v)| v)
+    }
+
+    /// Gets all of the information attached to this error, in the order it was added. See
+    /// [`Error::context`].
+    /// [`Error::context`]: ./struct.Error.html#method.context
+    pub fn contexts(&self) -> &[(ContextKind, ContextValue)] { &*self.context }
+
+    /// Renders the error as a single line JSON object for programs which drive the CLI, which is
+    /// what [`Error::exit`] prints with [`AppSettings::JsonErrors`].
+    ///
+    /// The object has these fields, taken from the error's [`Error::context`]. Any of them
+    /// besides `kind` and `message` are `null` when they don't apply to the error.
+    ///
+    /// * `kind`: the [`ErrorKind`], such as `"InvalidValue"`
+    /// * `message`: the [`Error::message`] without colors
+    /// * `arg`: the offending argument or subcommand, or a list of the required arguments which
+    ///   are missing
+    /// * `value`: the rejected value or key
+    /// * `valid_values`: the values or keys the argument accepts
+    /// * `suggestion`: a similar argument, value or subcommand
+    /// * `usage`: the usage string without its heading
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let err = App::new("prog")
+    ///     .arg(Arg::with_name("color").long("color"))
+    ///     .get_matches_from_safe(vec!["prog", "--size"])
+    ///     .unwrap_err();
+    /// let json = err.to_json();
+    /// assert!(json.starts_with(r#"{"kind":"UnknownArgument","message":"error: Found"#));
+    /// assert!(json.contains(r#""arg":"--size","value":null,"valid_values":null"#));
+    /// assert!(json.ends_with(r#""suggestion":null,"usage":"prog [FLAGS]"}"#));
+    /// ```
+    /// [`Error::exit`]: ./struct.Error.html#method.exit
+    /// [`AppSettings::JsonErrors`]: ./enum.AppSettings.html#variant.JsonErrors
+    /// [`Error::context`]: ./struct.Error.html#method.context
+    /// [`ErrorKind`]: ./enum.ErrorKind.html
+    /// [`Error::message`]: ./struct.Error.html#structfield.message
+    pub fn to_json(&self) -> String {
+        use self::ContextKind::*;
+        let first = |kinds: &[ContextKind]| {
+            kinds.iter().filter_map(|&k| self.context(k)).next().cloned()
+        };
+        let usage = self.string(Usage).map(|u| {
+            let u = u.splitn(2, '\n').last().unwrap_or(u);
+            ContextValue::String(uncolored(u.trim()))
+        });
+        let fields = [("kind", Some(ContextValue::String(format!("{:?}", self.kind)))),
+                      ("message", Some(ContextValue::String(uncolored(&*self.message)))),
+                      ("arg", first(&[InvalidArg, InvalidSubcommand])),
+                      ("value", first(&[InvalidKey, InvalidValue])),
+                      ("valid_values", first(&[ValidKeys, ValidValues])),
+                      ("suggestion", first(&[SuggestedArg, SuggestedValue, SuggestedSubcommand])),
+                      ("usage", usage)];
+        let fields = fields.iter()
+            .map(|&(name, ref v)| format!("{}:{}", json_string(name), json_value(v.as_ref())))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", fields.join(","))
     }
 
     #[doc(hidden)]
     pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> { write!(w, "{}", self.message) }
 
+    // Builds an error from its context, rendering the message from it
+    fn new(kind: ErrorKind,
+           info: Option<Vec<String>>,
+           context: Vec<(ContextKind, ContextValue)>,
+           color: ColorWhen)
+           -> Self {
+        let mut e = Error {
+            message: String::new(),
+            kind: kind,
+            info: info,
+            context: context,
+            color: color,
+            json: false,
+            code: None,
+        };
+        e.message = e.render();
+        e
+    }
+
+    // An error whose message is already complete, such as the help message
+    #[doc(hidden)]
+    pub fn raw<M: Into<String>>(message: M, kind: ErrorKind) -> Self {
+        Error {
+            message: message.into(),
+            kind: kind,
+            info: None,
+            context: vec![],
+            color: ColorWhen::Auto,
+            json: false,
+            code: None,
+        }
+    }
+
+    fn string(&self, kind: ContextKind) -> Option<&str> {
+        match self.context(kind) {
+            Some(&ContextValue::String(ref s)) => Some(&*s),
+            _ => None,
+        }
+    }
+
+    fn strings(&self, kind: ContextKind) -> Option<&[String]> {
+        match self.context(kind) {
+            Some(&ContextValue::Strings(ref v)) => Some(&*v),
+            _ => None,
+        }
+    }
+
+    fn number(&self, kind: ContextKind) -> Option<u64> {
+        match self.context(kind) {
+            Some(&ContextValue::Number(n)) => Some(n),
+            _ => None,
+        }
+    }
+
+    fn render(&self) -> String { self.render_with(&English) }
+
+    fn render_with(&self, m: &Messages) -> String {
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: self.color,
+        });
+        let desc = match self.string(ContextKind::Custom) {
+            Some(custom) => Some(custom.to_owned()),
+            None => self.describe(m, &c),
+        };
+        let mut message = format!("{} {}",
+                                  c.error(m.error_label()),
+                                  desc.unwrap_or_else(String::new));
+        if let (Some(file), Some(line)) =
+            (self.string(ContextKind::ConfigFile), self.number(ContextKind::ConfigLine)) {
+            let at = message.find('\n').unwrap_or(message.len());
+            message = format!("{}\n\t{}{}",
+                              &message[..at],
+                              m.in_config(file, line),
+                              &message[at..]);
+        }
+        message
+    }
+
+    // Re-renders the message in the given language, unless it's a help message or similar which
+    // has no context to render from
+    #[doc(hidden)]
+    pub fn localize(mut self, m: &Messages) -> Self {
+        if !self.context.is_empty() {
+            self.message = self.render_with(m);
+        }
+        self
+    }
+
+    // Sets the status `exit` exits with
+    #[doc(hidden)]
+    pub fn with_exit_code(mut self, code: i32) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    // Makes `exit` print the error as JSON
+    #[doc(hidden)]
+    pub fn as_json(mut self) -> Self {
+        self.json = true;
+        self
+    }
+
+    // Renders everything after "error: " from the context of each kind of error, or `None` when
+    // the context needed for the kind is missing
+    fn describe(&self, m: &Messages, c: &Colorizer) -> Option<String> {
+        let warning = |s: &str| c.warning(s).to_string();
+        let arg = self.string(ContextKind::InvalidArg).map(&warning);
+        let value = self.string(ContextKind::InvalidValue).map(&warning);
+        let usage = match self.string(ContextKind::Usage) {
+            Some(u) => format!("\n\n{}\n\n{}", u, m.more_info(&*c.good("--help").to_string())),
+            None => String::new(),
+        };
+        let desc = match self.kind {
+            ErrorKind::ArgumentConflict => {
+                let other = self.string(ContextKind::PriorArg).map(&warning);
+                format!("{}{}",
+                        m.argument_conflict(&*some!(arg), other.as_ref().map(|s| &**s)),
+                        usage)
+            }
+            ErrorKind::EmptyValue => format!("{}{}", m.empty_value(&*some!(arg)), usage),
+            ErrorKind::InvalidValue => {
+                let suggestion = self.suggestion(m, c, ContextKind::SuggestedValue);
+                if let Some(keys) = self.strings(ContextKind::ValidKeys) {
+                    let key = warning(some!(self.string(ContextKind::InvalidKey)));
+                    format!("{}\n{}{}",
+                            m.invalid_key(&*key, &*some!(arg), &*sorted(c, keys)),
+                            suggestion,
+                            usage)
+                } else if let Some(vals) = self.strings(ContextKind::ValidValues) {
+                    format!("{}\n{}{}",
+                            m.invalid_value(&*some!(value), &*some!(arg), &*sorted(c, vals)),
+                            suggestion,
+                            usage)
+                } else {
+                    let reason = some!(self.string(ContextKind::Reason));
+                    format!("{}{}", m.unparsable_value(&*some!(value), &*some!(arg), reason), usage)
+                }
+            }
+            ErrorKind::InvalidSubcommand => {
+                let s = some!(self.string(ContextKind::InvalidSubcommand));
+                let cmd = format!("{} {} {}",
+                                  some!(self.string(ContextKind::Command)),
+                                  c.good("--"),
+                                  s);
+                format!("{}{}\n\n{}{}",
+                        m.unrecognized_subcommand(&*warning(s)),
+                        self.suggestion(m, c, ContextKind::SuggestedSubcommand),
+                        m.escape_subcommand(&*cmd),
+                        usage)
+            }
+            ErrorKind::UnrecognizedSubcommand => {
+                let s = warning(some!(self.string(ContextKind::InvalidSubcommand)));
+                match self.string(ContextKind::Command) {
+                    Some(cmd) => {
+                        format!("{}\n\n{}\n\t{} help <subcommands>...\n\n{}",
+                                m.unrecognized_subcommand(&*s),
+                                c.warning(m.usage_heading()),
+                                cmd,
+                                m.more_info(&*c.good("--help").to_string()))
+                    }
+                    None => format!("{}{}", m.unrecognized_subcommand(&*s), usage),
+                }
+            }
+            ErrorKind::MissingRequiredArgument => {
+                let args = some!(self.strings(ContextKind::InvalidArg))
+                    .iter()
+                    .fold(String::new(),
+                          |acc, s| acc + &*format!("\n    {}", c.error(&**s)));
+                format!("{}{}", m.missing_required_arguments(&*args), usage)
+            }
+            ErrorKind::MissingSubcommand => {
+                match self.string(ContextKind::Command) {
+                    Some(cmd) => format!("{}{}", m.missing_subcommand(&*warning(cmd)), usage),
+                    None => format!("{}{}", m.subcommand_not_provided(), usage),
+                }
+            }
+            ErrorKind::InvalidUtf8 => format!("{}{}", m.invalid_utf8(), usage),
+            ErrorKind::TooManyValues => {
+                format!("{}{}", m.too_many_values(&*some!(value), &*some!(arg)), usage)
+            }
+            ErrorKind::TooFewValues => {
+                format!("{}{}",
+                        m.too_few_values(&*some!(arg),
+                                         some!(self.number(ContextKind::MinValues)),
+                                         some!(self.number(ContextKind::ActualNumValues))),
+                        usage)
+            }
+            ErrorKind::ValueValidation => {
+                m.value_validation(arg.as_ref().map(|s| &**s),
+                                   some!(self.string(ContextKind::Reason)))
+            }
+            ErrorKind::WrongNumberOfValues => {
+                format!("{}{}",
+                        m.wrong_number_of_values(&*some!(arg),
+                                                 some!(self.number(ContextKind::ExpectedNumValues)),
+                                                 some!(self.number(ContextKind::ActualNumValues))),
+                        usage)
+            }
+            ErrorKind::UnexpectedMultipleUsage => {
+                format!("{}{}", m.unexpected_multiple_usage(&*some!(arg)), usage)
+            }
+            ErrorKind::UnknownArgument => {
+                format!("{}{}{}",
+                        m.unknown_argument(&*some!(arg)),
+                        self.suggestion(m, c, ContextKind::SuggestedArg),
+                        usage)
+            }
+            ErrorKind::ArgumentNotFound => {
+                m.argument_not_found(some!(self.string(ContextKind::InvalidArg)))
+            }
+            ErrorKind::ShellSyntax => {
+                m.shell_syntax(some!(self.number(ContextKind::Column)),
+                               some!(self.string(ContextKind::Reason)))
+            }
+            ErrorKind::Io => {
+                let reason = some!(self.string(ContextKind::Reason));
+                if let Some(path) = self.string(ContextKind::ResponseFile) {
+                    m.response_file(&*warning(path), reason)
+                } else {
+                    let file = some!(self.string(ContextKind::ConfigFile));
+                    m.config_unreadable(&*warning(file), reason)
+                }
+            }
+            _ => return None,
+        };
+        Some(desc)
+    }
+
+    // The "Did you mean ...?" line, if there is a suggestion of the given kind
+    fn suggestion(&self, m: &Messages, c: &Colorizer, kind: ContextKind) -> String {
+        match self.string(kind) {
+            Some(s) if kind == ContextKind::SuggestedArg => {
+                format!("\n\t{}", m.did_you_mean(&*c.good(s).to_string()))
+            }
+            Some(s) => format!("\n\t{}", m.did_you_mean(&*format!("'{}'", c.good(s)))),
+            None => String::new(),
+        }
+    }
+
     #[doc(hidden)]
     pub fn argument_conflict<'a, 'b, A, O, U>(arg: &A,
                                               other: Option<O>,
@@ -414,33 +926,15 @@ impl Error {
               O: Into<String>,
               U: Display
     {
-        let mut v = vec![arg.name().to_owned()];
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} The argument '{}' cannot be used with {}\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(&*arg.to_string()),
-                             match other {
-                                 Some(name) => {
-                                     let n = name.into();
-                                     v.push(n.clone());
-                                     c.warning(format!("'{}'", n))
-                                 }
-                                 None => {
-                                     c.none("one or more of the other specified arguments"
-                                         .to_owned())
-                                 }
-                             },
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::ArgumentConflict,
-            info: Some(v),
+        let mut info = vec![arg.name().to_owned()];
+        let mut context = vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_string()))];
+        if let Some(other) = other {
+            let other = other.into();
+            info.push(other.clone());
+            context.push((ContextKind::PriorArg, ContextValue::String(other)));
         }
+        context.push((ContextKind::Usage, ContextValue::String(usage.to_string())));
+        Error::new(ErrorKind::ArgumentConflict, Some(info), context, color)
     }
 
     #[doc(hidden)]
@@ -448,22 +942,11 @@ impl Error {
         where A: AnyArg<'a, 'b> + Display,
               U: Display
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} The argument '{}' requires a value but none was supplied\
-                            \n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(arg.to_string()),
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::EmptyValue,
-            info: Some(vec![arg.name().to_owned()]),
-        }
+        Error::new(ErrorKind::EmptyValue,
+                   Some(vec![arg.name().to_owned()]),
+                   vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_string())),
+                        (ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
     }
 
     #[doc(hidden)]
@@ -478,38 +961,101 @@ impl Error {
               A: AnyArg<'a, 'b> + Display,
               U: Display
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        let suffix =
-            suggestions::did_you_mean_suffix(bad_val.as_ref(),
-                                             good_vals.iter(),
-                                             suggestions::DidYouMeanMessageStyle::EnumValue);
-
-        let mut sorted = vec![];
-        for v in good_vals {
-            let val = format!("{}", c.good(v));
-            sorted.push(val);
+        let bad_val = bad_val.as_ref();
+        let mut context =
+            vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_string())),
+                 (ContextKind::InvalidValue, ContextValue::String(bad_val.to_owned())),
+                 (ContextKind::ValidValues, ContextValue::Strings(strings(good_vals)))];
+        if let Some(s) = suggestions::did_you_mean(bad_val, good_vals.iter()) {
+            context.push((ContextKind::SuggestedValue, ContextValue::String(s.to_owned())));
         }
-        sorted.sort();
-        let valid_values = sorted.join(", ");
-        Error {
-            message: format!("{} '{}' isn't a valid value for '{}'\n\t\
-                            [values: {}]\n\
-                            {}\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(bad_val.as_ref()),
-                             c.warning(arg.to_string()),
-                             valid_values,
-                             suffix.0,
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::InvalidValue,
-            info: Some(vec![arg.name().to_owned(), bad_val.as_ref().to_owned()]),
+        context.push((ContextKind::Usage, ContextValue::String(usage.to_string())));
+        Error::new(ErrorKind::InvalidValue,
+                   Some(vec![arg.name().to_owned(), bad_val.to_owned()]),
+                   context,
+                   color)
+    }
+
+    #[doc(hidden)]
+    pub fn invalid_key<'a, 'b, B, G, A, U>(bad_key: B,
+                                           good_keys: &[G],
+                                           arg: &A,
+                                           usage: U,
+                                           color: ColorWhen)
+                                           -> Self
+        where B: AsRef<str>,
+              G: AsRef<str> + Display,
+              A: AnyArg<'a, 'b> + Display,
+              U: Display
+    {
+        let bad_key = bad_key.as_ref();
+        let mut context =
+            vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_string())),
+                 (ContextKind::InvalidKey, ContextValue::String(bad_key.to_owned())),
+                 (ContextKind::ValidKeys, ContextValue::Strings(strings(good_keys)))];
+        if let Some(s) = suggestions::did_you_mean(bad_key, good_keys.iter()) {
+            context.push((ContextKind::SuggestedValue, ContextValue::String(s.to_owned())));
         }
+        context.push((ContextKind::Usage, ContextValue::String(usage.to_string())));
+        Error::new(ErrorKind::InvalidValue,
+                   Some(vec![arg.name().to_owned(), bad_key.to_owned()]),
+                   context,
+                   color)
+    }
+
+    #[doc(hidden)]
+    pub fn unparsable_value<'a, 'b, B, R, A, U>(bad_val: B,
+                                                reason: R,
+                                                arg: &A,
+                                                usage: U,
+                                                color: ColorWhen)
+                                                -> Self
+        where B: AsRef<str>,
+              R: Display,
+              A: AnyArg<'a, 'b> + Display,
+              U: Display
+    {
+        let bad_val = bad_val.as_ref();
+        Error::new(ErrorKind::InvalidValue,
+                   Some(vec![arg.name().to_owned(), bad_val.to_owned()]),
+                   vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_string())),
+                        (ContextKind::InvalidValue, ContextValue::String(bad_val.to_owned())),
+                        (ContextKind::Reason, ContextValue::String(reason.to_string())),
+                        (ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
+    }
+
+    // A value in an `ArgMatches` couldn't be converted by `FromArg`, which only knows the
+    // argument's name and the usage stored in the matches
+    #[doc(hidden)]
+    pub fn unconvertible_value<R>(bad_val: &str, reason: R, arg: &str, usage: &str) -> Self
+        where R: Display
+    {
+        Error::new(ErrorKind::InvalidValue,
+                   Some(vec![arg.to_owned(), bad_val.to_owned()]),
+                   with_usage(vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_owned())),
+                                   (ContextKind::InvalidValue,
+                                    ContextValue::String(bad_val.to_owned())),
+                                   (ContextKind::Reason, ContextValue::String(reason.to_string()))],
+                              usage),
+                   ColorWhen::Auto)
+    }
+
+    // The subcommand in an `ArgMatches` isn't one `FromSubcommand` knows
+    #[doc(hidden)]
+    pub fn unconvertible_subcommand(subcmd: &str, usage: &str) -> Self {
+        Error::new(ErrorKind::UnrecognizedSubcommand,
+                   Some(vec![subcmd.to_owned()]),
+                   with_usage(vec![(ContextKind::InvalidSubcommand,
+                                    ContextValue::String(subcmd.to_owned()))],
+                              usage),
+                   ColorWhen::Auto)
+    }
+
+    // An `ArgMatches` has no subcommand for `FromSubcommand` to convert
+    #[doc(hidden)]
+    pub fn subcommand_not_provided(usage: &str) -> Self {
+        Error::new(ErrorKind::MissingSubcommand, None, with_usage(vec![], usage), ColorWhen::Auto)
     }
 
     #[doc(hidden)]
@@ -525,28 +1071,14 @@ impl Error {
               U: Display
     {
         let s = subcmd.into();
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} The subcommand '{}' wasn't recognized\n\t\
-                            Did you mean '{}'?\n\n\
-                            If you believe you received this message in error, try \
-                            re-running with '{} {} {}'\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(&*s),
-                             c.good(did_you_mean.as_ref()),
-                             name,
-                             c.good("--"),
-                             &*s,
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::InvalidSubcommand,
-            info: Some(vec![s]),
-        }
+        Error::new(ErrorKind::InvalidSubcommand,
+                   Some(vec![s.clone()]),
+                   vec![(ContextKind::InvalidSubcommand, ContextValue::String(s)),
+                        (ContextKind::SuggestedSubcommand,
+                         ContextValue::String(did_you_mean.as_ref().to_owned())),
+                        (ContextKind::Command, ContextValue::String(name.to_string())),
+                        (ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
     }
 
     #[doc(hidden)]
@@ -555,45 +1087,22 @@ impl Error {
               N: Display
     {
         let s = subcmd.into();
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} The subcommand '{}' wasn't recognized\n\n\
-                            {}\n\t\
-                                {} help <subcommands>...\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(&*s),
-                             c.warning("USAGE:"),
-                             name,
-                             c.good("--help")),
-            kind: ErrorKind::UnrecognizedSubcommand,
-            info: Some(vec![s]),
-        }
+        Error::new(ErrorKind::UnrecognizedSubcommand,
+                   Some(vec![s.clone()]),
+                   vec![(ContextKind::InvalidSubcommand, ContextValue::String(s)),
+                        (ContextKind::Command, ContextValue::String(name.to_string()))],
+                   color)
     }
 
     #[doc(hidden)]
-    pub fn missing_required_argument<R, U>(required: R, usage: U, color: ColorWhen) -> Self
-        where R: Display,
-              U: Display
+    pub fn missing_required_argument<U>(required: Vec<String>, usage: U, color: ColorWhen) -> Self
+        where U: Display
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} The following required arguments were not provided:{}\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             required,
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::MissingRequiredArgument,
-            info: None,
-        }
+        Error::new(ErrorKind::MissingRequiredArgument,
+                   None,
+                   vec![(ContextKind::InvalidArg, ContextValue::Strings(required)),
+                        (ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
     }
 
     #[doc(hidden)]
@@ -601,21 +1110,11 @@ impl Error {
         where N: AsRef<str> + Display,
               U: Display
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} '{}' requires a subcommand, but one was not provided\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(name),
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::MissingSubcommand,
-            info: None,
-        }
+        Error::new(ErrorKind::MissingSubcommand,
+                   None,
+                   vec![(ContextKind::Command, ContextValue::String(name.as_ref().to_owned())),
+                        (ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
     }
 
 
@@ -623,20 +1122,10 @@ impl Error {
     pub fn invalid_utf8<U>(usage: U, color: ColorWhen) -> Self
         where U: Display
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} Invalid UTF-8 was detected in one or more arguments\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::InvalidUtf8,
-            info: None,
-        }
+        Error::new(ErrorKind::InvalidUtf8,
+                   None,
+                   vec![(ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
     }
 
     #[doc(hidden)]
@@ -650,23 +1139,12 @@ impl Error {
               U: Display
     {
         let v = val.as_ref();
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} The value '{}' was provided to '{}', but it wasn't expecting \
-                            any more values\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(v),
-                             c.warning(arg.to_string()),
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::TooManyValues,
-            info: Some(vec![arg.name().to_owned(), v.to_owned()]),
-        }
+        Error::new(ErrorKind::TooManyValues,
+                   Some(vec![arg.name().to_owned(), v.to_owned()]),
+                   vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_string())),
+                        (ContextKind::InvalidValue, ContextValue::String(v.to_owned())),
+                        (ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
     }
 
     #[doc(hidden)]
@@ -679,47 +1157,25 @@ impl Error {
         where A: AnyArg<'a, 'b> + Display,
               U: Display
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} The argument '{}' requires at least {} values, but only {} w{} \
-                            provided\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(arg.to_string()),
-                             c.warning(min_vals.to_string()),
-                             c.warning(curr_vals.to_string()),
-                             if curr_vals > 1 { "ere" } else { "as" },
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::TooFewValues,
-            info: Some(vec![arg.name().to_owned()]),
-        }
+        Error::new(ErrorKind::TooFewValues,
+                   Some(vec![arg.name().to_owned()]),
+                   vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_string())),
+                        (ContextKind::MinValues, ContextValue::Number(min_vals)),
+                        (ContextKind::ActualNumValues, ContextValue::Number(curr_vals as u64)),
+                        (ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
     }
 
     #[doc(hidden)]
     pub fn value_validation<'a, 'b, A>(arg: Option<&A>, err: String, color: ColorWhen) -> Self
         where A: AnyArg<'a, 'b> + Display
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} Invalid value{}: {}",
-                             c.error("error:"),
-                             if let Some(a) = arg {
-                                 format!(" for '{}'", c.warning(a.to_string()))
-                             } else {
-                                 "".to_string()
-                             },
-                             err),
-            kind: ErrorKind::ValueValidation,
-            info: None,
+        let mut context = vec![];
+        if let Some(a) = arg {
+            context.push((ContextKind::InvalidArg, ContextValue::String(a.to_string())));
         }
+        context.push((ContextKind::Reason, ContextValue::String(err)));
+        Error::new(ErrorKind::ValueValidation, None, context, color)
     }
 
     #[doc(hidden)]
@@ -729,36 +1185,22 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn wrong_number_of_values<'a, 'b, A, S, U>(arg: &A,
-                                                   num_vals: u64,
-                                                   curr_vals: usize,
-                                                   suffix: S,
-                                                   usage: U,
-                                                   color: ColorWhen)
-                                                   -> Self
+    pub fn wrong_number_of_values<'a, 'b, A, U>(arg: &A,
+                                                num_vals: u64,
+                                                curr_vals: usize,
+                                                usage: U,
+                                                color: ColorWhen)
+                                                -> Self
         where A: AnyArg<'a, 'b> + Display,
-              S: Display,
               U: Display
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} The argument '{}' requires {} values, but {} w{} \
-                            provided\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(arg.to_string()),
-                             c.warning(num_vals.to_string()),
-                             c.warning(curr_vals.to_string()),
-                             suffix,
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::WrongNumberOfValues,
-            info: Some(vec![arg.name().to_owned()]),
-        }
+        Error::new(ErrorKind::WrongNumberOfValues,
+                   Some(vec![arg.name().to_owned()]),
+                   vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_string())),
+                        (ContextKind::ExpectedNumValues, ContextValue::Number(num_vals)),
+                        (ContextKind::ActualNumValues, ContextValue::Number(curr_vals as u64)),
+                        (ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
     }
 
     #[doc(hidden)]
@@ -766,27 +1208,16 @@ impl Error {
         where A: AnyArg<'a, 'b> + Display,
               U: Display
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} The argument '{}' was provided more than once, but cannot \
-                            be used multiple times\n\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(arg.to_string()),
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::UnexpectedMultipleUsage,
-            info: Some(vec![arg.name().to_owned()]),
-        }
+        Error::new(ErrorKind::UnexpectedMultipleUsage,
+                   Some(vec![arg.name().to_owned()]),
+                   vec![(ContextKind::InvalidArg, ContextValue::String(arg.to_string())),
+                        (ContextKind::Usage, ContextValue::String(usage.to_string()))],
+                   color)
     }
 
     #[doc(hidden)]
     pub fn unknown_argument<A, U>(arg: A,
-                                  did_you_mean: &str,
+                                  did_you_mean: Option<String>,
                                   usage: U,
                                   color: ColorWhen)
                                   -> Self
@@ -794,40 +1225,80 @@ impl Error {
               U: Display
     {
         let a = arg.into();
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} Found argument '{}' which wasn't expected, or isn't valid in \
-            this context{}\n\
-                            {}\n\n\
-                            For more information try {}",
-                             c.error("error:"),
-                             c.warning(&*a),
-                             if did_you_mean.is_empty() {
-                                 "\n".to_owned()
-                             } else {
-                                 format!("{}\n", did_you_mean)
-                             },
-                             usage,
-                             c.good("--help")),
-            kind: ErrorKind::UnknownArgument,
-            info: Some(vec![a]),
+        let mut context = vec![(ContextKind::InvalidArg, ContextValue::String(a.clone()))];
+        if let Some(s) = did_you_mean {
+            context.push((ContextKind::SuggestedArg, ContextValue::String(s)));
         }
+        context.push((ContextKind::Usage, ContextValue::String(usage.to_string())));
+        Error::new(ErrorKind::UnknownArgument, Some(vec![a]), context, color)
     }
 
     #[doc(hidden)]
     pub fn io_error(e: &Error, color: ColorWhen) -> Self {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        Error {
-            message: format!("{} {}", c.error("error:"), e.description()),
-            kind: ErrorKind::Io,
-            info: None,
-        }
+        Error::new(ErrorKind::Io,
+                   None,
+                   vec![(ContextKind::Custom, ContextValue::String(e.description().to_owned()))],
+                   color)
+    }
+
+    #[doc(hidden)]
+    pub fn response_file<P, R>(path: P, reason: R, color: ColorWhen) -> Self
+        where P: Display,
+              R: Display
+    {
+        let p = path.to_string();
+        Error::new(ErrorKind::Io,
+                   Some(vec![p.clone()]),
+                   vec![(ContextKind::ResponseFile, ContextValue::String(p)),
+                        (ContextKind::Reason, ContextValue::String(reason.to_string()))],
+                   color)
+    }
+
+    #[doc(hidden)]
+    pub fn shell_syntax(column: usize, reason: &str, color: ColorWhen) -> Self {
+        Error::new(ErrorKind::ShellSyntax,
+                   None,
+                   vec![(ContextKind::Column, ContextValue::Number(column as u64)),
+                        (ContextKind::Reason, ContextValue::String(reason.to_owned()))],
+                   color)
+    }
+
+    #[doc(hidden)]
+    pub fn config_unreadable<S, E>(source: S, e: E, color: ColorWhen) -> Self
+        where S: Into<String>,
+              E: Display
+    {
+        let s = source.into();
+        Error::new(ErrorKind::Io,
+                   Some(vec![s.clone()]),
+                   vec![(ContextKind::ConfigFile, ContextValue::String(s)),
+                        (ContextKind::Reason, ContextValue::String(e.to_string()))],
+                   color)
+    }
+
+    #[doc(hidden)]
+    pub fn config_entry<D>(desc: D,
+                           kind: ErrorKind,
+                           source: &str,
+                           line: usize,
+                           color: ColorWhen)
+                           -> Self
+        where D: Display
+    {
+        Error::new(kind,
+                   None,
+                   vec![(ContextKind::Custom, ContextValue::String(desc.to_string()))],
+                   color)
+            .in_config(source, line)
+    }
+
+    // Points an error at the line of the config file which caused it
+    #[doc(hidden)]
+    pub fn in_config(mut self, source: &str, line: usize) -> Self {
+        self.context.push((ContextKind::ConfigFile, ContextValue::String(source.to_owned())));
+        self.context.push((ContextKind::ConfigLine, ContextValue::Number(line as u64)));
+        self.message = self.render();
+        self
     }
 
     #[doc(hidden)]
@@ -835,17 +1306,10 @@ impl Error {
         where A: Into<String>
     {
         let a = arg.into();
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: ColorWhen::Auto,
-        });
-        Error {
-            message: format!("{} The argument '{}' wasn't found",
-                             c.error("error:"),
-                             a.clone()),
-            kind: ErrorKind::ArgumentNotFound,
-            info: Some(vec![a]),
-        }
+        Error::new(ErrorKind::ArgumentNotFound,
+                   Some(vec![a.clone()]),
+                   vec![(ContextKind::InvalidArg, ContextValue::String(a))],
+                   ColorWhen::Auto)
     }
 
     /// Create an error with a custom description.
@@ -853,15 +1317,78 @@ impl Error {
     /// This can be used in combination with `Error::exit` to exit your program
     /// with a custom error message.
     pub fn with_description(description: &str, kind: ErrorKind) -> Self {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: ColorWhen::Auto,
-        });
-        Error {
-            message: format!("{} {}", c.error("error:"), description),
-            kind: kind,
-            info: None,
+        Error::new(kind,
+                   None,
+                   vec![(ContextKind::Custom, ContextValue::String(description.to_owned()))],
+                   ColorWhen::Auto)
+    }
+}
+
+// Adds the usage to an error's context, unless there is none (such as for an `ArgMatches` which
+// wasn't returned by a parser)
+fn with_usage(mut context: Vec<(ContextKind, ContextValue)>,
+              usage: &str)
+              -> Vec<(ContextKind, ContextValue)> {
+    if !usage.is_empty() {
+        context.push((ContextKind::Usage, ContextValue::String(usage.to_owned())));
+    }
+    context
+}
+
+// Turns the values of an error's context into owned strings
+fn strings<G: AsRef<str>>(vals: &[G]) -> Vec<String> {
+    vals.iter().map(|v| v.as_ref().to_owned()).collect()
+}
+
+// Lists the valid values or keys of an argument in the order they're shown in an error
+fn sorted(c: &Colorizer, vals: &[String]) -> String {
+    let mut sorted = vals.iter().map(|v| format!("{}", c.good(&**v))).collect::<Vec<_>>();
+    sorted.sort();
+    sorted.join(", ")
+}
+
+// Removes the ANSI color codes added by `Colorizer`
+fn uncolored(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_code = false;
+    for c in s.chars() {
+        match c {
+            '\x1b' => in_code = true,
+            'm' if in_code => in_code = false,
+            _ if in_code => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&*format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_value(v: Option<&ContextValue>) -> String {
+    match v {
+        None => "null".to_owned(),
+        Some(&ContextValue::String(ref s)) => json_string(s),
+        Some(&ContextValue::Strings(ref v)) => {
+            let v = v.iter().map(|s| json_string(s)).collect::<Vec<_>>();
+            format!("[{}]", v.join(","))
         }
+        Some(&ContextValue::Number(n)) => n.to_string(),
     }
 }
 
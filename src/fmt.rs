@@ -34,6 +34,12 @@ pub fn is_a_tty(_: bool) -> bool {
     false
 }
 
+#[cfg(feature = "color")]
+pub fn is_stdin_a_tty() -> bool { atty::is(atty::Stream::Stdin) }
+
+#[cfg(not(feature = "color"))]
+pub fn is_stdin_a_tty() -> bool { false }
+
 pub fn is_term_dumb() -> bool { env::var("TERM").ok() == Some(String::from("dumb")) }
 
 #[doc(hidden)]
@@ -92,13 +98,6 @@ impl Colorizer {
         debugln!("Colorizer::error;");
         color!(self, Error, msg)
     }
-
-    pub fn none<T>(&self, msg: T) -> Format<T>
-        where T: fmt::Display + AsRef<str>
-    {
-        debugln!("Colorizer::none;");
-        Format::None(msg)
-    }
 }
 
 impl Default for Colorizer {
@@ -353,6 +353,9 @@
 //! #### Opt-in features
 //! 
 //! * **"yaml"**: Enables building CLIs from YAML documents. (builds dependency `yaml-rust`)
+//! * **"json"**: Enables building CLIs from JSON documents. (builds dependency `serde_json`)
+//! * **"toml"**: Enables building CLIs from TOML documents. (builds dependency `toml`)
+//! * **"regex"**: Enables `validators::regex` for checking values against a regular expression. (builds dependency `regex`)
 //! * **"unstable"**: Enables unstable `clap` features that may change from release to release
 //! 
 //! ### Dependencies Tree
@@ -535,6 +538,12 @@ extern crate strsim;
 extern crate ansi_term;
 #[cfg(feature = "yaml")]
 extern crate yaml_rust;
+#[cfg(feature = "json")]
+extern crate serde_json;
+#[cfg(feature = "toml")]
+extern crate toml;
+#[cfg(feature = "regex")]
+extern crate regex;
 extern crate unicode_width;
 #[macro_use]
 extern crate bitflags;
@@ -548,11 +557,19 @@ extern crate atty;
 
 #[cfg(feature = "yaml")]
 pub use yaml_rust::YamlLoader;
-pub use args::{Arg, ArgGroup, ArgMatches, ArgSettings, SubCommand, Values, OsValues};
-pub use app::{App, AppSettings};
+#[cfg(feature = "json")]
+pub use serde_json::Value as JsonValue;
+#[cfg(feature = "toml")]
+pub use toml::Value as TomlValue;
+pub use args::{Arg, ArgGroup, ArgMatches, ArgSettings, SubCommand, Values, OsValues, Indices,
+               ValueSource};
+pub use app::{App, AppSettings, Repl};
 pub use fmt::Format;
-pub use errors::{Error, ErrorKind, Result};
+pub use errors::{ContextKind, ContextValue, Error, ErrorKind, Result};
 pub use completions::Shell;
+pub use config::ConfigSource;
+pub use messages::{English, Messages};
+pub use derive::{ClapApp, FromArg, FromArgMatches, FromSubcommand, from_subcommand_matches};
 
 #[macro_use]
 mod macros;
@@ -565,6 +582,13 @@ mod errors;
 mod osstringext;
 mod strext;
 mod completions;
+mod manpage;
+mod docs;
+mod definition;
+mod config;
+mod messages;
+mod derive;
+pub mod validators;
 
 const INTERNAL_ERROR_MSG: &'static str = "Fatal internal error. Please consider filing a bug \
                                           report at https://github.com/kbknapp/clap-rs/issues";
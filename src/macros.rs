@@ -31,6 +31,60 @@ macro_rules! load_yaml {
     );
 }
 
+/// Like [`load_yaml!`], but loads a JSON file to be passed to [`App::from_json`].
+///
+/// # Panics
+///
+/// The JSON file must be properly formatted or this will panic!().
+///
+/// # Examples
+///
+/// ```ignore
+/// # #[macro_use]
+/// # extern crate clap;
+/// # use clap::App;
+/// # fn main() {
+/// let json = load_json!("app.json");
+/// let app = App::from_json(json);
+/// # }
+/// ```
+/// [`load_yaml!`]: ./macro.load_yaml!.html
+/// [`App::from_json`]: ./struct.App.html#method.from_json
+#[cfg(feature = "json")]
+#[macro_export]
+macro_rules! load_json {
+    ($json:expr) => (
+        &include_str!($json).parse::<::clap::JsonValue>().expect("failed to load JSON file")
+    );
+}
+
+/// Like [`load_yaml!`], but loads a TOML file to be passed to [`App::from_toml`].
+///
+/// # Panics
+///
+/// The TOML file must be properly formatted or this will panic!().
+///
+/// # Examples
+///
+/// ```ignore
+/// # #[macro_use]
+/// # extern crate clap;
+/// # use clap::App;
+/// # fn main() {
+/// let toml = load_toml!("app.toml");
+/// let app = App::from_toml(toml);
+/// # }
+/// ```
+/// [`load_yaml!`]: ./macro.load_yaml!.html
+/// [`App::from_toml`]: ./struct.App.html#method.from_toml
+#[cfg(feature = "toml")]
+#[macro_export]
+macro_rules! load_toml {
+    ($toml:expr) => (
+        &include_str!($toml).parse::<::clap::TomlValue>().expect("failed to load TOML file")
+    );
+}
+
 /// Convenience macro getting a typed value `T` where `T` implements [`std::str::FromStr`] from an
 /// argument value. This macro returns a `Result<T,String>` which allows you as the developer to
 /// decide what you'd like to do on a failed parse. There are two types of errors, parse failures
@@ -850,6 +904,16 @@ macro_rules! vec_remove {
     };
 }
 
+// `try!` for an `Option`, returning `None` from the function when there's no value
+macro_rules! some {
+    ($e:expr) => {
+        match $e {
+            Some(v) => v,
+            None => return None,
+        }
+    };
+}
+
 // convenience macro for remove an item from a vec
 macro_rules! vec_remove_all {
     ($vec:expr, $to_rem:expr) => {
@@ -1029,6 +1093,17 @@ macro_rules! longs {
     }};
 }
 
+// The `no-<long>` counterparts of any negatable flags
+macro_rules! negated_longs {
+    ($_self:ident) => {{
+        $_self.flags
+                .iter()
+                .filter(|f| f.b.is_set(::args::ArgSettings::Negatable))
+                .filter_map(|f| f.s.long)
+                .map(|l| format!("no-{}", l))
+    }};
+}
+
 macro_rules! _shorts_longs {
     ($_self:ident, $what:ident) => {{
         $_self.flags
@@ -0,0 +1,248 @@
+// Std
+use std::collections::BTreeMap;
+use std::io::Write;
+
+// Internal
+use app::help::{self, subcommands};
+use app::parser::Parser;
+use app::usage;
+use args::{AnyArg, DispOrder};
+use args::settings::ArgSettings;
+
+pub struct ManGen<'a, 'b>
+    where 'a: 'b
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> ManGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { ManGen { p: p } }
+
+    pub fn generate_to<W: Write>(&self, buf: &mut W) {
+        debugln!("ManGen::generate_to;");
+        let name = self.p.meta.bin_name.as_ref().unwrap_or(&self.p.meta.name);
+        let mut out = String::new();
+
+        out.push_str(&*format!(".TH {} 1 \"\" \"{}\" \"User Commands\"\n",
+                               escape(&*name.to_uppercase()),
+                               escape(&*match self.p.meta.version {
+                                          Some(v) => format!("{} {}", name, v),
+                                          None => name.clone(),
+                                      })));
+
+        out.push_str(".SH NAME\n");
+        out.push_str(&*escape(name));
+        if let Some(about) = self.p.meta.about.or(self.p.meta.long_about) {
+            out.push_str(" \\- ");
+            out.push_str(&*escape(&*about.lines().next().unwrap_or("")));
+        }
+        out.push_str("\n");
+
+        out.push_str(".SH SYNOPSIS\n");
+        synopsis(self.p, &mut out);
+
+        if let Some(about) = self.p.meta.long_about.or(self.p.meta.about) {
+            out.push_str(".SH DESCRIPTION\n");
+            out.push_str(&*text(about));
+        }
+
+        args(self.p, ".SH", &mut out);
+
+        for sc in subcommands(self.p) {
+            subcommand(&sc.p, &mut out);
+        }
+
+        if let Some(more) = self.p.meta.more_help {
+            out.push_str(".SH \"SEE ALSO\"\n");
+            out.push_str(&*text(more));
+        }
+        if let Some(v) = self.p.meta.long_version.or(self.p.meta.version) {
+            out.push_str(".SH VERSION\n");
+            out.push_str(&*text(v));
+        }
+        if let Some(author) = self.p.meta.author {
+            out.push_str(".SH AUTHOR\n");
+            out.push_str(&*text(author));
+        }
+
+        match buf.write_all(out.as_bytes()) {
+            Ok(..) => (),
+            Err(..) => panic!("Failed to write to man page"),
+        }
+    }
+}
+
+// Writes a section for a single subcommand, followed by a section for each of its own subcommands
+fn subcommand(p: &Parser, out: &mut String) {
+    debugln!("ManGen::subcommand: name={}", p.meta.name);
+    let name = p.meta.bin_name.as_ref().unwrap_or(&p.meta.name);
+    out.push_str(&*format!(".SH \"SUBCOMMAND: {}\"\n", escape(name)));
+    if let Some(about) = p.meta.long_about.or(p.meta.about) {
+        out.push_str(&*text(about));
+        out.push_str(".PP\n");
+    }
+    synopsis(p, out);
+    args(p, ".SS", out);
+
+    for sc in subcommands(p) {
+        subcommand(&sc.p, out);
+    }
+}
+
+fn synopsis(p: &Parser, out: &mut String) {
+    let usage = usage::create_usage_no_title(p, &[]);
+    for (i, line) in usage.lines().enumerate() {
+        if i != 0 {
+            out.push_str(".br\n");
+        }
+        out.push_str(&*escape(line.trim()));
+        out.push_str("\n");
+    }
+}
+
+// Writes the OPTIONS and ARGS parts using `head` (either a section or sub-section) as the heading
+fn args(p: &Parser, head: &str, out: &mut String) {
+    let mut ord_m = BTreeMap::new();
+    for f in p.flags().filter(|f| !f.is_set(ArgSettings::Hidden)) {
+        let btm = ord_m.entry(f.disp_ord()).or_insert_with(BTreeMap::new);
+        btm.insert(f.name(), arg(f));
+    }
+    for o in p.opts().filter(|o| !o.is_set(ArgSettings::Hidden)) {
+        let btm = ord_m.entry(o.disp_ord()).or_insert_with(BTreeMap::new);
+        btm.insert(o.name(), arg(o));
+    }
+    if !ord_m.is_empty() {
+        out.push_str(&*format!("{} OPTIONS\n", head));
+        for btm in ord_m.values() {
+            for a in btm.values() {
+                out.push_str(a);
+            }
+        }
+    }
+
+    let mut pos = p.positionals().filter(|p| !p.is_set(ArgSettings::Hidden)).peekable();
+    if pos.peek().is_some() {
+        out.push_str(&*format!("{} ARGS\n", head));
+        for a in pos {
+            out.push_str(&*arg(a));
+        }
+    }
+}
+
+// Renders a single argument as a tagged paragraph
+fn arg<'n, 'e, A>(a: &A) -> String
+    where A: AnyArg<'n, 'e>
+{
+    debugln!("ManGen::arg: name={}", a.name());
+    let mut ret = String::from(".TP\n");
+    if a.has_switch() {
+        let mut sw = vec![];
+        if let Some(s) = a.short() {
+            sw.push(format!("\\fB\\-{}\\fR", escape(&*s.to_string())));
+        }
+        if let Some(l) = a.long() {
+            sw.push(format!("\\fB\\-\\-{}\\fR", escape(l)));
+        }
+        ret.push_str(&*sw.join(", "));
+        if a.takes_value() {
+            ret.push_str(if a.is_set(ArgSettings::RequireEquals) {
+                             "="
+                         } else {
+                             " "
+                         });
+            ret.push_str(&*val_names(a));
+        }
+    } else {
+        ret.push_str(&*val_names(a));
+    }
+    ret.push_str("\n");
+
+    if let Some(h) = a.long_help().or_else(|| a.help()) {
+        ret.push_str(&*text(h));
+    }
+    let mut spec_vals = vec![];
+    if let Some(pv) = a.possible_vals() {
+        if !a.is_set(ArgSettings::HidePossibleValues) {
+            spec_vals.push(format!("[possible values: {}]", pv.join(", ")));
+        }
+    }
+    if let Some(env) = a.env() {
+        spec_vals.push(format!("[env: {}]", env.to_string_lossy()));
+    }
+    if let Some(dv) = a.default_val() {
+        if !a.is_set(ArgSettings::HideDefaultValue) {
+            spec_vals.push(format!("[default: {}]", dv.to_string_lossy()));
+        }
+    }
+    if !spec_vals.is_empty() {
+        ret.push_str(&*escape(&*spec_vals.join(" ")));
+        ret.push_str("\n");
+    }
+    ret
+}
+
+// The value names `--help` shows, in italics
+fn val_names<'n, 'e, A>(a: &A) -> String
+    where A: AnyArg<'n, 'e>
+{
+    match help::val_names(a) {
+        Some((names, multiple)) => {
+            let names: Vec<_> = names.iter().map(|n| format!("\\fI{}\\fR", escape(n))).collect();
+            format!("{}{}", names.join(" "), if multiple { "..." } else { "" })
+        }
+        None => String::new(),
+    }
+}
+
+// Turns free form text (i.e. help messages) into roff, keeping the author's line breaks
+fn text(s: &str) -> String {
+    let mut ret = String::new();
+    let mut prev_text = false;
+    for line in s.replace("{n}", "\n").lines() {
+        if line.trim().is_empty() {
+            ret.push_str(".sp\n");
+            prev_text = false;
+        } else {
+            if prev_text {
+                ret.push_str(".br\n");
+            }
+            ret.push_str(&*escape(line));
+            ret.push_str("\n");
+            prev_text = true;
+        }
+    }
+    ret
+}
+
+// Escapes characters with a special meaning to roff
+fn escape(s: &str) -> String {
+    let s = s.replace('\\', "\\e");
+    if s.starts_with('.') || s.starts_with('\'') {
+        format!("\\&{}", s)
+    } else {
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{escape, text};
+
+    #[test]
+    fn escape_backslash() {
+        assert_eq!(escape("C:\\dir"), "C:\\edir");
+    }
+
+    #[test]
+    fn escape_leading_control_chars() {
+        assert_eq!(escape(".hidden"), "\\&.hidden");
+        assert_eq!(escape("'quoted'"), "\\&'quoted'");
+        assert_eq!(escape("not.hidden"), "not.hidden");
+    }
+
+    #[test]
+    fn text_keeps_line_breaks() {
+        assert_eq!(text("one\n\n.two{n}three"),
+                   "one\n.sp\n\\&.two\n.br\nthree\n");
+    }
+}
@@ -0,0 +1,233 @@
+/// Supplies every heading of the help message and every error message `clap` shows, so they can
+/// be translated without forking `clap`. Set it with [`App::messages`].
+///
+/// Each method has a default which returns the English text, so an implementation only needs to
+/// override what it translates. The arguments are already formatted for display and may contain
+/// color codes, so they should be inserted as they are. Layout such as the usage string, blank
+/// lines and the `Did you mean` line is added around the returned text by `clap` itself.
+///
+/// **NOTE:** The descriptions of the built in help and version flags are set with
+/// [`App::help_message`] and [`App::version_message`] instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, Messages};
+/// struct German;
+///
+/// impl Messages for German {
+///     fn usage_heading(&self) -> &str { "VERWENDUNG:" }
+///     fn error_label(&self) -> &str { "Fehler:" }
+///     fn unknown_argument(&self, arg: &str) -> String {
+///         format!("Das Argument '{}' wurde nicht erwartet", arg)
+///     }
+///     fn more_info(&self, help: &str) -> String {
+///         format!("Weitere Informationen mit {}", help)
+///     }
+/// }
+///
+/// let res = App::new("prog")
+///     .messages(German)
+///     .arg(Arg::with_name("verbose").long("verbose"))
+///     .get_matches_from_safe(vec!["prog", "--leise"]);
+/// assert_eq!(res.unwrap_err().message,
+///            "Fehler: Das Argument '--leise' wurde nicht erwartet\n\n\
+///             VERWENDUNG:\n    prog [FLAGS]\n\n\
+///             Weitere Informationen mit --help");
+/// ```
+/// [`App::messages`]: ./struct.App.html#method.messages
+/// [`App::help_message`]: ./struct.App.html#method.help_message
+/// [`App::version_message`]: ./struct.App.html#method.version_message
+pub trait Messages {
+    /// The heading of the usage string, `USAGE:`
+    fn usage_heading(&self) -> &str { "USAGE:" }
+
+    /// The heading of the flags in the help message, `FLAGS:`
+    fn flags_heading(&self) -> &str { "FLAGS:" }
+
+    /// The heading of the options in the help message, `OPTIONS:`
+    fn options_heading(&self) -> &str { "OPTIONS:" }
+
+    /// The heading of the positional arguments in the help message, `ARGS:`
+    fn args_heading(&self) -> &str { "ARGS:" }
+
+    /// The heading of the subcommands in the help message, `SUBCOMMANDS:`
+    fn subcommands_heading(&self) -> &str { "SUBCOMMANDS:" }
+
+    /// The label of an argument's default value in the help message, as in `[default: 1]`
+    fn default_label(&self) -> &str { "default" }
+
+    /// The label of an argument's environment variable in the help message, as in
+    /// `[env: PORT=80]`
+    fn env_label(&self) -> &str { "env" }
+
+    /// The label of an argument's visible aliases in the help message, as in `[aliases: rm]`
+    fn aliases_label(&self) -> &str { "aliases" }
+
+    /// The label of an argument's possible values in the help message, as in
+    /// `[values: fast, slow]`
+    fn values_label(&self) -> &str { "values" }
+
+    /// The label of an argument's possible keys in the help message, as in
+    /// `[keys: user, email]`
+    fn keys_label(&self) -> &str { "keys" }
+
+    /// The prefix of every error message, `error:`
+    fn error_label(&self) -> &str { "error:" }
+
+    /// The last line of most error messages, pointing at the help flag
+    fn more_info(&self, help: &str) -> String { format!("For more information try {}", help) }
+
+    /// Suggests an argument, value or subcommand which is similar to the one which was used
+    fn did_you_mean(&self, suggestion: &str) -> String { format!("Did you mean {}?", suggestion) }
+
+    /// Names the config file and line an error came from
+    fn in_config(&self, file: &str, line: u64) -> String {
+        format!("in config file '{}', line {}", file, line)
+    }
+
+    /// An argument was used together with one it conflicts with, which is `None` when there's
+    /// more than one
+    fn argument_conflict(&self, arg: &str, other: Option<&str>) -> String {
+        match other {
+            Some(other) => format!("The argument '{}' cannot be used with '{}'", arg, other),
+            None => {
+                format!("The argument '{}' cannot be used with one or more of the other \
+                         specified arguments",
+                        arg)
+            }
+        }
+    }
+
+    /// An argument was given an empty value
+    fn empty_value(&self, arg: &str) -> String {
+        format!("The argument '{}' requires a value but none was supplied", arg)
+    }
+
+    /// A value isn't one of the argument's possible values
+    fn invalid_value(&self, value: &str, arg: &str, valid_values: &str) -> String {
+        format!("'{}' isn't a valid value for '{}'\n\t[{}: {}]",
+                value,
+                arg,
+                self.values_label(),
+                valid_values)
+    }
+
+    /// A key isn't one of the argument's possible keys
+    fn invalid_key(&self, key: &str, arg: &str, valid_keys: &str) -> String {
+        format!("'{}' isn't a valid key for '{}'\n\t[{}: {}]",
+                key,
+                arg,
+                self.keys_label(),
+                valid_keys)
+    }
+
+    /// A value couldn't be parsed or split, for the given reason
+    fn unparsable_value(&self, value: &str, arg: &str, reason: &str) -> String {
+        format!("'{}' isn't a valid value for '{}'\n\t{}", value, arg, reason)
+    }
+
+    /// A value was rejected by a validator or check, for the given reason
+    fn value_validation(&self, arg: Option<&str>, reason: &str) -> String {
+        match arg {
+            Some(arg) => format!("Invalid value for '{}': {}", arg, reason),
+            None => format!("Invalid value: {}", reason),
+        }
+    }
+
+    /// A subcommand doesn't exist
+    fn unrecognized_subcommand(&self, subcmd: &str) -> String {
+        format!("The subcommand '{}' wasn't recognized", subcmd)
+    }
+
+    /// Explains how to pass a value which looks like a subcommand, such as `prog -- instal`
+    fn escape_subcommand(&self, cmd: &str) -> String {
+        format!("If you believe you received this message in error, try re-running with '{}'",
+                cmd)
+    }
+
+    /// Required arguments weren't given, with one argument per line
+    fn missing_required_arguments(&self, args: &str) -> String {
+        format!("The following required arguments were not provided:{}", args)
+    }
+
+    /// A command requires a subcommand but none was given
+    fn missing_subcommand(&self, cmd: &str) -> String {
+        format!("'{}' requires a subcommand, but one was not provided", cmd)
+    }
+
+    /// A subcommand was needed to build a value from an [`ArgMatches`], but none was given
+    ///
+    /// [`ArgMatches`]: ./struct.ArgMatches.html
+    fn subcommand_not_provided(&self) -> String {
+        "A subcommand is required, but one was not provided".to_owned()
+    }
+
+    /// An argument contains invalid UTF-8
+    fn invalid_utf8(&self) -> String {
+        "Invalid UTF-8 was detected in one or more arguments".to_owned()
+    }
+
+    /// An argument was given more values than it accepts
+    fn too_many_values(&self, value: &str, arg: &str) -> String {
+        format!("The value '{}' was provided to '{}', but it wasn't expecting any more values",
+                value,
+                arg)
+    }
+
+    /// An argument was given fewer values than it needs
+    fn too_few_values(&self, arg: &str, min: u64, actual: u64) -> String {
+        format!("The argument '{}' requires at least {} values, but only {} w{} provided",
+                arg,
+                min,
+                actual,
+                if actual > 1 { "ere" } else { "as" })
+    }
+
+    /// An argument was given a different number of values than it needs
+    fn wrong_number_of_values(&self, arg: &str, expected: u64, actual: u64) -> String {
+        format!("The argument '{}' requires {} values, but {} w{} provided",
+                arg,
+                expected,
+                actual,
+                if actual == 1 { "as" } else { "ere" })
+    }
+
+    /// An argument which may only be used once was used more than once
+    fn unexpected_multiple_usage(&self, arg: &str) -> String {
+        format!("The argument '{}' was provided more than once, but cannot be used multiple times",
+                arg)
+    }
+
+    /// An argument doesn't exist, or can't be used in this place
+    fn unknown_argument(&self, arg: &str) -> String {
+        format!("Found argument '{}' which wasn't expected, or isn't valid in this context", arg)
+    }
+
+    /// An argument was looked up with a `value_t!` style macro but doesn't exist
+    fn argument_not_found(&self, arg: &str) -> String {
+        format!("The argument '{}' wasn't found", arg)
+    }
+
+    /// A response file couldn't be expanded, for the given reason
+    fn response_file(&self, path: &str, reason: &str) -> String {
+        format!("Failed to expand response file '{}': {}", path, reason)
+    }
+
+    /// A command line couldn't be split into arguments, because of a problem at the given column
+    fn shell_syntax(&self, column: u64, reason: &str) -> String {
+        format!("Failed to split the command line at column {}: {}", column, reason)
+    }
+
+    /// A config file couldn't be read, for the given reason
+    fn config_unreadable(&self, file: &str, reason: &str) -> String {
+        format!("Failed to read config file '{}': {}", file, reason)
+    }
+}
+
+/// The English [`Messages`] `clap` uses by default
+/// [`Messages`]: ./trait.Messages.html
+#[derive(Debug, Copy, Clone)]
+pub struct English;
+
+impl Messages for English {}
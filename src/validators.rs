@@ -0,0 +1,383 @@
+//! Ready made checks for common kinds of values, for use with [`Arg::check`].
+//!
+//! Each check fails with an [`ErrorKind::ValueValidation`] error describing what was wrong with
+//! the value, and most add a hint to the value's placeholder in the help message so users know
+//! what's expected before they get it wrong, such as `--port <port: 1-65535>`.
+//!
+//! The parsing functions used by the checks, like [`parse_duration`], are also public so the
+//! values can be turned into their final types with [`Arg::value_parser`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use clap::{App, Arg};
+//! use clap::validators;
+//! use std::time::Duration;
+//!
+//! let m = App::new("prog")
+//!     .arg(Arg::with_name("port")
+//!         .long("port")
+//!         .check(validators::range(1, 65535)))
+//!     .arg(Arg::with_name("timeout")
+//!         .long("timeout")
+//!         .value_parser(validators::parse_duration)
+//!         .check(validators::duration()))
+//!     .get_matches_from(vec![
+//!         "prog", "--port", "8080", "--timeout", "1m30s"
+//!     ]);
+//!
+//! assert_eq!(m.value_of("port"), Some("8080"));
+//! assert_eq!(m.get::<Duration>("timeout"), Some(&Duration::from_secs(90)));
+//! ```
+//! [`Arg::check`]: ../struct.Arg.html#method.check
+//! [`Arg::value_parser`]: ../struct.Arg.html#method.value_parser
+//! [`ErrorKind::ValueValidation`]: ../enum.ErrorKind.html#variant.ValueValidation
+//! [`parse_duration`]: ./fn.parse_duration.html
+
+// Std
+use std::ffi::OsStr;
+use std::fmt::{self, Display};
+use std::path::Path;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::Duration;
+use std::u64;
+
+// Third Party
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+/// A check of an argument's values, along with a hint to show in the help message. Created by
+/// the functions in the [`validators`] module, or with [`Check::new`] for checks of your own.
+///
+/// [`validators`]: ./index.html
+/// [`Check::new`]: ./struct.Check.html#method.new
+#[derive(Clone)]
+pub struct Check {
+    hint: Option<String>,
+    f: Rc<Fn(&OsStr) -> Result<(), String>>,
+}
+
+impl Check {
+    /// Creates a check from a function which accepts the value and returns an error message if
+    /// there's something wrong with it. The check has no hint unless one is added with
+    /// [`Check::hint`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// use clap::validators::Check;
+    ///
+    /// let even = Check::new(|v| match v.to_str().and_then(|s| s.parse::<u32>().ok()) {
+    ///     Some(n) if n % 2 == 0 => Ok(()),
+    ///     _ => Err(format!("'{}' isn't an even number", v.to_string_lossy())),
+    /// });
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("n").check(even.hint("even")))
+    ///     .get_matches_from_safe(vec!["prog", "3"]);
+    ///
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::ValueValidation);
+    /// ```
+    /// [`Check::hint`]: ./struct.Check.html#method.hint
+    pub fn new<F>(f: F) -> Self
+        where F: Fn(&OsStr) -> Result<(), String> + 'static
+    {
+        Check {
+            hint: None,
+            f: Rc::new(f),
+        }
+    }
+
+    /// Sets (or replaces) the hint shown after the value's name in the help message, i.e. the
+    /// `1-65535` of `<port: 1-65535>`
+    pub fn hint<S: Into<String>>(mut self, hint: S) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn get_hint(&self) -> Option<&str> { self.hint.as_ref().map(|s| &s[..]) }
+
+    #[doc(hidden)]
+    pub fn run(&self, val: &OsStr) -> Result<(), String> { (self.f)(val) }
+}
+
+impl fmt::Debug for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Check {{ hint: {:?} }}", self.hint)
+    }
+}
+
+// Gets the value as a `&str`, since anything which isn't valid UTF-8 can't be a number, duration,
+// etc.
+fn utf8(val: &OsStr) -> Result<&str, String> {
+    val.to_str().ok_or_else(|| format!("'{}' isn't valid UTF-8", val.to_string_lossy()))
+}
+
+/// Checks the value is a number from `min` to `max` inclusive, with the hint `min-max`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ErrorKind};
+/// # use clap::validators;
+/// let res = App::new("prog")
+///     .arg(Arg::with_name("port")
+///         .long("port")
+///         .check(validators::range(1, 65535)))
+///     .get_matches_from_safe(vec!["prog", "--port", "0"]);
+///
+/// let err = res.unwrap_err();
+/// assert_eq!(err.kind, ErrorKind::ValueValidation);
+/// assert!(err.message.contains("'0' isn't between 1 and 65535"));
+/// ```
+pub fn range<T>(min: T, max: T) -> Check
+    where T: FromStr + PartialOrd + Display + 'static
+{
+    let hint = format!("{}-{}", min, max);
+    Check::new(move |val| {
+            let s = try!(utf8(val));
+            match s.parse::<T>() {
+                Ok(ref n) if *n >= min && *n <= max => Ok(()),
+                Ok(_) => Err(format!("'{}' isn't between {} and {}", s, min, max)),
+                Err(_) => Err(format!("'{}' isn't a number", s)),
+            }
+        })
+        .hint(hint)
+}
+
+/// Checks the value is the path of an existing file, with the hint `file`.
+pub fn existing_file() -> Check {
+    Check::new(|val| {
+            let path = Path::new(val);
+            if path.is_file() {
+                Ok(())
+            } else if path.exists() {
+                Err(format!("'{}' isn't a file", path.display()))
+            } else {
+                Err(format!("'{}' doesn't exist", path.display()))
+            }
+        })
+        .hint("file")
+}
+
+/// Checks the value is the path of an existing directory, with the hint `dir`.
+pub fn existing_dir() -> Check {
+    Check::new(|val| {
+            let path = Path::new(val);
+            if path.is_dir() {
+                Ok(())
+            } else if path.exists() {
+                Err(format!("'{}' isn't a directory", path.display()))
+            } else {
+                Err(format!("'{}' doesn't exist", path.display()))
+            }
+        })
+        .hint("dir")
+}
+
+/// Checks the value contains something other than whitespace. Unlike
+/// [`Arg::empty_values(false)`] this also rejects values such as `" "`. There is no hint.
+///
+/// [`Arg::empty_values(false)`]: ../struct.Arg.html#method.empty_values
+pub fn non_empty() -> Check {
+    Check::new(|val| if val.to_string_lossy().trim().is_empty() {
+                   Err(String::from("the value can't be empty or blank"))
+               } else {
+                   Ok(())
+               })
+}
+
+/// Checks the value matches the regular expression `re`, with the pattern as the hint.
+/// Requires the `regex` feature.
+///
+/// **NOTE:** The pattern isn't anchored automatically, use `^` and `$` to match the whole value.
+#[cfg(feature = "regex")]
+pub fn regex(re: Regex) -> Check {
+    let hint = re.as_str().to_owned();
+    Check::new(move |val| {
+            let s = try!(utf8(val));
+            if re.is_match(s) {
+                Ok(())
+            } else {
+                Err(format!("'{}' doesn't match the pattern '{}'", s, re.as_str()))
+            }
+        })
+        .hint(hint)
+}
+
+/// Checks the value is a duration understood by [`parse_duration`], with the hint `duration`.
+///
+/// [`parse_duration`]: ./fn.parse_duration.html
+pub fn duration() -> Check {
+    Check::new(|val| parse_duration(try!(utf8(val))).map(|_| ())).hint("duration")
+}
+
+/// Checks the value is a size understood by [`parse_byte_size`], with the hint `size`.
+///
+/// [`parse_byte_size`]: ./fn.parse_byte_size.html
+pub fn byte_size() -> Check {
+    Check::new(|val| parse_byte_size(try!(utf8(val))).map(|_| ())).hint("size")
+}
+
+/// Checks the value is a `host:port` pair understood by [`parse_host_port`], with the hint
+/// `host:port`.
+///
+/// [`parse_host_port`]: ./fn.parse_host_port.html
+pub fn host_port() -> Check {
+    Check::new(|val| parse_host_port(try!(utf8(val))).map(|_| ())).hint("host:port")
+}
+
+/// Parses a human readable duration, made up of one or more whole numbers each followed by one
+/// of the units `ms`, `s`, `m`, `h` or `d`, such as `30s`, `5m` or `1h30m`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::validators::parse_duration;
+/// # use std::time::Duration;
+/// assert_eq!(parse_duration("1m30s"), Ok(Duration::from_secs(90)));
+/// assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+/// assert!(parse_duration("30").is_err());
+/// ```
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let err = || format!("'{}' isn't a duration such as 30s, 5m or 1h30m", s);
+    if s.is_empty() {
+        return Err(err());
+    }
+    let mut total: u64 = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_digit(10)).unwrap_or(rest.len());
+        let unit_len = rest[digits..].find(|c: char| c.is_digit(10)).unwrap_or(rest.len() - digits);
+        let n = try!(rest[..digits].parse::<u64>().map_err(|_| err()));
+        let millis = match &rest[digits..digits + unit_len] {
+            "ms" => 1,
+            "s" => 1000,
+            "m" => 60 * 1000,
+            "h" => 60 * 60 * 1000,
+            "d" => 24 * 60 * 60 * 1000,
+            _ => return Err(err()),
+        };
+        total = try!(n.checked_mul(millis)
+            .and_then(|n| total.checked_add(n))
+            .ok_or_else(|| format!("'{}' is too long a duration", s)));
+        rest = &rest[digits + unit_len..];
+    }
+    Ok(Duration::from_millis(total))
+}
+
+/// Parses a number of bytes, optionally followed by a unit. The units `B`, `KB`, `MB`, `GB` and
+/// `TB` are powers of 1000 and `KiB`, `MiB`, `GiB` and `TiB` are powers of 1024, while `K`, `M`,
+/// `G` and `T` are shorthand for the former. Units are case insensitive, may be separated from
+/// the number by a space, and the number may have a fractional part, such as `1.5GiB`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::validators::parse_byte_size;
+/// assert_eq!(parse_byte_size("10MiB"), Ok(10 * 1024 * 1024));
+/// assert_eq!(parse_byte_size("2 kb"), Ok(2000));
+/// assert_eq!(parse_byte_size("512"), Ok(512));
+/// assert!(parse_byte_size("10 parsecs").is_err());
+/// ```
+pub fn parse_byte_size(s: &str) -> Result<u64, String> {
+    let err = || format!("'{}' isn't a size such as 512, 64KiB or 10MB", s);
+    let num_len = s.find(|c: char| !c.is_digit(10) && c != '.').unwrap_or(s.len());
+    let (num, unit) = (&s[..num_len], s[num_len..].trim());
+    let mult: u64 = match &*unit.to_lowercase() {
+        "" | "b" => 1,
+        "k" | "kb" => 1000,
+        "m" | "mb" => 1000 * 1000,
+        "g" | "gb" => 1000 * 1000 * 1000,
+        "t" | "tb" => 1000 * 1000 * 1000 * 1000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => return Err(err()),
+    };
+    let too_big = || format!("'{}' is too large a size", s);
+    if num.contains('.') {
+        let n = try!(num.parse::<f64>().map_err(|_| err()));
+        let bytes = (n * mult as f64).round();
+        if bytes >= u64::MAX as f64 {
+            return Err(too_big());
+        }
+        Ok(bytes as u64)
+    } else {
+        let n = try!(num.parse::<u64>().map_err(|_| err()));
+        n.checked_mul(mult).ok_or_else(too_big)
+    }
+}
+
+/// Parses a `host:port` pair such as `localhost:8080` or `[::1]:22`, where IPv6 addresses must
+/// be surrounded by brackets. The brackets aren't included in the host that's returned.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::validators::parse_host_port;
+/// assert_eq!(parse_host_port("example.com:443"), Ok((String::from("example.com"), 443)));
+/// assert_eq!(parse_host_port("[::1]:22"), Ok((String::from("::1"), 22)));
+/// assert!(parse_host_port("example.com").is_err());
+/// ```
+pub fn parse_host_port(s: &str) -> Result<(String, u16), String> {
+    let err = || format!("'{}' isn't in the form host:port", s);
+    let colon = try!(s.rfind(':').ok_or_else(&err));
+    let (host, port) = (&s[..colon], &s[colon + 1..]);
+    let host = if host.starts_with('[') && host.ends_with(']') {
+        &host[1..host.len() - 1]
+    } else if host.contains(':') {
+        return Err(err());
+    } else {
+        host
+    };
+    if host.is_empty() {
+        return Err(err());
+    }
+    match port.parse::<u16>() {
+        Ok(p) => Ok((host.to_owned(), p)),
+        Err(_) => Err(format!("'{}' isn't a valid port number in '{}'", port, s)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+    use super::{parse_byte_size, parse_duration, parse_host_port};
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("2d1h"), Ok(Duration::from_secs(49 * 60 * 60)));
+        assert_eq!(parse_duration("1s500ms"), Ok(Duration::from_millis(1500)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("5 m").is_err());
+        assert!(parse_duration("5w").is_err());
+        assert!(parse_duration("99999999999999999d").is_err());
+    }
+
+    #[test]
+    fn byte_sizes() {
+        assert_eq!(parse_byte_size("1.5GiB"), Ok(3 << 29));
+        assert_eq!(parse_byte_size("4K"), Ok(4000));
+        assert_eq!(parse_byte_size("1 TiB"), Ok(1 << 40));
+        assert_eq!(parse_byte_size("7b"), Ok(7));
+        assert!(parse_byte_size("").is_err());
+        assert!(parse_byte_size("MiB").is_err());
+        assert!(parse_byte_size("1.2.3").is_err());
+        assert!(parse_byte_size("20000000TB").is_err());
+    }
+
+    #[test]
+    fn host_ports() {
+        assert_eq!(parse_host_port("127.0.0.1:80"), Ok((String::from("127.0.0.1"), 80)));
+        assert!(parse_host_port(":80").is_err());
+        assert!(parse_host_port("::1:80").is_err());
+        assert!(parse_host_port("host:").is_err());
+        assert!(parse_host_port("host:70000").is_err());
+    }
+}
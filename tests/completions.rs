@@ -593,6 +593,191 @@ static BASH_WUS: &'static str = r#"_my_app() {
 complete -F _my_app -o bashdefault -o default my_app
 "#;
 
+static ELVISH: &'static str = r#"
+use str;
+
+set edit:completion:arg-completer[myapp] = {|@words|
+    fn spaces {|n|
+        if (> $n 0) {
+            repeat $n ' ' | str:join ''
+        } else {
+            put ' '
+        }
+    }
+    fn cand {|text desc|
+        edit:complex-candidate $text &display=$text(spaces (- 16 (wcswidth $text)))$desc
+    }
+    var command = 'myapp'
+    for word $words[1..-1] {
+        if (str:has-prefix $word '-') {
+            break
+        }
+        set command = $command';'$word
+    }
+    var values = [&]
+    var prev = $command';'$words[-2]
+    if (has-key $values $prev) {
+        for value $values[$prev] {
+            edit:complex-candidate $value
+        }
+        return
+    }
+    var completions = [
+        &'myapp'= {
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+            cand test 'tests things'
+            cand help 'Prints this message or the help of the given subcommand(s)'
+        }
+        &'myapp;test'= {
+            cand --case 'the case to test'
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+        }
+        &'myapp;help'= {
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+        }
+    ]
+    if (has-key $completions $command) {
+        $completions[$command]
+    }
+}
+"#;
+
+static ELVISH_WUS: &'static str = r#"
+use str;
+
+set edit:completion:arg-completer[my_app] = {|@words|
+    fn spaces {|n|
+        if (> $n 0) {
+            repeat $n ' ' | str:join ''
+        } else {
+            put ' '
+        }
+    }
+    fn cand {|text desc|
+        edit:complex-candidate $text &display=$text(spaces (- 16 (wcswidth $text)))$desc
+    }
+    var command = 'my_app'
+    for word $words[1..-1] {
+        if (str:has-prefix $word '-') {
+            break
+        }
+        set command = $command';'$word
+    }
+    var values = [&]
+    var prev = $command';'$words[-2]
+    if (has-key $values $prev) {
+        for value $values[$prev] {
+            edit:complex-candidate $value
+        }
+        return
+    }
+    var completions = [
+        &'my_app'= {
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+            cand test 'tests things'
+            cand some_cmd 'tests other things'
+            cand help 'Prints this message or the help of the given subcommand(s)'
+        }
+        &'my_app;test'= {
+            cand --case 'the case to test'
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+        }
+        &'my_app;some_cmd'= {
+            cand --config 'the other case to test'
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+        }
+        &'my_app;help'= {
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+        }
+    ]
+    if (has-key $completions $command) {
+        $completions[$command]
+    }
+}
+"#;
+
+static NUSHELL: &'static str = r#"module completions {
+
+  # Tests completions
+  export extern "myapp" [
+    --help(-h)    # Prints help information
+    --version(-V) # Prints version information
+    file?: string # some input file
+  ]
+
+  # tests things
+  export extern "myapp test" [
+    --case: string # the case to test
+    --help(-h)     # Prints help information
+    --version(-V)  # Prints version information
+  ]
+
+  # Prints this message or the help of the given subcommand(s)
+  export extern "myapp help" [
+    --help(-h)    # Prints help information
+    --version(-V) # Prints version information
+  ]
+
+}
+
+use completions *
+"#;
+
+static NUSHELL_WUS: &'static str = r#"module completions {
+
+  # Tests completions
+  export extern "my_app" [
+    --help(-h)    # Prints help information
+    --version(-V) # Prints version information
+    file?: string # some input file
+  ]
+
+  # tests things
+  export extern "my_app test" [
+    --case: string # the case to test
+    --help(-h)     # Prints help information
+    --version(-V)  # Prints version information
+  ]
+
+  # tests other things
+  export extern "my_app some_cmd" [
+    --config: string # the other case to test
+    --help(-h)       # Prints help information
+    --version(-V)    # Prints version information
+  ]
+
+  # Prints this message or the help of the given subcommand(s)
+  export extern "my_app help" [
+    --help(-h)    # Prints help information
+    --version(-V) # Prints version information
+  ]
+
+}
+
+use completions *
+"#;
+
 fn compare(left: &str, right: &str) -> bool {
     let b = left == right;
     if !b {
@@ -713,3 +898,88 @@ fn zsh_with_underscore() {
 
     assert!(compare(&*string, ZSH_WUS));
 }
+
+#[test]
+fn elvish() {
+    let mut app = build_app();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Elvish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, ELVISH));
+}
+
+#[test]
+fn elvish_with_underscore() {
+    let mut app = build_app_with_underscore();
+    let mut buf = vec![];
+    app.gen_completions_to("my_app", Shell::Elvish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, ELVISH_WUS));
+}
+
+#[test]
+fn elvish_possible_values() {
+    let mut app = build_app().arg(Arg::with_name("color")
+        .short("c")
+        .long("color")
+        .takes_value(true)
+        .possible_values(&["always", "never"])
+        .help("when to use 'color'"));
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Elvish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("    var values = [\n        &'myapp;-c'= ['always' 'never']\n        \
+                             &'myapp;--color'= ['always' 'never']\n    ]\n"));
+    assert!(string.contains("            cand --color 'when to use ''color'''\n"));
+}
+
+#[test]
+fn nushell() {
+    let mut app = build_app();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Nushell, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, NUSHELL));
+}
+
+#[test]
+fn nushell_with_underscore() {
+    let mut app = build_app_with_underscore();
+    let mut buf = vec![];
+    app.gen_completions_to("my_app", Shell::Nushell, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, NUSHELL_WUS));
+}
+
+#[test]
+fn nushell_positionals_and_possible_values() {
+    let mut app = App::new("myapp")
+        .arg(Arg::with_name("color")
+            .short("c")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "never"])
+            .help("when to use \"color\""))
+        .arg(Arg::with_name("out-file").required(true))
+        .arg(Arg::with_name("rest").multiple(true))
+        .subcommand(SubCommand::with_name("remote")
+            .alias("rem")
+            .subcommand(SubCommand::with_name("add")
+                .arg(Arg::with_name("kind").possible_values(&["fetch", "push"]))));
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Nushell, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("  def \"nu-complete myapp color\" [] {\n    \
+                             [ \"always\" \"never\" ]\n  }\n"));
+    assert!(string.contains("    --color(-c): string@\"nu-complete myapp color\" \
+                             # when to use \"color\"\n"));
+    assert!(string.contains("    out_file: string\n    ...rest: string\n  ]\n"));
+    assert!(string.contains("  export extern \"myapp remote add\" [\n"));
+    assert!(string.contains("    kind?: string@\"nu-complete myapp rem add kind\"\n"));
+}
@@ -0,0 +1,204 @@
+extern crate clap;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::process;
+
+use clap::{App, Arg, ArgMatches, ConfigSource, ErrorKind, SubCommand, ValueSource};
+
+struct Text(&'static str, &'static str);
+
+impl ConfigSource for Text {
+    fn name(&self) -> String { self.0.to_owned() }
+
+    fn read(&self) -> io::Result<Option<String>> { Ok(Some(self.1.to_owned())) }
+}
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .arg(Arg::with_name("verbose").short("v").long("verbose").multiple(true))
+        .arg(Arg::with_name("debug").long("debug"))
+        .arg(Arg::with_name("color")
+            .long("color")
+            .possible_values(&["always", "auto", "never"])
+            .takes_value(true))
+        .arg(Arg::with_name("level")
+            .long("level")
+            .takes_value(true)
+            .env("CLAP_CONFIG_TEST_LEVEL")
+            .default_value("1")
+            .validator(|v| v.parse::<u8>().map(|_| ()).map_err(|e| e.to_string())))
+        .arg(Arg::with_name("tags").long("tag").takes_value(true).multiple(true))
+        .arg(Arg::with_name("input"))
+        .subcommand(SubCommand::with_name("remote")
+            .arg(Arg::with_name("name").long("name").takes_value(true))
+            .subcommand(SubCommand::with_name("add")
+                .arg(Arg::with_name("url").long("url").takes_value(true))))
+}
+
+fn matches(contents: &'static str, args: &[&str]) -> ArgMatches<'static> {
+    let mut argv = vec!["prog"];
+    argv.extend(args);
+    app().config_source(Text("test.conf", contents)).get_matches_from(argv)
+}
+
+fn err(contents: &'static str, args: &[&str]) -> clap::Error {
+    let mut argv = vec!["prog"];
+    argv.extend(args);
+    app().config_source(Text("test.conf", contents)).get_matches_from_safe(argv).unwrap_err()
+}
+
+#[test]
+fn option_from_config() {
+    let m = matches("# comment\ncolor = never\n", &[]);
+    assert_eq!(m.value_of("color"), Some("never"));
+    assert_eq!(m.value_source("color"), Some(ValueSource::Config));
+    assert_eq!(m.occurrences_of("color"), 0);
+    assert_eq!(m.index_of("color"), None);
+}
+
+#[test]
+fn positional_by_name() {
+    let m = matches("input = 'file.txt'", &[]);
+    assert_eq!(m.value_of("input"), Some("file.txt"));
+}
+
+#[test]
+fn command_line_beats_config() {
+    let m = matches("color = never", &["--color", "always"]);
+    assert_eq!(m.value_of("color"), Some("always"));
+    assert_eq!(m.value_source("color"), Some(ValueSource::CommandLine));
+}
+
+#[test]
+fn env_beats_config_beats_default() {
+    let m = matches("", &[]);
+    assert_eq!(m.value_of("level"), Some("1"));
+    assert_eq!(m.value_source("level"), Some(ValueSource::DefaultValue));
+
+    let m = matches("level = 2", &[]);
+    assert_eq!(m.value_of("level"), Some("2"));
+    assert_eq!(m.value_source("level"), Some(ValueSource::Config));
+
+    env::set_var("CLAP_CONFIG_TEST_LEVEL", "3");
+    let m = matches("level = 2", &[]);
+    env::remove_var("CLAP_CONFIG_TEST_LEVEL");
+    assert_eq!(m.value_of("level"), Some("3"));
+    assert_eq!(m.value_source("level"), Some(ValueSource::Environment));
+}
+
+#[test]
+fn flags_from_config() {
+    let m = matches("verbose = 3\ndebug = true", &[]);
+    assert_eq!(m.occurrences_of("verbose"), 3);
+    assert!(m.is_present("debug"));
+    assert_eq!(m.value_source("debug"), Some(ValueSource::Config));
+
+    let m = matches("debug = false", &[]);
+    assert!(!m.is_present("debug"));
+
+    let m = matches("verbose = 3", &["-v"]);
+    assert_eq!(m.occurrences_of("verbose"), 1);
+}
+
+#[test]
+fn arrays_fill_multiple_values() {
+    let m = matches("tag = [\"a b\", c]", &[]);
+    assert_eq!(m.values_of("tags").unwrap().collect::<Vec<_>>(), &["a b", "c"]);
+}
+
+#[test]
+fn subcommand_sections() {
+    let m = matches("color = auto\n[remote]\nname = origin\n\n[remote.add]\nurl = x.git",
+                    &["remote", "add"]);
+    assert_eq!(m.value_of("color"), Some("auto"));
+    let remote = m.subcommand_matches("remote").unwrap();
+    assert_eq!(remote.value_of("name"), Some("origin"));
+    let add = remote.subcommand_matches("add").unwrap();
+    assert_eq!(add.value_of("url"), Some("x.git"));
+}
+
+#[test]
+fn later_sources_take_precedence() {
+    let m = app()
+        .config_source(Text("system.conf", "color = never\nlevel = 5"))
+        .config_source(Text("user.conf", "color = always"))
+        .get_matches_from(vec!["prog"]);
+    assert_eq!(m.value_of("color"), Some("always"));
+    assert_eq!(m.value_of("level"), Some("5"));
+}
+
+#[test]
+fn invalid_possible_value_names_line() {
+    let e = err("\ncolor = sometimes", &[]);
+    assert_eq!(e.kind, ErrorKind::InvalidValue);
+    assert!(e.message.contains("'sometimes' isn't a valid value for '--color <color>'\n\tin config \
+                                file 'test.conf', line 2\n\t[values: always, auto, never]"));
+}
+
+#[test]
+fn failed_validator_names_line() {
+    let e = err("level = 300", &[]);
+    assert_eq!(e.kind, ErrorKind::ValueValidation);
+    assert!(e.message.contains("in config file 'test.conf', line 1"));
+}
+
+#[test]
+fn too_many_values_names_line() {
+    let e = err("\n\nlevel = [1, 2]", &[]);
+    assert_eq!(e.kind, ErrorKind::TooManyValues);
+    assert!(e.message.contains("in config file 'test.conf', line 3"));
+}
+
+#[test]
+fn invalid_flag_value() {
+    let e = err("debug = yes", &[]);
+    assert_eq!(e.kind, ErrorKind::InvalidValue);
+    assert!(e.message.contains("Invalid value for '--debug' in config file, expected true, \
+                                false or a number\n\tin config file 'test.conf', line 1"));
+}
+
+#[test]
+fn unknown_key() {
+    let e = err("[remote]\nurl = x", &[]);
+    assert_eq!(e.kind, ErrorKind::UnknownArgument);
+    assert!(e.message.contains("Found config key 'url' which doesn't match any argument\n\tin \
+                                config file 'test.conf', line 2"));
+}
+
+#[test]
+fn unknown_section() {
+    let e = err("[nope]\nurl = x", &[]);
+    assert_eq!(e.kind, ErrorKind::UnrecognizedSubcommand);
+    assert!(e.message.contains("[nope]"));
+}
+
+#[test]
+fn syntax_error() {
+    let e = err("color = auto\ncolor\n", &[]);
+    assert_eq!(e.kind, ErrorKind::InvalidValue);
+    assert!(e.message.contains("Expected a 'key = value' pair\n\tin config file 'test.conf', \
+                                line 2"));
+}
+
+#[test]
+fn required_satisfied_by_config() {
+    let res = App::new("prog")
+        .arg(Arg::with_name("out").long("out").takes_value(true).required(true))
+        .config_source(Text("test.conf", "out = a.txt"))
+        .get_matches_from_safe(vec!["prog"]);
+    assert_eq!(res.unwrap().value_of("out"), Some("a.txt"));
+}
+
+#[test]
+fn config_file() {
+    let path = env::temp_dir().join(format!("clap-config-{}.conf", process::id()));
+    File::create(&path).unwrap().write_all(b"color = never\n").unwrap();
+    let m = app()
+        .config_file(&*path)
+        .config_file("/does/not/exist.conf")
+        .get_matches_from(vec!["prog"]);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(m.value_of("color"), Some("never"));
+}
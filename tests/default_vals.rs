@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, ErrorKind};
+use clap::{App, Arg, ErrorKind, SubCommand, ValueSource};
 
 #[test]
 fn opts() {
@@ -431,3 +431,60 @@ fn conditional_reqs_pass() {
     assert_eq!(m.value_of("output"), Some("other"));
     assert_eq!(m.value_of("input"), Some("some"));
 }
+
+// Value Sources
+
+#[test]
+fn value_source_default() {
+    let m = App::new("df")
+        .arg(Arg::from_usage("-o [opt] 'some opt'")
+            .default_value("default"))
+        .get_matches_from(vec![""]);
+    assert_eq!(m.value_source("o"), Some(ValueSource::DefaultValue));
+}
+
+#[test]
+fn value_source_command_line() {
+    let m = App::new("df")
+        .arg(Arg::from_usage("-o [opt] 'some opt'")
+            .default_value("default"))
+        .arg(Arg::from_usage("-f 'some flag'"))
+        .arg(Arg::from_usage("[arg] 'some arg'"))
+        .get_matches_from(vec!["", "-o", "value", "-f", "pos"]);
+    assert_eq!(m.value_source("o"), Some(ValueSource::CommandLine));
+    assert_eq!(m.value_source("f"), Some(ValueSource::CommandLine));
+    assert_eq!(m.value_source("arg"), Some(ValueSource::CommandLine));
+}
+
+#[test]
+fn value_source_conditional_default() {
+    let m = App::new("df")
+        .arg(Arg::from_usage("--opt [FILE] 'some arg'"))
+        .arg(Arg::from_usage("[arg] 'some arg'")
+            .default_value("default")
+            .default_value_if("opt", Some("some"), "cond"))
+        .get_matches_from(vec!["", "--opt", "some"]);
+    assert_eq!(m.value_of("arg"), Some("cond"));
+    assert_eq!(m.value_source("arg"), Some(ValueSource::ConditionalDefault));
+}
+
+#[test]
+fn value_source_not_present() {
+    let m = App::new("df")
+        .arg(Arg::from_usage("--opt [FILE] 'some arg'"))
+        .get_matches_from(vec![""]);
+    assert_eq!(m.value_source("opt"), None);
+}
+
+#[test]
+fn value_source_propagated_global() {
+    let m = App::new("df")
+        .arg(Arg::from_usage("--opt [FILE] 'some arg'")
+            .global(true)
+            .default_value("default"))
+        .subcommand(SubCommand::with_name("sub"))
+        .get_matches_from(vec!["", "sub"]);
+    assert_eq!(m.value_source("opt"), Some(ValueSource::DefaultValue));
+    let sub_m = m.subcommand_matches("sub").unwrap();
+    assert_eq!(sub_m.value_source("opt"), Some(ValueSource::DefaultValue));
+}
@@ -0,0 +1,184 @@
+#[macro_use]
+extern crate clap;
+
+use clap::{App, AppSettings, Arg, ArgGroup, SubCommand};
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .version("1.0")
+        .author("Kevin K.")
+        .about("does \"things\"\nwell")
+        .setting(AppSettings::ArgRequiredElseHelp)
+        .global_setting(AppSettings::ColoredHelp)
+        .arg(Arg::with_name("verbose")
+            .short("v")
+            .long("verbose")
+            .multiple(true)
+            .global(true)
+            .help("be loud"))
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .visible_alias("m0de")
+            .possible_values(&["fast", "slow"])
+            .default_value_if("verbose", None, "slow")
+            .required_if("input", "-"))
+        .arg(Arg::with_name("define")
+            .short("D")
+            .multiple(true)
+            .number_of_values(1)
+            .key_value_delimiter(":")
+            .group("extra"))
+        .arg(Arg::with_name("list")
+            .long("list")
+            .value_delimiter(";")
+            .empty_values(false)
+            .secret(true))
+        .arg(Arg::with_name("input").required(true).requires_if("-", "mode").index(1))
+        .group(ArgGroup::with_name("extra").arg("list").required(true))
+        .subcommand(SubCommand::with_name("remote")
+            .alias("rem")
+            .display_order(2)
+            .arg(Arg::with_name("name").conflicts_with("url"))
+            .arg(Arg::with_name("url").long("url").takes_value(true)))
+}
+
+#[test]
+fn yaml() {
+    assert_eq!(app().to_yaml(),
+               r#"name: "prog"
+version: "1.0"
+author: "Kevin K."
+about: "does \"things\"\nwell"
+settings: ["ArgRequiredElseHelp"]
+global_settings: ["ColoredHelp"]
+args:
+  - verbose:
+      short: "v"
+      long: "verbose"
+      help: "be loud"
+      multiple: true
+      global: true
+  - mode:
+      long: "mode"
+      visible_aliases: ["m0de"]
+      required_ifs: [["input", "-"]]
+      takes_value: true
+      default_value_ifs: [["verbose", null, "slow"]]
+      possible_values: ["fast", "slow"]
+  - define:
+      short: "D"
+      takes_value: true
+      multiple: true
+      groups: ["extra"]
+      number_of_values: 1
+      key_value: true
+      key_value_delimiter: ":"
+  - list:
+      long: "list"
+      takes_value: true
+      secret: true
+      empty_values: false
+      value_delimiter: ";"
+  - input:
+      index: 1
+      required: true
+      requires_ifs: [["-", "mode"]]
+subcommands:
+  - remote:
+      aliases: ["rem"]
+      display_order: 2
+      args:
+        - url:
+            long: "url"
+            takes_value: true
+        - name:
+            index: 1
+            conflicts_with: ["url"]
+groups:
+  - extra:
+      args: ["list"]
+      required: true
+"#);
+}
+
+#[test]
+fn json() {
+    let json = App::new("prog")
+        .subcommand(SubCommand::with_name("init").about("starts"))
+        .to_json();
+    assert_eq!(json,
+               r#"{
+  "name": "prog",
+  "subcommands": [
+    {
+      "init": {
+        "about": "starts"
+      }
+    }
+  ]
+}
+"#);
+}
+
+#[test]
+fn generated_args_left_out() {
+    let mut app = App::new("prog").subcommand(SubCommand::with_name("init"));
+    let _ = app.get_matches_from_safe_borrow(vec!["prog", "init"]);
+    assert_eq!(app.to_yaml(),
+               r#"name: "prog"
+bin_name: "prog"
+subcommands:
+  - init:
+      bin_name: "prog init"
+"#);
+}
+
+#[test]
+fn odd_names_are_quoted() {
+    let yaml = App::new("prog").arg(Arg::with_name("2nd").long("second")).to_yaml();
+    assert!(yaml.contains("\n  - \"2nd\":\n      long: \"second\"\n"));
+}
+
+#[cfg(feature = "yaml")]
+mod round_trip {
+    use clap::{App, YamlLoader};
+
+    fn help(mut app: App) -> String {
+        let mut buf = Vec::new();
+        app.write_help(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    fn round_trip(app: App) {
+        let yaml = app.to_yaml();
+        let docs = YamlLoader::load_from_str(&*yaml).expect("to_yaml wrote invalid YAML");
+        let loaded = App::from_yaml(&docs[0]);
+        assert_eq!(loaded.to_yaml(), yaml);
+        assert_eq!(help(loaded.clone()), help(app.clone()));
+
+        // JSON is valid YAML as well
+        let docs = YamlLoader::load_from_str(&*app.to_json()).unwrap();
+        assert_eq!(App::from_yaml(&docs[0]).to_yaml(), yaml);
+    }
+
+    #[test]
+    fn builder() { round_trip(super::app()); }
+
+    #[test]
+    fn app_yml() { round_trip(App::from_yaml(load_yaml!("app.yml"))); }
+
+    #[test]
+    fn same_errors() {
+        let yaml = super::app().to_yaml();
+        let docs = YamlLoader::load_from_str(&*yaml).unwrap();
+        for args in &[vec!["prog", "-"], vec!["prog", "x", "--list", ""], vec!["prog", "x"]] {
+            let expected = super::app().get_matches_from_safe(args.clone()).unwrap_err();
+            let actual = App::from_yaml(&docs[0]).get_matches_from_safe(args.clone()).unwrap_err();
+            assert_eq!(actual.kind, expected.kind);
+            assert_eq!(actual.message, expected.message);
+        }
+        let m = App::from_yaml(&docs[0]).get_matches_from(vec!["prog", "x", "-v", "-Da:1"]);
+        assert_eq!(m.value_of("mode"), Some("slow"));
+        assert_eq!(m.values_of_map("define").unwrap()["a"], "1");
+    }
+}
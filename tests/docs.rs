@@ -0,0 +1,225 @@
+extern crate regex;
+extern crate clap;
+
+use clap::{App, AppSettings, Arg, SubCommand};
+use regex::Regex;
+
+static MARKDOWN: &'static str = r##"<a name="myapp"></a>
+# myapp
+
+Tests docs
+
+```text
+myapp [FLAGS] [OPTIONS] <file> [SUBCOMMAND]
+```
+
+| Short | Long | Values | Default | Possible values | Env | Required | Description |
+|-------|------|--------|---------|-----------------|-----|----------|-------------|
+| `-h` | `--help` |  |  |  |  |  | Prints help information |
+| `-V` | `--version` |  |  |  |  |  | Prints version information |
+| `-v` | `--verbose` |  |  |  |  |  | Sets the level<br>of verbosity |
+|  | `--mode` | `<MODE>` | `fast` | `fast`, `slow` | `MYAPP_MODE` |  | the mode to use |
+|  |  | `<file>` |  |  |  | yes | some input file |
+
+**Subcommands:**
+
+* [`myapp test`](#myapp-test): tests things
+
+<a name="myapp-test"></a>
+## myapp test
+
+tests things
+
+```text
+myapp test [OPTIONS]
+```
+
+| Short | Long | Values | Default | Possible values | Env | Required | Description |
+|-------|------|--------|---------|-----------------|-----|----------|-------------|
+| `-h` | `--help` |  |  |  |  |  | Prints help information |
+| `-V` | `--version` |  |  |  |  |  | Prints version information |
+|  | `--case` | `<case>` |  |  |  |  | the case to test |
+
+"##;
+
+static HTML: &'static str = r##"<section id="myapp">
+<h1>myapp</h1>
+<p>Tests docs</p>
+<pre><code>myapp [FLAGS] [OPTIONS] &lt;file&gt; [SUBCOMMAND]</code></pre>
+<table>
+<thead><tr><th>Short</th><th>Long</th><th>Values</th><th>Default</th><th>Possible values</th><th>Env</th><th>Required</th><th>Description</th></tr></thead>
+<tbody>
+<tr><td><code>-h</code></td><td><code>--help</code></td><td></td><td></td><td></td><td></td><td></td><td>Prints help information</td></tr>
+<tr><td><code>-V</code></td><td><code>--version</code></td><td></td><td></td><td></td><td></td><td></td><td>Prints version information</td></tr>
+<tr><td><code>-v</code></td><td><code>--verbose</code></td><td></td><td></td><td></td><td></td><td></td><td>Sets the level<br>of verbosity</td></tr>
+<tr><td></td><td><code>--mode</code></td><td><code>&lt;MODE&gt;</code></td><td><code>fast</code></td><td><code>fast</code>, <code>slow</code></td><td><code>MYAPP_MODE</code></td><td></td><td>the mode to use</td></tr>
+<tr><td></td><td></td><td><code>&lt;file&gt;</code></td><td></td><td></td><td></td><td>yes</td><td>some input file</td></tr>
+</tbody>
+</table>
+<p><strong>Subcommands:</strong></p>
+<ul>
+<li><a href="#myapp-test"><code>myapp test</code></a>: tests things</li>
+</ul>
+</section>
+<section id="myapp-test">
+<h2>myapp test</h2>
+<p>tests things</p>
+<pre><code>myapp test [OPTIONS]</code></pre>
+<table>
+<thead><tr><th>Short</th><th>Long</th><th>Values</th><th>Default</th><th>Possible values</th><th>Env</th><th>Required</th><th>Description</th></tr></thead>
+<tbody>
+<tr><td><code>-h</code></td><td><code>--help</code></td><td></td><td></td><td></td><td></td><td></td><td>Prints help information</td></tr>
+<tr><td><code>-V</code></td><td><code>--version</code></td><td></td><td></td><td></td><td></td><td></td><td>Prints version information</td></tr>
+<tr><td></td><td><code>--case</code></td><td><code>&lt;case&gt;</code></td><td></td><td></td><td></td><td></td><td>the case to test</td></tr>
+</tbody>
+</table>
+</section>
+"##;
+
+fn compare(left: &str, right: &str) -> bool {
+    let b = left == right;
+    if !b {
+        let re = Regex::new(" ").unwrap();
+        println!("");
+        println!("--> left");
+        println!("{}", re.replace_all(left, "\u{2022}"));
+        println!("--> right");
+        println!("{}", re.replace_all(right, "\u{2022}"));
+        println!("--")
+    }
+    b
+}
+
+fn build_app() -> App<'static, 'static> {
+    App::new("myapp")
+        .about("Tests docs")
+        .setting(AppSettings::DisableHelpSubcommand)
+        .arg(Arg::with_name("file")
+            .required(true)
+            .help("some input file"))
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .value_name("MODE")
+            .possible_values(&["fast", "slow"])
+            .default_value("fast")
+            .env("MYAPP_MODE")
+            .help("the mode to use"))
+        .arg(Arg::with_name("verbose")
+            .short("v")
+            .long("verbose")
+            .help("Sets verbosity")
+            .long_help("Sets the level\nof verbosity"))
+        .arg(Arg::with_name("secret")
+            .long("secret")
+            .hidden(true)
+            .help("never shown"))
+        .subcommand(SubCommand::with_name("test")
+            .about("tests things")
+            .arg(Arg::with_name("case")
+                .long("case")
+                .takes_value(true)
+                .help("the case to test")))
+        .subcommand(SubCommand::with_name("internal")
+            .setting(AppSettings::Hidden)
+            .about("never shown"))
+}
+
+fn markdown(mut app: App) -> String {
+    let mut buf = vec![];
+    app.write_markdown(&mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+fn html(mut app: App) -> String {
+    let mut buf = vec![];
+    app.write_html(&mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn markdown_full() {
+    assert!(compare(&*markdown(build_app()), MARKDOWN));
+}
+
+#[test]
+fn html_full() {
+    assert!(compare(&*html(build_app()), HTML));
+}
+
+#[test]
+fn docs_nested_subcommand_anchors() {
+    let app = || {
+        App::new("myapp")
+            .setting(AppSettings::DisableHelpSubcommand)
+            .subcommand(SubCommand::with_name("remote")
+                .setting(AppSettings::DisableHelpSubcommand)
+                .subcommand(SubCommand::with_name("add").about("adds a remote")))
+    };
+    let md = markdown(app());
+    assert!(md.contains("* [`myapp remote add`](#myapp-remote-add): adds a remote\n"));
+    assert!(md.contains("<a name=\"myapp-remote-add\"></a>\n### myapp remote add\n"));
+
+    let html = html(app());
+    assert!(html.contains("<section id=\"myapp-remote-add\">\n<h3>myapp remote add</h3>\n"));
+}
+
+#[test]
+fn docs_hide_default_and_possible_values() {
+    let app = App::new("myapp")
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .possible_values(&["fast", "slow"])
+            .default_value("fast")
+            .hide_default_value(true)
+            .hide_possible_values(true));
+    let md = markdown(app);
+    assert!(md.contains("|  | `--mode` | `<mode>` |  |  |  |  |  |\n"));
+}
+
+#[test]
+fn docs_escape_markup() {
+    let app = App::new("myapp")
+        .arg(Arg::with_name("pipe")
+            .long("pipe")
+            .help("reads <a|b> & more"));
+    assert!(markdown(app.clone()).contains("| reads &lt;a\\|b&gt; &amp; more |"));
+    assert!(html(app).contains("<td>reads &lt;a|b&gt; &amp; more</td>"));
+}
+
+#[test]
+fn docs_unified_help_message() {
+    let app = App::new("myapp")
+        .setting(AppSettings::UnifiedHelpMessage)
+        .arg(Arg::with_name("aopt").long("aopt").takes_value(true))
+        .arg(Arg::with_name("zflag").long("zflag"));
+    let md = markdown(app);
+    let opt = md.find("--aopt").unwrap();
+    let flag = md.find("--zflag").unwrap();
+    assert!(opt < flag);
+}
+
+#[test]
+fn docs_derive_display_order() {
+    let app = App::new("myapp")
+        .setting(AppSettings::DeriveDisplayOrder)
+        .arg(Arg::with_name("zflag").long("zflag"))
+        .arg(Arg::with_name("aflag").long("aflag"));
+    let md = markdown(app);
+    assert!(md.find("--zflag").unwrap() < md.find("--aflag").unwrap());
+}
+
+#[test]
+fn docs_value_names_match_help() {
+    let mut app = App::new("myapp")
+        .arg(Arg::with_name("pair").long("pair").value_names(&["key", "val"]))
+        .arg(Arg::with_name("files").multiple(true));
+    let mut help = vec![];
+    app.write_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.contains("--pair <key> <val>"));
+    assert!(help.contains("<files>..."));
+
+    let md = markdown(app);
+    assert!(md.contains("| `--pair` | `<key> <val>` |"));
+    assert!(md.contains("| `<files>...` |"));
+}
@@ -0,0 +1,150 @@
+extern crate clap;
+
+use clap::{App, AppSettings, Arg, ErrorKind, SubCommand, Shell};
+
+fn branches(_: &str) -> Vec<String> { vec!["master".into(), "feature".into()] }
+
+fn hosts(cur: &str) -> Vec<String> { vec![format!("{}.local", cur), "db".into()] }
+
+fn build_app() -> App<'static, 'static> {
+    App::new("myapp")
+        .arg(Arg::with_name("branch")
+            .short("b")
+            .long("branch")
+            .completer(branches))
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "auto", "never"]))
+        .arg(Arg::with_name("verbose").short("v"))
+        .subcommand(SubCommand::with_name("connect")
+            .arg(Arg::with_name("port")
+                .short("p")
+                .takes_value(true))
+            .arg(Arg::with_name("host").completer(hosts))
+            .arg(Arg::with_name("db").possible_values(&["users", "orders"])))
+}
+
+fn complete(words: &[&str]) -> Vec<String> {
+    let mut argv = vec!["myapp", "__complete"];
+    argv.extend(words);
+    let err = build_app().get_matches_from_safe(argv).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::CompletionsDisplayed);
+    if err.message.is_empty() {
+        return vec![];
+    }
+    err.message.lines().map(String::from).collect()
+}
+
+#[test]
+fn complete_opt_value() {
+    assert_eq!(complete(&["--branch", ""]), &["master", "feature"]);
+    assert_eq!(complete(&["-v", "-b", "f"]), &["master", "feature"]);
+}
+
+#[test]
+fn complete_opt_value_combined_short() {
+    assert_eq!(complete(&["-vb", ""]), &["master", "feature"]);
+}
+
+#[test]
+fn complete_opt_value_after_attached_value() {
+    // -bfoo already has its value, so the next word is a subcommand
+    assert_eq!(complete(&["-bfoo", ""]), &["connect", "help"]);
+}
+
+#[test]
+fn complete_opt_value_with_equals() {
+    assert_eq!(complete(&["--branch=m"]), &["--branch=master", "--branch=feature"]);
+}
+
+#[test]
+fn complete_possible_values() {
+    assert_eq!(complete(&["--color", "a"]), &["always", "auto"]);
+    assert_eq!(complete(&["--color=n"]), &["--color=never"]);
+}
+
+#[test]
+fn complete_arg_names() {
+    assert_eq!(complete(&["--b"]), &["--branch"]);
+    assert_eq!(complete(&["-"]),
+               &["--branch", "--color", "--help", "--version", "-V", "-b", "-h", "-v"]);
+}
+
+#[test]
+fn complete_subcommand_names() {
+    assert_eq!(complete(&["c"]), &["connect"]);
+    assert_eq!(complete(&[""]), &["connect", "help"]);
+}
+
+#[test]
+fn complete_positional_in_subcommand() {
+    assert_eq!(complete(&["connect", "web"]), &["web.local", "db"]);
+    assert_eq!(complete(&["-v", "connect", "-p", "80", ""]), &[".local", "db"]);
+}
+
+#[test]
+fn complete_second_positional() {
+    assert_eq!(complete(&["connect", "db", "o"]), &["orders"]);
+}
+
+#[test]
+fn complete_trailing_values() {
+    // After `--` everything is a positional value, even things that look like flags
+    assert_eq!(complete(&["connect", "--", "-x"]), &["-x.local", "db"]);
+}
+
+#[test]
+fn complete_nothing_typed() {
+    assert_eq!(complete(&[]), &["connect", "help"]);
+}
+
+#[test]
+fn complete_not_intercepted_without_completers() {
+    let m = App::new("myapp")
+        .setting(AppSettings::TrailingVarArg)
+        .arg(Arg::with_name("args").multiple(true))
+        .get_matches_from_safe(vec!["myapp", "__complete", "foo"])
+        .unwrap();
+    assert_eq!(m.values_of("args").unwrap().collect::<Vec<_>>(),
+               &["__complete", "foo"]);
+}
+
+#[test]
+fn complete_only_first_arg() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("branch")
+            .long("branch")
+            .completer(branches))
+        .arg(Arg::with_name("args").multiple(true))
+        .get_matches_from_safe(vec!["myapp", "foo", "__complete"])
+        .unwrap();
+    assert_eq!(m.values_of("args").unwrap().collect::<Vec<_>>(),
+               &["foo", "__complete"]);
+}
+
+#[test]
+fn bash_calls_back() {
+    let mut buf = vec![];
+    build_app().gen_completions_to("myapp", Shell::Bash, &mut buf);
+    let script = String::from_utf8(buf).unwrap();
+
+    let callback = "$(compgen -W \"$(\"${COMP_WORDS[0]}\" __complete \
+                    \"${COMP_WORDS[@]:1:${COMP_CWORD}}\" 2>/dev/null)\" -- \"${cur}\")";
+    assert!(script.contains(&*format!("--branch)\n                    COMPREPLY=({})", callback)));
+    // `connect` has a positional with a completer, so the first word isn't completed statically
+    assert!(script.contains("if [[ ${cur} == -* ]] ; then"));
+    assert!(script.contains("if [[ ${cur} == -* || ${COMP_CWORD} -eq 1 ]] ; then"));
+}
+
+#[test]
+fn zsh_calls_back() {
+    let mut buf = vec![];
+    build_app().gen_completions_to("myapp", Shell::Zsh, &mut buf);
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.contains("\"--branch+[]: :_myapp__complete\" \\"));
+    assert!(script.contains("_myapp__complete() {"));
+    assert!(script.contains("_describe -t commands 'myapp connect commands' commands \"$@\"\n    \
+                             _myapp__complete\n}"));
+}
@@ -0,0 +1,327 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use std::env;
+
+use clap::{App, Arg, ErrorKind, ValueSource};
+
+static ENV_HELP: &'static str = "prog 0.1
+
+USAGE:
+    prog [OPTIONS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --level <level>    Sets the log level [env: CLP_TEST_ENV_HELP=warn]  [default: info]";
+
+static HIDDEN_ENV_HELP: &'static str = "prog 0.1
+
+USAGE:
+    prog [OPTIONS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --password <password>    The password [env: CLP_TEST_ENV_SECRET]
+        --token <token>          The API token [env: CLP_TEST_ENV_HIDDEN]";
+
+#[test]
+fn env() {
+    env::set_var("CLP_TEST_ENV", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'").env("CLP_TEST_ENV"))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.occurrences_of("arg"), 0);
+    assert_eq!(m.value_of("arg").unwrap(), "env");
+    assert_eq!(m.value_source("arg"), Some(ValueSource::Environment));
+}
+
+#[test]
+fn env_os() {
+    use std::ffi::OsStr;
+    env::set_var("CLP_TEST_ENV_OS", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'").env_os(OsStr::new("CLP_TEST_ENV_OS")))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.value_of("arg").unwrap(), "env");
+}
+
+#[test]
+fn no_env() {
+    // All the other tests use the presence of the Environment variable...
+    // we need another variable just in case one of the others is running at the same time...
+    env::remove_var("CLP_TEST_ENV_NONE");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'").env("CLP_TEST_ENV_NONE"))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(!m.is_present("arg"));
+    assert_eq!(m.occurrences_of("arg"), 0);
+    assert_eq!(m.value_of("arg"), None);
+}
+
+#[test]
+fn with_default() {
+    env::set_var("CLP_TEST_ENV_DEF", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'")
+                 .env("CLP_TEST_ENV_DEF")
+                 .default_value("default"))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.occurrences_of("arg"), 0);
+    assert_eq!(m.value_of("arg").unwrap(), "env");
+}
+
+#[test]
+fn missing_env_uses_default() {
+    env::remove_var("CLP_TEST_ENV_MISSING_DEF");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'")
+                 .env("CLP_TEST_ENV_MISSING_DEF")
+                 .default_value("default"))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert_eq!(m.value_of("arg").unwrap(), "default");
+    assert_eq!(m.value_source("arg"), Some(ValueSource::DefaultValue));
+}
+
+#[test]
+fn env_beats_default_value_if() {
+    env::set_var("CLP_TEST_ENV_DEF_IF", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("--flag 'some flag'"))
+        .arg(Arg::from_usage("[arg] 'some opt'")
+                 .env("CLP_TEST_ENV_DEF_IF")
+                 .default_value_if("flag", None, "default"))
+        .get_matches_from_safe(vec!["", "--flag"]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert_eq!(m.value_of("arg").unwrap(), "env");
+}
+
+#[test]
+fn opt_user_override() {
+    env::set_var("CLP_TEST_ENV_OR", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("--arg [FILE] 'some arg'").env("CLP_TEST_ENV_OR"))
+        .get_matches_from_safe(vec!["", "--arg", "opt"]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.occurrences_of("arg"), 1);
+    assert_eq!(m.value_of("arg").unwrap(), "opt");
+    assert_eq!(m.value_source("arg"), Some(ValueSource::CommandLine));
+}
+
+#[test]
+fn positionals() {
+    env::set_var("CLP_TEST_ENV_P", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'").env("CLP_TEST_ENV_P"))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.value_of("arg").unwrap(), "env");
+}
+
+#[test]
+fn positionals_user_override() {
+    env::set_var("CLP_TEST_ENV_POR", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'").env("CLP_TEST_ENV_POR"))
+        .get_matches_from_safe(vec!["", "opt"]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.occurrences_of("arg"), 1);
+    assert_eq!(m.value_of("arg").unwrap(), "opt");
+}
+
+#[test]
+fn multiple_one() {
+    env::set_var("CLP_TEST_ENV_MO", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'")
+                 .env("CLP_TEST_ENV_MO")
+                 .use_delimiter(true)
+                 .multiple(true))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.values_of("arg").unwrap().collect::<Vec<_>>(), vec!["env"]);
+}
+
+#[test]
+fn multiple_three() {
+    env::set_var("CLP_TEST_ENV_MULTI1", "env1,env2,env3");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'")
+                 .env("CLP_TEST_ENV_MULTI1")
+                 .use_delimiter(true)
+                 .multiple(true))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.occurrences_of("arg"), 0);
+    assert_eq!(m.values_of("arg").unwrap().collect::<Vec<_>>(),
+               vec!["env1", "env2", "env3"]);
+}
+
+#[test]
+fn required_satisfied_by_env() {
+    env::set_var("CLP_TEST_ENV_REQ", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("--arg <FILE> 'some arg'").env("CLP_TEST_ENV_REQ"))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    assert_eq!(r.unwrap().value_of("arg").unwrap(), "env");
+}
+
+#[test]
+fn possible_value() {
+    env::set_var("CLP_TEST_ENV_PV", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'")
+                 .env("CLP_TEST_ENV_PV")
+                 .possible_value("env"))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.value_of("arg").unwrap(), "env");
+}
+
+#[test]
+fn not_possible_value() {
+    env::set_var("CLP_TEST_ENV_NPV", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'")
+                 .env("CLP_TEST_ENV_NPV")
+                 .possible_value("never"))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_err());
+    assert_eq!(r.unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[test]
+fn validator() {
+    env::set_var("CLP_TEST_ENV_VDOR", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'")
+                 .env("CLP_TEST_ENV_VDOR")
+                 .validator(|s| if s == "env" {
+                                Ok(())
+                            } else {
+                                Err("not equal".to_string())
+                            }))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert!(m.is_present("arg"));
+    assert_eq!(m.value_of("arg").unwrap(), "env");
+}
+
+#[test]
+fn validator_invalid() {
+    env::set_var("CLP_TEST_ENV_IV", "env");
+
+    let r = App::new("df")
+        .arg(Arg::from_usage("[arg] 'some opt'")
+                 .env("CLP_TEST_ENV_IV")
+                 .validator(|s| if s != "env" {
+                                Ok(())
+                            } else {
+                                Err("is equal".to_string())
+                            }))
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_err());
+    assert_eq!(r.unwrap_err().kind, ErrorKind::ValueValidation);
+}
+
+#[test]
+fn env_in_help() {
+    env::set_var("CLP_TEST_ENV_HELP", "warn");
+
+    let app = App::new("prog")
+        .version("0.1")
+        .set_term_width(120)
+        .arg(Arg::with_name("level")
+                 .long("level")
+                 .help("Sets the log level")
+                 .env("CLP_TEST_ENV_HELP")
+                 .default_value("info"));
+    assert!(test::compare_output(app, "prog --help", ENV_HELP, false));
+}
+
+#[test]
+fn env_values_hidden_in_help() {
+    env::set_var("CLP_TEST_ENV_HIDDEN", "abc123");
+    env::set_var("CLP_TEST_ENV_SECRET", "hunter2");
+
+    let app = App::new("prog")
+        .version("0.1")
+        .set_term_width(120)
+        .arg(Arg::with_name("token")
+                 .long("token")
+                 .help("The API token")
+                 .env("CLP_TEST_ENV_HIDDEN")
+                 .hide_env_values(true))
+        .arg(Arg::with_name("password")
+                 .long("password")
+                 .help("The password")
+                 .env("CLP_TEST_ENV_SECRET")
+                 .secret(true));
+    assert!(test::compare_output(app, "prog --help", HIDDEN_ENV_HELP, false));
+}
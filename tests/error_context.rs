@@ -0,0 +1,124 @@
+extern crate clap;
+
+use clap::{App, Arg, ContextKind, ContextValue, ErrorKind, SubCommand};
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "auto", "never"])
+            .conflicts_with("quiet"))
+        .arg(Arg::with_name("quiet").long("quiet"))
+        .arg(Arg::with_name("pair").long("pair").number_of_values(2))
+        .arg(Arg::with_name("port")
+            .long("port")
+            .takes_value(true)
+            .validator(|v| v.parse::<u16>().map(|_| ()).map_err(|e| e.to_string())))
+        .subcommand(SubCommand::with_name("install"))
+}
+
+fn err(args: &[&str]) -> clap::Error {
+    let mut argv = vec!["prog"];
+    argv.extend(args);
+    app().get_matches_from_safe(argv).unwrap_err()
+}
+
+fn string(s: &str) -> Option<ContextValue> { Some(ContextValue::String(s.to_owned())) }
+
+#[test]
+fn invalid_value() {
+    let e = err(&["--color", "nevr"]);
+    assert_eq!(e.kind, ErrorKind::InvalidValue);
+    assert_eq!(e.context(ContextKind::InvalidArg).cloned(), string("--color <color>"));
+    assert_eq!(e.context(ContextKind::InvalidValue).cloned(), string("nevr"));
+    assert_eq!(e.context(ContextKind::ValidValues),
+               Some(&ContextValue::Strings(vec!["always".into(), "auto".into(), "never".into()])));
+    assert!(e.context(ContextKind::Usage).unwrap().to_string().contains("prog --color <color>"));
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn suggested_value() {
+    let e = err(&["--color", "nevr"]);
+    assert_eq!(e.context(ContextKind::SuggestedValue).cloned(), string("never"));
+}
+
+#[test]
+fn unknown_argument() {
+    let e = err(&["--colr"]);
+    assert_eq!(e.kind, ErrorKind::UnknownArgument);
+    assert_eq!(e.context(ContextKind::InvalidArg).cloned(), string("--colr"));
+
+    let e = err(&["--xyz"]);
+    assert_eq!(e.context(ContextKind::SuggestedArg), None);
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn suggested_arg() {
+    let e = err(&["--colr"]);
+    assert_eq!(e.context(ContextKind::SuggestedArg).cloned(), string("--color"));
+    assert!(e.message.contains("\tDid you mean --color?"));
+}
+
+#[test]
+fn argument_conflict() {
+    let e = err(&["--quiet", "--color", "auto"]);
+    assert_eq!(e.kind, ErrorKind::ArgumentConflict);
+    assert_eq!(e.context(ContextKind::InvalidArg).cloned(), string("--color <color>"));
+    assert_eq!(e.context(ContextKind::PriorArg).cloned(), string("--quiet"));
+}
+
+#[test]
+fn wrong_number_of_values() {
+    let e = err(&["--pair", "a"]);
+    assert_eq!(e.kind, ErrorKind::WrongNumberOfValues);
+    assert_eq!(e.context(ContextKind::ExpectedNumValues), Some(&ContextValue::Number(2)));
+    assert_eq!(e.context(ContextKind::ActualNumValues), Some(&ContextValue::Number(1)));
+    assert!(e.message.contains("requires 2 values, but 1 was provided"));
+}
+
+#[test]
+fn validation_reason() {
+    let e = err(&["--port", "70000"]);
+    assert_eq!(e.kind, ErrorKind::ValueValidation);
+    assert_eq!(e.context(ContextKind::Reason).cloned(),
+               string("number too large to fit in target type"));
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn invalid_subcommand() {
+    let e = err(&["instal"]);
+    assert_eq!(e.kind, ErrorKind::InvalidSubcommand);
+    assert_eq!(e.context(ContextKind::InvalidSubcommand).cloned(), string("instal"));
+    assert_eq!(e.context(ContextKind::SuggestedSubcommand).cloned(), string("install"));
+    assert_eq!(e.context(ContextKind::Command).cloned(), string("prog"));
+}
+
+#[test]
+fn missing_required() {
+    let e = App::new("prog")
+        .arg(Arg::with_name("input").required(true))
+        .arg(Arg::with_name("out").long("out").takes_value(true).required(true))
+        .get_matches_from_safe(vec!["prog"])
+        .unwrap_err();
+    assert_eq!(e.kind, ErrorKind::MissingRequiredArgument);
+    assert_eq!(e.context(ContextKind::InvalidArg),
+               Some(&ContextValue::Strings(vec!["<input>".into(), "--out <out>".into()])));
+}
+
+#[test]
+fn with_description() {
+    let e = clap::Error::with_description("something broke", ErrorKind::ValueValidation);
+    assert_eq!(e.contexts(), &[(ContextKind::Custom, string("something broke").unwrap())]);
+    assert_eq!(e.message, "error: something broke");
+}
+
+#[test]
+fn contexts_in_order() {
+    let e = err(&["--quiet", "--color", "auto"]);
+    let kinds = e.contexts().iter().map(|&(k, _)| k).collect::<Vec<_>>();
+    assert_eq!(kinds, [ContextKind::InvalidArg, ContextKind::PriorArg, ContextKind::Usage]);
+}
@@ -0,0 +1,62 @@
+extern crate clap;
+
+use clap::{App, Arg, ErrorKind, SubCommand};
+
+const EX_USAGE: i32 = 64;
+const EX_DATAERR: i32 = 65;
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .version("0.1")
+        .exit_code(ErrorKind::UnknownArgument, EX_USAGE)
+        .exit_code(ErrorKind::MissingRequiredArgument, EX_USAGE)
+        .exit_code(ErrorKind::InvalidValue, EX_DATAERR)
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "never"]))
+        .subcommand(SubCommand::with_name("add").arg(Arg::with_name("file").required(true)))
+}
+
+fn code(args: &[&str]) -> i32 {
+    let mut argv = vec!["prog"];
+    argv.extend(args);
+    app().get_matches_from_safe(argv).unwrap_err().exit_code()
+}
+
+#[test]
+fn configured_kinds() {
+    assert_eq!(code(&["--colr"]), EX_USAGE);
+    assert_eq!(code(&["--color", "sometimes"]), EX_DATAERR);
+}
+
+#[test]
+fn other_kinds_default_to_one() {
+    assert_eq!(code(&["--color"]), 1);
+}
+
+#[test]
+fn help_and_version_default_to_zero() {
+    assert_eq!(code(&["--help"]), 0);
+    assert_eq!(code(&["--version"]), 0);
+}
+
+#[test]
+fn applies_to_subcommands() {
+    assert_eq!(code(&["add"]), EX_USAGE);
+}
+
+#[test]
+fn last_code_wins() {
+    let err = app()
+        .exit_code(ErrorKind::UnknownArgument, 2)
+        .get_matches_from_safe(vec!["prog", "-x"])
+        .unwrap_err();
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn errors_built_by_hand() {
+    let err = clap::Error::with_description("oops", ErrorKind::Io);
+    assert_eq!(err.exit_code(), 1);
+}
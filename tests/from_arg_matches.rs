@@ -0,0 +1,135 @@
+extern crate clap;
+
+use clap::{App, Arg, ArgMatches, ErrorKind, FromArg, FromArgMatches, FromSubcommand, SubCommand};
+
+static PARSE_ERROR: &'static str = "error: 'abc' isn't a valid value for 'port'
+\tinvalid digit found in string
+
+USAGE:
+    prog [FLAGS] [OPTIONS]
+
+For more information try --help";
+
+static NO_SUBCOMMAND: &'static str = "error: A subcommand is required, but one was not provided
+
+USAGE:
+    git [SUBCOMMAND]
+
+For more information try --help";
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .arg(Arg::with_name("verbose").short("v").multiple(true))
+        .arg(Arg::with_name("port").long("port").takes_value(true))
+        .arg(Arg::with_name("ids").long("id").takes_value(true).multiple(true))
+}
+
+fn matches(args: &[&str]) -> ArgMatches<'static> {
+    let mut argv = vec!["prog"];
+    argv.extend(args);
+    app().get_matches_from(argv)
+}
+
+#[test]
+fn from_arg_flags() {
+    let m = matches(&["-vvv"]);
+    assert!(bool::from_arg(&m, "verbose").unwrap());
+    assert_eq!(u64::from_arg(&m, "verbose").unwrap(), 3);
+
+    let m = matches(&[]);
+    assert!(!bool::from_arg(&m, "verbose").unwrap());
+    assert_eq!(u64::from_arg(&m, "verbose").unwrap(), 0);
+}
+
+#[test]
+fn from_arg_values() {
+    let m = matches(&["--port", "8080", "--id", "1", "2"]);
+    assert_eq!(Option::<u16>::from_arg(&m, "port").unwrap(), Some(8080));
+    assert_eq!(Vec::<u32>::from_arg(&m, "ids").unwrap(), vec![1, 2]);
+
+    let m = matches(&[]);
+    assert_eq!(Option::<u16>::from_arg(&m, "port").unwrap(), None);
+    assert!(Vec::<u32>::from_arg(&m, "ids").unwrap().is_empty());
+}
+
+#[test]
+fn from_arg_invalid_value() {
+    let m = matches(&["--port", "abc"]);
+    let err = Option::<u16>::from_arg(&m, "port").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert_eq!(err.to_string().trim_right(), PARSE_ERROR);
+
+    let m = matches(&["--id", "1", "x"]);
+    assert_eq!(Vec::<u32>::from_arg(&m, "ids").unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Push { force: bool, remote: Option<String> },
+    Status,
+}
+
+impl FromSubcommand for Command {
+    fn from_subcommand(name: &str, m: &ArgMatches) -> clap::Result<Option<Self>> {
+        Ok(match name {
+            "push" => {
+                Some(Command::Push {
+                    force: try!(bool::from_arg(m, "force")),
+                    remote: try!(Option::from_arg(m, "remote")),
+                })
+            }
+            "status" => Some(Command::Status),
+            _ => None,
+        })
+    }
+}
+
+impl FromArgMatches for Command {
+    fn from_matches(m: &ArgMatches) -> clap::Result<Self> { clap::from_subcommand_matches(m) }
+}
+
+fn git() -> App<'static, 'static> {
+    App::new("git")
+        .subcommand(SubCommand::with_name("push").arg_from_usage("-f, --force 'force'")
+            .arg_from_usage("[remote] 'the remote'"))
+        .subcommand(SubCommand::with_name("status"))
+        .subcommand(SubCommand::with_name("log"))
+}
+
+#[test]
+fn from_subcommand() {
+    let m = git().get_matches_from(vec!["git", "push", "-f", "origin"]);
+    assert_eq!(Command::from_matches(&m).unwrap(),
+               Command::Push {
+                   force: true,
+                   remote: Some("origin".to_owned()),
+               });
+
+    let m = git().get_matches_from(vec!["git", "status"]);
+    assert_eq!(Command::from_matches(&m).unwrap(), Command::Status);
+}
+
+#[test]
+fn from_subcommand_missing() {
+    let m = git().get_matches_from(vec!["git"]);
+    let err = Command::from_matches(&m).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingSubcommand);
+    assert_eq!(err.to_string().trim_right(), NO_SUBCOMMAND);
+}
+
+#[test]
+fn from_subcommand_unknown() {
+    let m = git().get_matches_from(vec!["git", "log"]);
+    let err = Command::from_matches(&m).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnrecognizedSubcommand);
+    assert!(err.to_string().starts_with("error: The subcommand 'log' wasn't recognized\n"));
+    assert!(err.to_string().contains("USAGE:\n    git [SUBCOMMAND]"));
+}
+
+#[test]
+fn without_usage() {
+    let err = Command::from_matches(&ArgMatches::new()).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingSubcommand);
+    assert_eq!(err.to_string(),
+               "error: A subcommand is required, but one was not provided\n");
+}
@@ -0,0 +1,265 @@
+extern crate clap;
+
+use std::env;
+
+use clap::{App, Arg, AppSettings, SubCommand};
+
+#[test]
+fn indices_mult_opts() {
+    let m = App::new("ind")
+        .arg(Arg::with_name("exclude")
+                 .short("e")
+                 .takes_value(true)
+                 .multiple(true))
+        .arg(Arg::with_name("include")
+                 .short("i")
+                 .takes_value(true)
+                 .multiple(true))
+        .get_matches_from(vec!["ind", "-e", "A", "B", "-i", "B", "C", "-e", "C"]);
+
+    assert_eq!(m.indices_of("exclude").unwrap().collect::<Vec<_>>(), &[2, 3, 8]);
+    assert_eq!(m.indices_of("include").unwrap().collect::<Vec<_>>(), &[5, 6]);
+}
+
+#[test]
+fn index_mult_opts() {
+    let m = App::new("ind")
+        .arg(Arg::with_name("exclude")
+                 .short("e")
+                 .takes_value(true)
+                 .multiple(true))
+        .arg(Arg::with_name("include")
+                 .short("i")
+                 .takes_value(true)
+                 .multiple(true))
+        .get_matches_from(vec!["ind", "-e", "A", "B", "-i", "B", "C", "-e", "C"]);
+
+    assert_eq!(m.index_of("exclude"), Some(2));
+    assert_eq!(m.index_of("include"), Some(5));
+}
+
+#[test]
+fn index_flag() {
+    let m = App::new("ind")
+        .arg(Arg::with_name("exclude").short("e"))
+        .arg(Arg::with_name("include").short("i"))
+        .get_matches_from(vec!["ind", "-e", "-i"]);
+
+    assert_eq!(m.index_of("exclude"), Some(1));
+    assert_eq!(m.index_of("include"), Some(2));
+}
+
+#[test]
+fn index_flags() {
+    let m = App::new("ind")
+        .arg(Arg::with_name("exclude").short("e").multiple(true))
+        .arg(Arg::with_name("include").short("i").multiple(true))
+        .get_matches_from(vec!["ind", "-e", "-i", "-e", "-e", "-i"]);
+
+    assert_eq!(m.index_of("exclude"), Some(1));
+    assert_eq!(m.index_of("include"), Some(2));
+    assert_eq!(m.indices_of("exclude").unwrap().collect::<Vec<_>>(), &[1, 3, 4]);
+    assert_eq!(m.indices_of("include").unwrap().collect::<Vec<_>>(), &[2, 5]);
+}
+
+#[test]
+fn indices_mult_flags_combined() {
+    let m = App::new("ind")
+        .arg(Arg::with_name("exclude").short("e").multiple(true))
+        .arg(Arg::with_name("include").short("i").multiple(true))
+        .get_matches_from(vec!["ind", "-eieei"]);
+
+    assert_eq!(m.indices_of("exclude").unwrap().collect::<Vec<_>>(), &[1, 3, 4]);
+    assert_eq!(m.indices_of("include").unwrap().collect::<Vec<_>>(), &[2, 5]);
+}
+
+#[test]
+fn indices_mult_flags_opt_combined() {
+    let m = App::new("ind")
+        .arg(Arg::with_name("exclude").short("e").multiple(true))
+        .arg(Arg::with_name("include").short("i").multiple(true))
+        .arg(Arg::with_name("option").short("o").takes_value(true))
+        .get_matches_from(vec!["ind", "-eieeio", "val"]);
+
+    assert_eq!(m.indices_of("exclude").unwrap().collect::<Vec<_>>(), &[1, 3, 4]);
+    assert_eq!(m.indices_of("include").unwrap().collect::<Vec<_>>(), &[2, 5]);
+    assert_eq!(m.indices_of("option").unwrap().collect::<Vec<_>>(), &[7]);
+}
+
+#[test]
+fn indices_mult_flags_opt_combined_eq() {
+    let m = App::new("ind")
+        .arg(Arg::with_name("exclude").short("e").multiple(true))
+        .arg(Arg::with_name("include").short("i").multiple(true))
+        .arg(Arg::with_name("option").short("o").takes_value(true))
+        .get_matches_from(vec!["ind", "-eieeio=val"]);
+
+    assert_eq!(m.indices_of("exclude").unwrap().collect::<Vec<_>>(), &[1, 3, 4]);
+    assert_eq!(m.indices_of("include").unwrap().collect::<Vec<_>>(), &[2, 5]);
+    assert_eq!(m.indices_of("option").unwrap().collect::<Vec<_>>(), &[7]);
+}
+
+#[test]
+fn indices_mult_opt_value_delim_eq() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("option")
+                 .short("o")
+                 .takes_value(true)
+                 .use_delimiter(true)
+                 .multiple(true))
+        .get_matches_from(vec!["myapp", "-o=val1,val2,val3"]);
+
+    assert_eq!(m.indices_of("option").unwrap().collect::<Vec<_>>(), &[2, 3, 4]);
+}
+
+#[test]
+fn indices_mult_opt_value_no_delim_eq() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("option")
+                 .short("o")
+                 .takes_value(true)
+                 .multiple(true))
+        .get_matches_from(vec!["myapp", "-o=val1,val2,val3"]);
+
+    assert_eq!(m.indices_of("option").unwrap().collect::<Vec<_>>(), &[2]);
+}
+
+#[test]
+fn indices_long_opt_eq() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("option")
+                 .long("option")
+                 .takes_value(true))
+        .arg(Arg::with_name("flag").long("flag"))
+        .get_matches_from(vec!["myapp", "--option=val", "--flag"]);
+
+    assert_eq!(m.index_of("option"), Some(2));
+    assert_eq!(m.index_of("flag"), Some(3));
+}
+
+#[test]
+fn indices_mult_opt_mult_flag() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("option")
+                 .short("o")
+                 .takes_value(true)
+                 .multiple(true))
+        .arg(Arg::with_name("flag").short("f").multiple(true))
+        .get_matches_from(vec!["myapp", "-o", "val1", "-f", "-o", "val2", "-f"]);
+
+    assert_eq!(m.indices_of("option").unwrap().collect::<Vec<_>>(), &[2, 5]);
+    assert_eq!(m.indices_of("flag").unwrap().collect::<Vec<_>>(), &[3, 6]);
+}
+
+#[test]
+fn indices_positionals() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("first"))
+        .arg(Arg::with_name("rest").multiple(true))
+        .arg(Arg::with_name("flag").short("f"))
+        .get_matches_from(vec!["myapp", "a", "-f", "b", "c"]);
+
+    assert_eq!(m.index_of("first"), Some(1));
+    assert_eq!(m.index_of("flag"), Some(2));
+    assert_eq!(m.indices_of("rest").unwrap().collect::<Vec<_>>(), &[3, 4]);
+}
+
+#[test]
+fn indices_trailing_values() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("flag").short("f"))
+        .arg(Arg::with_name("args").multiple(true))
+        .get_matches_from(vec!["myapp", "-f", "--", "-a", "b"]);
+
+    assert_eq!(m.index_of("flag"), Some(1));
+    assert_eq!(m.indices_of("args").unwrap().collect::<Vec<_>>(), &[3, 4]);
+}
+
+#[test]
+fn indices_subcommand() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("flag").short("f"))
+        .subcommand(SubCommand::with_name("sub")
+                        .arg(Arg::with_name("opt").short("o").takes_value(true))
+                        .arg(Arg::with_name("pos")))
+        .get_matches_from(vec!["myapp", "-f", "sub", "-o", "val", "file"]);
+
+    assert_eq!(m.index_of("flag"), Some(1));
+    let sub_m = m.subcommand_matches("sub").unwrap();
+    assert_eq!(sub_m.index_of("opt"), Some(4));
+    assert_eq!(sub_m.index_of("pos"), Some(5));
+}
+
+#[test]
+fn indices_external_subcommand() {
+    let m = App::new("myapp")
+        .setting(AppSettings::AllowExternalSubcommands)
+        .arg(Arg::with_name("flag").short("f"))
+        .get_matches_from(vec!["myapp", "-f", "ext", "a", "--b"]);
+
+    match m.subcommand() {
+        ("ext", Some(sub_m)) => {
+            assert_eq!(sub_m.indices_of("").unwrap().collect::<Vec<_>>(), &[3, 4]);
+        }
+        _ => panic!("external subcommand not matched"),
+    }
+}
+
+#[test]
+fn indices_no_binary_name() {
+    let m = App::new("myapp")
+        .setting(AppSettings::NoBinaryName)
+        .arg(Arg::with_name("flag").short("f"))
+        .arg(Arg::with_name("pos"))
+        .get_matches_from(vec!["-f", "file"]);
+
+    assert_eq!(m.index_of("flag"), Some(1));
+    assert_eq!(m.index_of("pos"), Some(2));
+}
+
+#[test]
+fn indices_not_present() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("flag").short("f"))
+        .get_matches_from(vec!["myapp"]);
+
+    assert_eq!(m.index_of("flag"), None);
+    assert!(m.indices_of("flag").is_none());
+}
+
+#[test]
+fn indices_default_value() {
+    let m = App::new("myapp")
+        .arg(Arg::with_name("opt")
+                 .long("opt")
+                 .default_value("def"))
+        .get_matches_from(vec!["myapp"]);
+
+    assert!(m.is_present("opt"));
+    assert_eq!(m.index_of("opt"), None);
+    assert!(m.indices_of("opt").is_none());
+}
+
+#[test]
+fn indices_env() {
+    env::set_var("CLP_TEST_IND_ENV", "env");
+
+    let m = App::new("myapp")
+        .arg(Arg::with_name("opt")
+                 .long("opt")
+                 .env("CLP_TEST_IND_ENV"))
+        .get_matches_from(vec!["myapp"]);
+
+    assert_eq!(m.value_of("opt"), Some("env"));
+    assert_eq!(m.index_of("opt"), None);
+}
+
+#[test]
+fn indices_reparse() {
+    let mut app = App::new("myapp").arg(Arg::with_name("flag").short("f"));
+
+    let m = app.get_matches_from_safe_borrow(vec!["myapp", "-f"]).unwrap();
+    assert_eq!(m.index_of("flag"), Some(1));
+    let m = app.get_matches_from_safe_borrow(vec!["myapp", "-f"]).unwrap();
+    assert_eq!(m.index_of("flag"), Some(1));
+}
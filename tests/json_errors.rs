@@ -0,0 +1,89 @@
+extern crate clap;
+
+use clap::{App, AppSettings, Arg, ErrorKind, SubCommand};
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .setting(AppSettings::JsonErrors)
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "auto", "never"]))
+        .arg(Arg::with_name("input").required(true))
+        .subcommand(SubCommand::with_name("install"))
+}
+
+fn json(args: &[&str]) -> String {
+    let mut argv = vec!["prog"];
+    argv.extend(args);
+    app().get_matches_from_safe(argv).unwrap_err().to_json()
+}
+
+#[test]
+fn invalid_value() {
+    assert_eq!(json(&["--color", "purple", "x"]),
+               "{\"kind\":\"InvalidValue\",\
+                \"message\":\"error: 'purple' isn't a valid value for '--color <color>'\\n\
+                \\t[values: always, auto, never]\\n\\n\\n\
+                USAGE:\\n    prog <input> --color <color>\\n\\n\
+                For more information try --help\",\
+                \"arg\":\"--color <color>\",\
+                \"value\":\"purple\",\
+                \"valid_values\":[\"always\",\"auto\",\"never\"],\
+                \"suggestion\":null,\
+                \"usage\":\"prog <input> --color <color>\"}");
+}
+
+#[test]
+fn missing_required_lists_args() {
+    let json = json(&[]);
+    assert!(json.starts_with("{\"kind\":\"MissingRequiredArgument\","));
+    assert!(json.contains(",\"arg\":[\"<input>\"],\"value\":null,\"valid_values\":null,"));
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn unknown_argument() {
+    let json = json(&["--colr"]);
+    assert!(json.contains("\"arg\":\"--colr\""));
+    assert!(json.contains("\"suggestion\":\"--color\""));
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn invalid_subcommand() {
+    let json = App::new("prog")
+        .setting(AppSettings::JsonErrors)
+        .subcommand(SubCommand::with_name("install"))
+        .get_matches_from_safe(vec!["prog", "instal"])
+        .unwrap_err()
+        .to_json();
+    assert!(json.starts_with("{\"kind\":\"InvalidSubcommand\","));
+    assert!(json.contains("\"arg\":\"instal\""));
+    assert!(json.contains("\"suggestion\":\"install\""));
+}
+
+#[test]
+fn message_without_colors() {
+    let err = App::new("prog")
+        .setting(AppSettings::ColorAlways)
+        .get_matches_from_safe(vec!["prog", "x"])
+        .unwrap_err();
+    assert!(!err.to_json().contains('\x1b'));
+    assert!(err.to_json().contains("\"message\":\"error: Found argument 'x' which"));
+}
+
+#[test]
+fn custom_error() {
+    let err = clap::Error::with_description("a \"quoted\"\tthing", ErrorKind::Io);
+    assert_eq!(err.to_json(),
+               "{\"kind\":\"Io\",\"message\":\"error: a \\\"quoted\\\"\\tthing\",\"arg\":null,\
+                \"value\":null,\"valid_values\":null,\"suggestion\":null,\"usage\":null}");
+}
+
+#[test]
+fn help_is_not_an_error() {
+    let err = app().get_matches_from_safe(vec!["prog", "--help"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(!err.use_stderr());
+}
@@ -0,0 +1,158 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use std::env;
+
+use clap::{App, AppSettings, Arg, ErrorKind, SubCommand};
+
+static KEYS_HELP: &'static str = "prog 0.1
+
+USAGE:
+    prog [OPTIONS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+    -D <define>...        define a variable
+        --set <set>...    change a setting [keys: user.name, user.email]";
+
+static MISSING_DELIM: &'static str = "error: 'name' isn't a valid value for '-D <define>...'
+\texpected a key and value separated by '='
+
+USAGE:
+    prog -D <define>...
+
+For more information try --help";
+
+#[cfg(feature = "suggestions")]
+static BAD_KEY: &'static str = "error: 'user.nmae' isn't a valid key for '--set <set>...'
+\t[keys: user.email, user.name]
+
+\tDid you mean 'user.name'?
+
+USAGE:
+    prog --set <set>...
+
+For more information try --help";
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .version("0.1")
+        .arg(Arg::with_name("define")
+            .short("D")
+            .multiple(true)
+            .number_of_values(1)
+            .key_value(true)
+            .help("define a variable"))
+        .arg(Arg::with_name("set")
+            .long("set")
+            .multiple(true)
+            .number_of_values(1)
+            .possible_keys(&["user.name", "user.email"])
+            .help("change a setting"))
+}
+
+#[test]
+fn pairs() {
+    let m = app().get_matches_from(vec!["prog", "-D", "name=value", "-Dempty=", "-D", "a=b=c"]);
+    let map = m.values_of_map("define").unwrap();
+    assert_eq!(map.into_iter().collect::<Vec<_>>(),
+               [("a", "b=c"), ("empty", ""), ("name", "value")]);
+    assert_eq!(m.values_of("define").unwrap().collect::<Vec<_>>(),
+               ["name=value", "empty=", "a=b=c"]);
+}
+
+#[test]
+fn last_value_wins() {
+    let m = app().get_matches_from(vec!["prog", "-D", "x=1", "-D", "x=2"]);
+    assert_eq!(m.values_of_map("define").unwrap()["x"], "2");
+}
+
+#[test]
+fn not_present() {
+    let m = app().get_matches_from(vec!["prog"]);
+    assert_eq!(m.values_of_map("define"), None);
+}
+
+#[test]
+fn missing_delimiter() {
+    assert!(test::compare_output(app(), "prog -D name", MISSING_DELIM, true));
+    let err = app().get_matches_from_safe(vec!["prog", "-D", "name"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert_eq!(err.info, Some(vec!["define".to_owned(), "name".to_owned()]));
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn unknown_key() {
+    assert!(test::compare_output(app(), "prog --set user.nmae=kevin", BAD_KEY, true));
+    let err = app().get_matches_from_safe(vec!["prog", "--set", "user.nmae=kevin"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert_eq!(err.info, Some(vec!["set".to_owned(), "user.nmae".to_owned()]));
+}
+
+#[test]
+fn known_key() {
+    let m = app().get_matches_from(vec!["prog", "--set", "user.name=kevin"]);
+    assert_eq!(m.values_of_map("set").unwrap()["user.name"], "kevin");
+}
+
+#[test]
+fn custom_delimiter() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("header")
+            .short("H")
+            .multiple(true)
+            .number_of_values(1)
+            .key_value_delimiter(":"))
+        .get_matches_from(vec!["prog", "-H", "Host:example.com:8080", "-H", "Accept:*/*"]);
+    let map = m.values_of_map("header").unwrap();
+    assert_eq!(map["Host"], "example.com:8080");
+    assert_eq!(map["Accept"], "*/*");
+}
+
+#[test]
+fn global_custom_delimiter() {
+    let m = App::new("prog")
+        .setting(AppSettings::PropagateGlobalValuesDown)
+        .arg(Arg::with_name("header")
+            .short("H")
+            .multiple(true)
+            .number_of_values(1)
+            .key_value_delimiter(":")
+            .global(true))
+        .subcommand(SubCommand::with_name("get"))
+        .get_matches_from(vec!["prog", "-H", "Host:a=b", "get"]);
+    let map = m.subcommand_matches("get").unwrap().values_of_map("header").unwrap();
+    assert_eq!(map["Host"], "a=b");
+}
+
+#[test]
+fn positional() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("vars").multiple(true).key_value(true))
+        .get_matches_from(vec!["prog", "a=1", "b=2"]);
+    assert_eq!(m.values_of_map("vars").unwrap().len(), 2);
+}
+
+#[test]
+fn env_value_is_checked() {
+    env::set_var("CLAP_KEY_VALUE_TEST_DEFINE", "oops");
+    let res = App::new("prog")
+        .arg(Arg::with_name("define")
+            .short("D")
+            .key_value(true)
+            .env("CLAP_KEY_VALUE_TEST_DEFINE"))
+        .get_matches_from_safe(vec!["prog"]);
+    env::remove_var("CLAP_KEY_VALUE_TEST_DEFINE");
+    assert_eq!(res.unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[test]
+fn keys_in_help() {
+    assert!(test::compare_output(app(), "prog --help", KEYS_HELP, false));
+}
@@ -0,0 +1,163 @@
+extern crate regex;
+extern crate clap;
+
+use clap::{App, AppSettings, Arg, SubCommand};
+use regex::Regex;
+
+static MANPAGE: &'static str = r#".TH MYAPP 1 "" "myapp 1.0" "User Commands"
+.SH NAME
+myapp \- Tests man pages
+.SH SYNOPSIS
+myapp [FLAGS] [OPTIONS] [file] [SUBCOMMAND]
+.SH DESCRIPTION
+Tests man pages.
+.sp
+Including the long description.
+.SH OPTIONS
+.TP
+\fB\-h\fR, \fB\-\-help\fR
+Prints help information
+.TP
+\fB\-\-mode\fR \fI<MODE>\fR
+the mode to use
+[possible values: fast, slow] [default: fast]
+.TP
+\fB\-V\fR, \fB\-\-version\fR
+Prints version information
+.TP
+\fB\-v\fR, \fB\-\-verbose\fR
+Sets the level
+.br
+of verbosity
+.SH ARGS
+.TP
+\fI<file>\fR
+some input file
+.SH "SUBCOMMAND: myapp help"
+Prints this message or the help of the given subcommand(s)
+.PP
+myapp help
+.SS OPTIONS
+.TP
+\fB\-h\fR, \fB\-\-help\fR
+Prints help information
+.TP
+\fB\-V\fR, \fB\-\-version\fR
+Prints version information
+.SH "SUBCOMMAND: myapp test"
+tests things
+.PP
+myapp test [OPTIONS]
+.SS OPTIONS
+.TP
+\fB\-\-case\fR \fI<case>\fR
+the case to test
+.TP
+\fB\-h\fR, \fB\-\-help\fR
+Prints help information
+.TP
+\fB\-V\fR, \fB\-\-version\fR
+Prints version information
+.SH VERSION
+1.0
+.SH AUTHOR
+Kevin K. <kbknapp@gmail.com>
+"#;
+
+fn compare(left: &str, right: &str) -> bool {
+    let b = left == right;
+    if !b {
+        let re = Regex::new(" ").unwrap();
+        println!("");
+        println!("--> left");
+        println!("{}", re.replace_all(left, "\u{2022}"));
+        println!("--> right");
+        println!("{}", re.replace_all(right, "\u{2022}"));
+        println!("--")
+    }
+    b
+}
+
+fn build_app() -> App<'static, 'static> {
+    App::new("myapp")
+        .version("1.0")
+        .author("Kevin K. <kbknapp@gmail.com>")
+        .about("Tests man pages")
+        .long_about("Tests man pages.\n\nIncluding the long description.")
+        .arg(Arg::with_name("file").help("some input file"))
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .value_name("MODE")
+            .possible_values(&["fast", "slow"])
+            .default_value("fast")
+            .help("the mode to use"))
+        .arg(Arg::with_name("verbose")
+            .short("v")
+            .long("verbose")
+            .help("Sets verbosity")
+            .long_help("Sets the level\nof verbosity"))
+        .arg(Arg::with_name("secret")
+            .long("secret")
+            .hidden(true)
+            .help("never shown"))
+        .subcommand(SubCommand::with_name("test")
+            .about("tests things")
+            .arg(Arg::with_name("case")
+                .long("case")
+                .takes_value(true)
+                .help("the case to test")))
+        .subcommand(SubCommand::with_name("internal")
+            .setting(AppSettings::Hidden)
+            .about("never shown"))
+}
+
+fn manpage(mut app: App) -> String {
+    let mut buf = vec![];
+    app.gen_manpage_to(&mut buf);
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn manpage_full() {
+    assert!(compare(&*manpage(build_app()), MANPAGE));
+}
+
+#[test]
+fn manpage_hidden() {
+    let page = manpage(build_app());
+    assert!(!page.contains("secret"));
+    assert!(!page.contains("internal"));
+    assert!(!page.contains("never shown"));
+}
+
+#[test]
+fn manpage_nested_subcommands() {
+    let app = App::new("myapp")
+        .setting(AppSettings::DisableHelpSubcommand)
+        .subcommand(SubCommand::with_name("remote")
+            .subcommand(SubCommand::with_name("add").about("adds a remote")));
+    let page = manpage(app);
+    assert!(page.contains(".SH \"SUBCOMMAND: myapp remote\"\n"));
+    assert!(page.contains(".SH \"SUBCOMMAND: myapp remote add\"\nadds a remote\n"));
+}
+
+#[test]
+fn manpage_escapes_roff() {
+    let app = App::new("myapp")
+        .about("Reads C:\\config")
+        .arg(Arg::with_name("flag")
+            .long("flag")
+            .help(".starts with a dot"));
+    let page = manpage(app);
+    assert!(page.contains("myapp \\- Reads C:\\econfig\n"));
+    assert!(page.contains("\\fB\\-\\-flag\\fR\n\\&.starts with a dot\n"));
+}
+
+#[test]
+fn manpage_no_version_or_author() {
+    let page = manpage(App::new("myapp"));
+    assert!(page.starts_with(".TH MYAPP 1 \"\" \"myapp\" \"User Commands\"\n"));
+    assert!(!page.contains(".SH VERSION"));
+    assert!(!page.contains(".SH AUTHOR"));
+    assert!(!page.contains(".SH DESCRIPTION"));
+}
@@ -0,0 +1,138 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use std::io;
+
+use clap::{App, Arg, ConfigSource, English, ErrorKind, Messages, SubCommand};
+
+static GERMAN_HELP: &'static str = "prog 0.1
+
+VERWENDUNG:
+    prog [OPTIONS] [input] [SUBCOMMAND]
+
+SCHALTER:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONEN:
+        --mode <mode>    the mode [Standard: fast]  [Werte: fast, slow]
+
+ARGUMENTE:
+    <input>    the input
+
+UNTERBEFEHLE:
+    help      Prints this message or the help of the given subcommand(s)
+    remote    manage remotes";
+
+#[cfg(feature = "suggestions")]
+static GERMAN_ERROR: &'static str = "Fehler: 'fats' ist kein gültiger Wert für '--mode <mode>'
+\t[Werte: fast, slow]
+
+\tMeinten Sie 'fast'?
+
+VERWENDUNG:
+    prog --mode <mode>
+
+Mehr Informationen mit --help";
+
+struct German;
+
+impl Messages for German {
+    fn usage_heading(&self) -> &str { "VERWENDUNG:" }
+    fn flags_heading(&self) -> &str { "SCHALTER:" }
+    fn options_heading(&self) -> &str { "OPTIONEN:" }
+    fn args_heading(&self) -> &str { "ARGUMENTE:" }
+    fn subcommands_heading(&self) -> &str { "UNTERBEFEHLE:" }
+    fn default_label(&self) -> &str { "Standard" }
+    fn values_label(&self) -> &str { "Werte" }
+    fn error_label(&self) -> &str { "Fehler:" }
+    fn more_info(&self, help: &str) -> String { format!("Mehr Informationen mit {}", help) }
+    fn did_you_mean(&self, suggestion: &str) -> String { format!("Meinten Sie {}?", suggestion) }
+    fn in_config(&self, file: &str, line: u64) -> String {
+        format!("in der Konfigurationsdatei '{}', Zeile {}", file, line)
+    }
+    fn invalid_value(&self, value: &str, arg: &str, valid_values: &str) -> String {
+        format!("'{}' ist kein gültiger Wert für '{}'\n\t[{}: {}]",
+                value,
+                arg,
+                self.values_label(),
+                valid_values)
+    }
+    fn unknown_argument(&self, arg: &str) -> String {
+        format!("Das Argument '{}' wurde nicht erwartet", arg)
+    }
+}
+
+struct Text(&'static str);
+
+impl ConfigSource for Text {
+    fn name(&self) -> String { "prog.conf".to_owned() }
+
+    fn read(&self) -> io::Result<Option<String>> { Ok(Some(self.0.to_owned())) }
+}
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .version("0.1")
+        .messages(German)
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .help("the mode")
+            .takes_value(true)
+            .default_value("fast")
+            .possible_values(&["fast", "slow"]))
+        .arg(Arg::with_name("input").help("the input"))
+        .subcommand(SubCommand::with_name("remote")
+            .about("manage remotes")
+            .arg(Arg::with_name("name").long("name")))
+}
+
+#[test]
+fn help_headings() {
+    assert!(test::compare_output(app(), "prog --help", GERMAN_HELP, false));
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn error_message() {
+    assert!(test::compare_output(app(), "prog --mode fats", GERMAN_ERROR, true));
+}
+
+#[test]
+fn untranslated_messages_fall_back_to_english() {
+    let err = app().get_matches_from_safe(vec!["prog", "a", "b"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(err.message.starts_with("Fehler: Das Argument 'b' wurde nicht erwartet"));
+
+    let err = app().get_matches_from_safe(vec!["prog", "--mode"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::EmptyValue);
+    assert!(err.message
+        .starts_with("Fehler: The argument '--mode <mode>' requires a value but none was \
+                      supplied"));
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn subcommands_inherit_messages() {
+    let err = app().get_matches_from_safe(vec!["prog", "remote", "--nmae"]).unwrap_err();
+    assert!(err.message.contains("Das Argument '--nmae' wurde nicht erwartet\n\tMeinten Sie \
+                                  --name?\n\nVERWENDUNG:\n    prog remote --name"));
+}
+
+#[test]
+fn config_location() {
+    let err = app()
+        .config_source(Text("mode = fats"))
+        .get_matches_from_safe(vec!["prog"])
+        .unwrap_err();
+    assert!(err.message.contains("\n\tin der Konfigurationsdatei 'prog.conf', Zeile 1\n"));
+}
+
+#[test]
+fn english_is_the_default() {
+    assert_eq!(English.usage_heading(), "USAGE:");
+    let err = App::new("prog").get_matches_from_safe(vec!["prog", "x"]).unwrap_err();
+    assert!(err.message.starts_with("error: Found argument 'x' which wasn't expected"));
+}
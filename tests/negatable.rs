@@ -0,0 +1,171 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use clap::{App, Arg, ArgGroup, ErrorKind, Shell};
+
+static NEGATABLE_HELP: &'static str = "prog 0.1
+
+USAGE:
+    prog [FLAGS]
+
+FLAGS:
+    -c, --[no-]color    colorize the output
+    -h, --help          Prints help information
+    -V, --version       Prints version information
+    -v, --verbose       be chatty";
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .version("0.1")
+        .arg(Arg::with_name("color")
+            .short("c")
+            .long("color")
+            .negatable(true)
+            .help("colorize the output"))
+        .arg(Arg::with_name("verbose").short("v").long("verbose").help("be chatty"))
+}
+
+#[test]
+fn flag_given() {
+    let m = app().get_matches_from(vec!["prog", "--color"]);
+    assert!(m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(true));
+}
+
+#[test]
+fn negation_given() {
+    let m = app().get_matches_from(vec!["prog", "--no-color"]);
+    assert!(!m.is_present("color"));
+    assert_eq!(m.occurrences_of("color"), 0);
+    assert_eq!(m.flag_state("color"), Some(false));
+}
+
+#[test]
+fn neither_given() {
+    let m = app().get_matches_from(vec!["prog", "-v"]);
+    assert_eq!(m.flag_state("color"), None);
+    assert_eq!(m.flag_state("verbose"), Some(true));
+}
+
+#[test]
+fn last_one_wins() {
+    let m = app().get_matches_from(vec!["prog", "-c", "--no-color"]);
+    assert_eq!(m.flag_state("color"), Some(false));
+
+    let m = app().get_matches_from(vec!["prog", "--no-color", "--color", "--no-color", "-c"]);
+    assert_eq!(m.flag_state("color"), Some(true));
+    assert_eq!(m.occurrences_of("color"), 1);
+}
+
+#[test]
+fn negation_forgets_requirements() {
+    let res = App::new("prog")
+        .arg(Arg::with_name("color").long("color").negatable(true).requires("theme"))
+        .arg(Arg::with_name("theme").long("theme").takes_value(true))
+        .get_matches_from_safe(vec!["prog", "--color", "--no-color"]);
+    assert_eq!(res.unwrap().flag_state("color"), Some(false));
+}
+
+#[test]
+fn negation_leaves_groups() {
+    let app = || {
+        App::new("prog")
+            .arg(Arg::with_name("color").long("color").negatable(true))
+            .arg(Arg::with_name("mono").long("mono"))
+            .group(ArgGroup::with_name("output").args(&["color", "mono"]).required(true))
+    };
+    let res = app().get_matches_from_safe(vec!["prog", "--color", "--no-color"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+
+    let m = app().get_matches_from(vec!["prog", "--color", "--no-color", "--mono"]);
+    assert!(m.is_present("output"));
+    assert_eq!(m.occurrences_of("output"), 1);
+}
+
+#[test]
+fn negation_requires_group_args_again() {
+    let res = App::new("prog")
+        .arg(Arg::with_name("color").long("color").negatable(true))
+        .arg(Arg::with_name("mono").long("mono").required(true))
+        .group(ArgGroup::with_name("output").args(&["color", "mono"]))
+        .get_matches_from_safe(vec!["prog", "--color", "--no-color"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn negation_forgets_conflicts() {
+    let app = || {
+        App::new("prog")
+            .arg(Arg::with_name("color").long("color").negatable(true).conflicts_with("mono"))
+            .arg(Arg::with_name("mono").long("mono"))
+    };
+    let m = app().get_matches_from(vec!["prog", "--color", "--no-color", "--mono"]);
+    assert!(m.is_present("mono"));
+    let m = app().get_matches_from(vec!["prog", "--mono", "--color", "--no-color"]);
+    assert!(m.is_present("mono"));
+    let res = app().get_matches_from_safe(vec!["prog", "--no-color", "--color", "--mono"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn negation_forgets_group_conflicts() {
+    let app = || {
+        App::new("prog")
+            .arg(Arg::with_name("color").long("color").negatable(true))
+            .arg(Arg::with_name("mono").long("mono"))
+            .group(ArgGroup::with_name("output").args(&["color", "mono"]))
+    };
+    let m = app().get_matches_from(vec!["prog", "--color", "--no-color", "--mono"]);
+    assert!(m.is_present("mono"));
+    assert!(!m.is_present("color"));
+    let m = app().get_matches_from(vec!["prog", "--mono", "--color", "--no-color"]);
+    assert!(m.is_present("mono"));
+    assert_eq!(m.occurrences_of("output"), 1);
+}
+
+#[test]
+fn not_negatable() {
+    let res = app().get_matches_from_safe(vec!["prog", "--no-verbose"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn help_shows_one_line() {
+    assert!(test::compare_output(app(), "prog --help", NEGATABLE_HELP, false));
+}
+
+#[test]
+fn completions_include_negation() {
+    let mut buf = vec![];
+    app().gen_completions_to("prog", Shell::Bash, &mut buf);
+    let script = String::from_utf8(buf).unwrap();
+    assert!(script.contains("--color --verbose --help --version --no-color"));
+}
+
+#[test]
+#[should_panic]
+fn negatable_option() {
+    App::new("prog")
+        .arg(Arg::with_name("color").long("color").takes_value(true).negatable(true))
+        .get_matches_from(vec!["prog"]);
+}
+
+#[test]
+#[should_panic]
+fn negation_already_in_use() {
+    App::new("prog")
+        .arg(Arg::with_name("no-color").long("no-color"))
+        .arg(Arg::with_name("color").long("color").negatable(true))
+        .get_matches_from(vec!["prog"]);
+}
+
+#[test]
+#[should_panic]
+fn negation_used_afterwards() {
+    App::new("prog")
+        .arg(Arg::with_name("color").long("color").negatable(true))
+        .arg(Arg::with_name("no-color").long("no-color"))
+        .get_matches_from(vec!["prog"]);
+}
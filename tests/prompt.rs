@@ -0,0 +1,48 @@
+extern crate clap;
+
+use std::env;
+use std::process::{Command, Stdio};
+
+use clap::{App, AppSettings, Arg, ErrorKind};
+
+// Runs the test `name` again in a child process whose stdin isn't a terminal, returning whether
+// it passed. Within that child it returns `None`, so the test goes on to do the actual checks.
+fn without_tty(name: &str) -> Option<bool> {
+    if env::var_os("CLAP_TEST_NO_TTY").is_some() {
+        return None;
+    }
+    let status = Command::new(env::current_exe().unwrap())
+        .args(&[name, "--exact", "--nocapture"])
+        .env("CLAP_TEST_NO_TTY", "1")
+        .stdin(Stdio::null())
+        .status()
+        .unwrap();
+    Some(status.success())
+}
+
+#[test]
+fn missing_without_tty() {
+    if let Some(passed) = without_tty("missing_without_tty") {
+        assert!(passed);
+        return;
+    }
+    let res = App::new("prog")
+        .setting(AppSettings::PromptForMissing)
+        .arg(Arg::with_name("user").long("user").takes_value(true).required(true))
+        .arg(Arg::with_name("file").required(true))
+        .get_matches_from_safe(vec!["prog"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn given_without_tty() {
+    if let Some(passed) = without_tty("given_without_tty") {
+        assert!(passed);
+        return;
+    }
+    let m = App::new("prog")
+        .setting(AppSettings::PromptForMissing)
+        .arg(Arg::with_name("user").long("user").takes_value(true).required(true).secret(true))
+        .get_matches_from(vec!["prog", "--user", "kevin"]);
+    assert_eq!(m.value_of("user"), Some("kevin"));
+}
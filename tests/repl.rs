@@ -0,0 +1,152 @@
+extern crate clap;
+
+use std::io::Cursor;
+
+use clap::{App, AppSettings, Arg, ErrorKind, SubCommand};
+
+fn console() -> App<'static, 'static> {
+    App::new("console")
+        .version("1.0")
+        .arg(Arg::with_name("verbose").long("verbose").global(true))
+        .subcommand(SubCommand::with_name("add")
+            .arg(Arg::with_name("force").short("f"))
+            .arg(Arg::with_name("file").required(true)))
+        .subcommand(SubCommand::with_name("rm").arg(Arg::with_name("file")))
+}
+
+#[test]
+fn repl_lines() {
+    let input = "add -f 'a b'\nrm \"c\\\"d\"\n";
+    let res: Vec<_> = console().repl_from(Cursor::new(input)).collect();
+    assert_eq!(res.len(), 2);
+
+    let m = res[0].as_ref().unwrap();
+    let add = m.subcommand_matches("add").unwrap();
+    assert!(add.is_present("force"));
+    assert_eq!(add.value_of("file"), Some("a b"));
+
+    let m = res[1].as_ref().unwrap();
+    assert_eq!(m.subcommand_matches("rm").unwrap().value_of("file"), Some("c\"d"));
+}
+
+#[test]
+fn repl_skips_blank_lines() {
+    let mut repl = console().repl_from(Cursor::new("\n   \nrm\n\n"));
+    assert_eq!(repl.next().unwrap().unwrap().subcommand_name(), Some("rm"));
+    assert!(repl.next().is_none());
+    assert!(repl.next().is_none());
+}
+
+#[test]
+fn repl_returns_errors() {
+    let input = "--help\nadd\nrm -x\nrm\n";
+    let kinds: Vec<_> = console()
+        .repl_from(Cursor::new(input))
+        .map(|res| res.map(|_| ()).map_err(|e| e.kind))
+        .collect();
+    assert_eq!(kinds,
+               vec![Err(ErrorKind::HelpDisplayed),
+                    Err(ErrorKind::MissingRequiredArgument),
+                    Err(ErrorKind::UnknownArgument),
+                    Ok(())]);
+}
+
+#[test]
+fn repl_usage_uses_app_name() {
+    let mut repl = console().repl_from(Cursor::new("add\n"));
+    let err = repl.next().unwrap().unwrap_err();
+    assert!(err.message.contains("console add [FLAGS] <file>"));
+}
+
+#[test]
+fn repl_no_binary_name() {
+    let app = App::new("console")
+        .setting(AppSettings::NoBinaryName)
+        .arg(Arg::with_name("cmd"));
+    let mut repl = app.repl_from(Cursor::new("ls\n"));
+    assert_eq!(repl.next().unwrap().unwrap().value_of("cmd"), Some("ls"));
+}
+
+#[test]
+fn repl_global_args() {
+    let input = "add --verbose a\nadd b\n--verbose rm\n";
+    let res: Vec<_> = console().repl_from(Cursor::new(input)).map(Result::unwrap).collect();
+    assert!(res[0].subcommand_matches("add").unwrap().is_present("verbose"));
+    assert!(!res[1].subcommand_matches("add").unwrap().is_present("verbose"));
+    assert!(res[2].is_present("verbose"));
+}
+
+#[test]
+fn reparse_with_global_args() {
+    let mut app = console();
+    for _ in 0..3 {
+        let m = app.get_matches_from_safe_borrow(vec!["console", "rm", "--verbose"]).unwrap();
+        assert!(m.subcommand_matches("rm").unwrap().is_present("verbose"));
+    }
+}
+
+#[test]
+fn reparse_resets_args_negate_subcommands() {
+    let mut app = App::new("prog")
+        .setting(AppSettings::ArgsNegateSubcommands)
+        .arg(Arg::with_name("file"))
+        .subcommand(SubCommand::with_name("add"));
+    let m = app.get_matches_from_safe_borrow(vec!["prog", "a"]).unwrap();
+    assert_eq!(m.value_of("file"), Some("a"));
+    let m = app.get_matches_from_safe_borrow(vec!["prog", "add"]).unwrap();
+    assert_eq!(m.subcommand_name(), Some("add"));
+}
+
+#[test]
+fn reparse_resets_trailing_values() {
+    let mut app = App::new("prog")
+        .arg(Arg::with_name("verbose").short("v"))
+        .arg(Arg::with_name("rest").multiple(true));
+    let m = app.get_matches_from_safe_borrow(vec!["prog", "--", "-v"]).unwrap();
+    assert_eq!(m.value_of("rest"), Some("-v"));
+    let m = app.get_matches_from_safe_borrow(vec!["prog", "-v"]).unwrap();
+    assert!(m.is_present("verbose"));
+}
+
+#[test]
+fn repl_resets_requirements() {
+    let app = App::new("prog")
+        .arg(Arg::with_name("a").short("a").requires("b"))
+        .arg(Arg::with_name("b").short("b"))
+        .arg(Arg::with_name("c").short("c"));
+    let res: Vec<_> = app.repl_from(Cursor::new("-a -b\n-c\n-a\n")).collect();
+    assert!(res[0].is_ok());
+    assert!(res[1].as_ref().unwrap().is_present("c"));
+    assert_eq!(res[2].as_ref().unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn repl_resets_overrides() {
+    let app = App::new("prog")
+        .arg(Arg::with_name("r").short("r").required(true))
+        .arg(Arg::with_name("o").short("o").overrides_with("r"))
+        .arg(Arg::with_name("c").short("c"));
+    let res: Vec<_> = app.repl_from(Cursor::new("-o\n-c\n-r -c\n")).collect();
+    assert!(res[0].is_ok());
+    assert_eq!(res[1].as_ref().unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+    assert!(res[2].is_ok());
+}
+
+#[test]
+fn reparse_resets_requirements() {
+    let mut app = App::new("prog")
+        .arg(Arg::with_name("a").short("a").requires("b"))
+        .arg(Arg::with_name("b").short("b"))
+        .arg(Arg::with_name("c").short("c"));
+    assert!(app.get_matches_from_safe_borrow(vec!["prog", "-a", "-b"]).is_ok());
+    assert!(app.get_matches_from_safe_borrow(vec!["prog", "-c"]).is_ok());
+}
+
+#[test]
+fn reparse_resets_conflicts() {
+    let mut app = App::new("prog")
+        .arg(Arg::with_name("a").short("a").conflicts_with("b"))
+        .arg(Arg::with_name("b").short("b"));
+    assert!(app.get_matches_from_safe_borrow(vec!["prog", "-a"]).is_ok());
+    assert!(app.get_matches_from_safe_borrow(vec!["prog", "-b"]).is_ok());
+}
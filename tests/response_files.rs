@@ -0,0 +1,133 @@
+extern crate clap;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process;
+
+use clap::{App, AppSettings, Arg, ArgMatches, ErrorKind};
+
+// A path for a response file unique to this test run
+fn rsp_path(name: &str) -> String {
+    let path: PathBuf = env::temp_dir().join(format!("clap-rsp-{}-{}", process::id(), name));
+    path.to_str().unwrap().to_owned()
+}
+
+// Writes a response file and returns its path
+fn rsp(name: &str, contents: &str) -> String {
+    let path = rsp_path(name);
+    File::create(&path).unwrap().write_all(contents.as_bytes()).unwrap();
+    path
+}
+
+fn app() -> App<'static, 'static> {
+    App::new("rsp")
+        .setting(AppSettings::ResponseFiles)
+        .arg(Arg::with_name("verbose").short("v").multiple(true))
+        .arg(Arg::with_name("out").short("o").takes_value(true))
+        .arg(Arg::with_name("files").multiple(true))
+}
+
+fn files<'a>(m: &'a ArgMatches) -> Vec<&'a str> { m.values_of("files").unwrap().collect() }
+
+#[test]
+fn expands_response_file() {
+    let path = rsp("expand", "-v -o out.txt\n  a.c\tb.c\n");
+    let m = app().get_matches_from(vec!["rsp".to_owned(), format!("@{}", path), "c.c".into()]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(m.occurrences_of("verbose"), 1);
+    assert_eq!(m.value_of("out"), Some("out.txt"));
+    assert_eq!(files(&m), &["a.c", "b.c", "c.c"]);
+}
+
+#[test]
+fn quotes_and_escapes() {
+    let path = rsp("quotes", "'with space.c' \"quoted \\\"name\\\".c\" escaped\\ space.c \"\"");
+    let m = app().get_matches_from(vec!["rsp".to_owned(), format!("@{}", path)]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(files(&m), &["with space.c", "quoted \"name\".c", "escaped space.c", ""]);
+}
+
+#[test]
+fn nested_response_files() {
+    let inner = rsp("inner", "b.c -v");
+    let outer = rsp("outer", &*format!("a.c @{} c.c", inner));
+    let m = app().get_matches_from(vec!["rsp".to_owned(), format!("@{}", outer)]);
+    fs::remove_file(&inner).unwrap();
+    fs::remove_file(&outer).unwrap();
+
+    assert_eq!(files(&m), &["a.c", "b.c", "c.c"]);
+    assert_eq!(m.occurrences_of("verbose"), 1);
+}
+
+#[test]
+fn same_file_twice_is_not_a_cycle() {
+    let path = rsp("twice", "a.c");
+    let m = app()
+        .get_matches_from(vec!["rsp".to_owned(), format!("@{}", path), format!("@{}", path)]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(files(&m), &["a.c", "a.c"]);
+}
+
+#[test]
+fn cycle_is_an_error() {
+    let a = rsp_path("cycle-a");
+    let b = rsp("cycle-b", &*format!("y.c @{}", a));
+    rsp("cycle-a", &*format!("x.c @{}", b));
+    let res = app().get_matches_from_safe(vec!["rsp".to_owned(), format!("@{}", a)]);
+    fs::remove_file(&a).unwrap();
+    fs::remove_file(&b).unwrap();
+
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert!(err.message.contains("includes itself"));
+    assert_eq!(err.info, Some(vec![a]));
+}
+
+#[test]
+fn missing_file_is_io_error() {
+    let res = app().get_matches_from_safe(vec!["rsp", "a.c", "@/does/not/exist.rsp"]);
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert!(err.message.contains("Failed to expand response file"));
+    assert!(err.message.contains("/does/not/exist.rsp"));
+    assert_eq!(err.info, Some(vec!["/does/not/exist.rsp".to_owned()]));
+}
+
+#[test]
+fn escaped_at() {
+    let path = rsp("escaped", "@@from-file");
+    let m = app()
+        .get_matches_from(vec!["rsp".to_owned(), "@@literal".into(), format!("@{}", path)]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(files(&m), &["@literal", "@from-file"]);
+}
+
+#[test]
+fn lone_at_is_left_alone() {
+    let m = app().get_matches_from(vec!["rsp", "@"]);
+    assert_eq!(files(&m), &["@"]);
+}
+
+#[test]
+fn not_expanded_without_setting() {
+    let m = App::new("rsp")
+        .arg(Arg::with_name("files").multiple(true))
+        .get_matches_from(vec!["rsp", "@/does/not/exist.rsp", "@@literal"]);
+    assert_eq!(files(&m), &["@/does/not/exist.rsp", "@@literal"]);
+}
+
+#[test]
+fn indices_count_expanded_args() {
+    let path = rsp("indices", "-o out.txt a.c");
+    let m = app().get_matches_from(vec!["rsp".to_owned(), format!("@{}", path), "b.c".into()]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(m.index_of("out"), Some(2));
+    assert_eq!(m.indices_of("files").unwrap().collect::<Vec<_>>(), &[3, 4]);
+}
@@ -0,0 +1,86 @@
+extern crate clap;
+
+use std::io::Cursor;
+
+use clap::{App, AppSettings, Arg, ContextKind, ContextValue, ErrorKind};
+
+static UNTERMINATED: &'static str = "error: Failed to split the command line at column 14: \
+                                     unterminated double quote";
+
+fn tool() -> App<'static, 'static> {
+    App::new("tool")
+        .arg(Arg::with_name("opt").long("opt").takes_value(true))
+        .arg(Arg::with_name("verbose").short("v"))
+        .arg(Arg::with_name("files").multiple(true))
+}
+
+#[test]
+fn from_str() {
+    let m = tool().get_matches_from_str("tool --opt 'a b' \"c\\\"d\" e\\ f").unwrap();
+    assert_eq!(m.value_of("opt"), Some("a b"));
+    assert_eq!(m.values_of("files").unwrap().collect::<Vec<_>>(), ["c\"d", "e f"]);
+}
+
+#[test]
+fn from_str_comments_and_continuations() {
+    let m = tool().get_matches_from_str("tool -v \\\n  --opt=x # --opt=y\n z").unwrap();
+    assert!(m.is_present("verbose"));
+    assert_eq!(m.value_of("opt"), Some("x"));
+    assert_eq!(m.value_of("files"), Some("z"));
+}
+
+#[test]
+fn from_str_empty_values() {
+    let m = tool().get_matches_from_str("tool --opt '' \"\"").unwrap();
+    assert_eq!(m.value_of("opt"), Some(""));
+    assert_eq!(m.value_of("files"), Some(""));
+}
+
+#[test]
+fn from_str_no_binary_name() {
+    let m = tool().setting(AppSettings::NoBinaryName).get_matches_from_str("-v 'a b'").unwrap();
+    assert!(m.is_present("verbose"));
+    assert_eq!(m.value_of("files"), Some("a b"));
+}
+
+#[test]
+fn from_str_parse_errors() {
+    let err = tool().get_matches_from_str("tool --nope").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn from_str_unterminated_quote() {
+    let err = tool().get_matches_from_str("tool --opt=a \"b c").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ShellSyntax);
+    assert_eq!(err.context(ContextKind::Column), Some(&ContextValue::Number(14)));
+    assert_eq!(err.message, UNTERMINATED);
+}
+
+#[test]
+fn from_str_trailing_backslash() {
+    let err = tool().get_matches_from_str("tool a\\").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ShellSyntax);
+    assert_eq!(err.context(ContextKind::Column), Some(&ContextValue::Number(7)));
+}
+
+#[test]
+fn from_str_exit_code() {
+    let err = tool()
+        .exit_code(ErrorKind::ShellSyntax, 2)
+        .get_matches_from_str("tool 'a")
+        .unwrap_err();
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn repl_shell_syntax() {
+    let input = "-v 'a b' # comment\n# only a comment\n--opt \"x\n--opt y\n";
+    let res: Vec<_> = tool().repl_from(Cursor::new(input)).collect();
+    assert_eq!(res.len(), 3);
+    assert_eq!(res[0].as_ref().unwrap().value_of("files"), Some("a b"));
+    let err = res[1].as_ref().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ShellSyntax);
+    assert_eq!(err.context(ContextKind::Column), Some(&ContextValue::Number(7)));
+    assert_eq!(res[2].as_ref().unwrap().value_of("opt"), Some("y"));
+}
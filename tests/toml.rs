@@ -0,0 +1,85 @@
+#![cfg(feature="toml")]
+
+#[macro_use]
+extern crate clap;
+
+use clap::{App, AppSettings, Arg, ArgGroup, TomlValue, SubCommand};
+
+// The same definition as app.toml and app.toml
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .version("1.0")
+        .about("does things")
+        .setting(AppSettings::ArgRequiredElseHelp)
+        .arg(Arg::with_name("verbose")
+            .short("v")
+            .long("verbose")
+            .multiple(true)
+            .global(true)
+            .help("be loud"))
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .visible_alias("m0de")
+            .possible_values(&["fast", "slow"])
+            .default_value_if("verbose", None, "slow")
+            .required_if("input", "-"))
+        .arg(Arg::with_name("define")
+            .short("D")
+            .multiple(true)
+            .number_of_values(1)
+            .key_value_delimiter(":")
+            .group("extra"))
+        .arg(Arg::with_name("list").long("list").value_delimiter(";"))
+        .arg(Arg::with_name("input").required(true).index(1))
+        .group(ArgGroup::with_name("extra").arg("list"))
+        .subcommand(SubCommand::with_name("remote")
+            .alias("rem")
+            .arg(Arg::with_name("url").long("url").takes_value(true).required(true)))
+}
+
+#[test]
+fn same_as_builder() {
+    assert_eq!(App::from_toml(load_toml!("app.toml")).to_yaml(), app().to_yaml());
+}
+
+#[test]
+fn parses_like_builder() {
+    let def = load_toml!("app.toml");
+    let m = App::from_toml(def)
+        .get_matches_from(vec!["prog", "x", "-v", "-Da:1"]);
+    assert_eq!(m.value_of("mode"), Some("slow"));
+    assert_eq!(m.values_of_map("define").unwrap()["a"], "1");
+
+    let err = App::from_toml(def)
+        .get_matches_from_safe(vec!["prog", "-"])
+        .unwrap_err();
+    assert_eq!(err.message, app().get_matches_from_safe(vec!["prog", "-"]).unwrap_err().message);
+}
+
+#[test]
+fn nested_tables() {
+    let toml: TomlValue = r#"
+        name = "prog"
+
+        [[subcommands]]
+        [subcommands.remote]
+        args = [{ url = { long = "url", takes_value = true } }]
+        groups = [{ name = "source", args = ["url"], required = true }]
+    "#
+        .parse()
+        .unwrap();
+    let m = App::from_toml(&toml).get_matches_from(vec!["prog", "remote", "--url", "x"]);
+    assert_eq!(m.subcommand_matches("remote").unwrap().value_of("url"), Some("x"));
+}
+
+#[test]
+#[should_panic(expected = "Unknown Arg setting 'shrot' in app definition for arg 'verbose'")]
+fn unknown_key() {
+    let toml: TomlValue = r#"
+        name = "prog"
+        args = [{ verbose = { shrot = "v" } }]
+    "#
+        .parse()
+        .unwrap();
+    App::from_toml(&toml);
+}
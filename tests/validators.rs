@@ -0,0 +1,177 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use std::env;
+
+use clap::{App, Arg, ErrorKind};
+use clap::validators::{self, Check};
+
+static HINTS: &'static str = "prog 0.1
+
+USAGE:
+    prog [OPTIONS] <input: file>
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --port <port: 1-65535>           the port to listen on
+        --size <size: size, even>        the size of the cache
+        --timeout <timeout: duration>    how long to wait
+
+ARGS:
+    <input: file>    the file to read";
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .version("0.1")
+        .arg(Arg::with_name("port")
+            .long("port")
+            .help("the port to listen on")
+            .check(validators::range(1, 65535)))
+        .arg(Arg::with_name("timeout")
+            .long("timeout")
+            .help("how long to wait")
+            .check(validators::duration()))
+        .arg(Arg::with_name("size")
+            .long("size")
+            .help("the size of the cache")
+            .check(validators::byte_size())
+            .check(Check::new(|v| if v == "3" {
+                                  Err(String::from("odd"))
+                              } else {
+                                  Ok(())
+                              })
+                .hint("even")))
+        .arg(Arg::with_name("input")
+            .help("the file to read")
+            .required(true)
+            .check(validators::existing_file()))
+}
+
+fn err(app: App, args: Vec<&str>) -> clap::Error { app.get_matches_from_safe(args).unwrap_err() }
+
+#[test]
+fn hints_in_help() {
+    assert!(test::compare_output(app(), "prog --help", HINTS, false));
+}
+
+#[test]
+fn all_pass() {
+    let m = app().get_matches_from(vec!["prog",
+                                        "--port=443",
+                                        "--timeout=1h",
+                                        "--size=10MiB",
+                                        "Cargo.toml"]);
+    assert_eq!(m.value_of("timeout"), Some("1h"));
+}
+
+#[test]
+fn range() {
+    let e = err(app(), vec!["prog", "--port", "65536", "Cargo.toml"]);
+    assert_eq!(e.kind, ErrorKind::ValueValidation);
+    assert!(e.message.contains("Invalid value for '--port <port: 1-65535>': '65536' isn't \
+                                between 1 and 65535"));
+    let e = err(app(), vec!["prog", "--port", "x", "Cargo.toml"]);
+    assert!(e.message.contains("'x' isn't a number"));
+}
+
+#[test]
+fn negative_float_range() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("temp").check(validators::range(-1.5, 1.5)))
+        .get_matches_from_safe(vec!["prog", "1.25"]);
+    assert!(m.is_ok());
+}
+
+#[test]
+fn existing_file() {
+    let e = err(app(), vec!["prog", "does-not-exist.txt"]);
+    assert_eq!(e.kind, ErrorKind::ValueValidation);
+    assert!(e.message.contains("'does-not-exist.txt' doesn't exist"));
+    let e = err(app(), vec!["prog", "src"]);
+    assert!(e.message.contains("'src' isn't a file"));
+}
+
+#[test]
+fn existing_dir() {
+    let app = || App::new("prog").arg(Arg::with_name("dir").check(validators::existing_dir()));
+    assert!(app().get_matches_from_safe(vec!["prog", "src"]).is_ok());
+    let e = err(app(), vec!["prog", "Cargo.toml"]);
+    assert!(e.message.contains("'Cargo.toml' isn't a directory"));
+}
+
+#[test]
+fn non_empty() {
+    let app = || {
+        App::new("prog").arg(Arg::with_name("name").long("name").check(validators::non_empty()))
+    };
+    assert!(app().get_matches_from_safe(vec!["prog", "--name", "x"]).is_ok());
+    let e = err(app(), vec!["prog", "--name", "  "]);
+    assert_eq!(e.kind, ErrorKind::ValueValidation);
+    assert!(e.message.contains("can't be empty or blank"));
+}
+
+#[test]
+fn duration() {
+    let e = err(app(), vec!["prog", "--timeout", "5 minutes", "Cargo.toml"]);
+    assert_eq!(e.kind, ErrorKind::ValueValidation);
+    assert!(e.message.contains("'5 minutes' isn't a duration such as 30s, 5m or 1h30m"));
+}
+
+#[test]
+fn byte_size_and_custom_check() {
+    let e = err(app(), vec!["prog", "--size", "10 parsecs", "Cargo.toml"]);
+    assert!(e.message.contains("isn't a size"));
+    let e = err(app(), vec!["prog", "--size", "3", "Cargo.toml"]);
+    assert!(e.message.contains("odd"));
+}
+
+#[test]
+fn host_port() {
+    let app = || {
+        App::new("prog").arg(Arg::with_name("addr").long("addr").check(validators::host_port()))
+    };
+    assert!(app().get_matches_from_safe(vec!["prog", "--addr", "[::1]:8080"]).is_ok());
+    let e = err(app(), vec!["prog", "--addr", "localhost"]);
+    assert!(e.message.contains("'localhost' isn't in the form host:port"));
+}
+
+#[test]
+fn checks_env_values() {
+    env::set_var("CLAP_VALIDATORS_TEST_PORT", "0");
+    let e = err(App::new("prog").arg(Arg::with_name("port")
+                    .long("port")
+                    .env("CLAP_VALIDATORS_TEST_PORT")
+                    .check(validators::range(1u16, 65535))),
+                vec!["prog"]);
+    env::remove_var("CLAP_VALIDATORS_TEST_PORT");
+    assert_eq!(e.kind, ErrorKind::ValueValidation);
+}
+
+#[test]
+fn parsed_with_value_parser() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("cache")
+            .long("cache")
+            .value_parser(validators::parse_byte_size)
+            .check(validators::byte_size()))
+        .get_matches_from(vec!["prog", "--cache", "2KiB"]);
+    assert_eq!(m.get::<u64>("cache"), Some(&2048));
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn regex() {
+    let app = || {
+        App::new("prog").arg(Arg::with_name("id")
+            .long("id")
+            .check(validators::regex(regex::Regex::new("^[a-z]{3}[0-9]+$").unwrap())))
+    };
+    assert!(app().get_matches_from_safe(vec!["prog", "--id", "abc123"]).is_ok());
+    let e = err(app(), vec!["prog", "--id", "ab1"]);
+    assert!(e.message.contains("'ab1' doesn't match the pattern '^[a-z]{3}[0-9]+$'"));
+}
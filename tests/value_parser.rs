@@ -0,0 +1,163 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use std::env;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+use clap::{App, AppSettings, Arg, ArgMatches, ErrorKind};
+
+static PARSE_ERROR: &'static str = "error: 'abc' isn't a valid value for '--port <port>'
+\tinvalid digit found in string
+
+USAGE:
+    prog --port <port>
+
+For more information try --help";
+
+#[derive(Debug, PartialEq)]
+enum Level {
+    Low,
+    High,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Level::Low),
+            "high" => Ok(Level::High),
+            _ => Err(String::from("expected 'low' or 'high'")),
+        }
+    }
+}
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .arg(Arg::with_name("port").long("port").value_parser(str::parse::<u16>))
+        .arg(Arg::with_name("level").long("level").value_parser(str::parse::<Level>))
+        .arg(Arg::with_name("addrs").multiple(true).value_parser(str::parse::<Ipv4Addr>))
+}
+
+#[test]
+fn option() {
+    let m = app().get_matches_from(vec!["prog", "--port", "22"]);
+    assert_eq!(m.get::<u16>("port"), Some(&22));
+    assert_eq!(m.value_of("port"), Some("22"));
+}
+
+#[test]
+fn custom_type() {
+    let m = app().get_matches_from(vec!["prog", "--level", "high"]);
+    assert_eq!(m.get::<Level>("level"), Some(&Level::High));
+}
+
+#[test]
+fn multiple_positionals() {
+    let m = app().get_matches_from(vec!["prog", "127.0.0.1", "10.0.0.1"]);
+    assert_eq!(m.get_all::<Ipv4Addr>("addrs"),
+               Some(vec![&Ipv4Addr::new(127, 0, 0, 1), &Ipv4Addr::new(10, 0, 0, 1)]));
+    assert_eq!(m.get::<Ipv4Addr>("addrs"), Some(&Ipv4Addr::new(127, 0, 0, 1)));
+}
+
+#[test]
+fn not_present() {
+    let m = app().get_matches_from(vec!["prog"]);
+    assert_eq!(m.get::<u16>("port"), None);
+    assert_eq!(m.get_all::<Ipv4Addr>("addrs"), None);
+}
+
+#[test]
+fn no_value_parser() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("name").long("name").takes_value(true))
+        .get_matches_from(vec!["prog", "--name", "x"]);
+    assert_eq!(m.get::<String>("name"), None);
+}
+
+#[test]
+#[should_panic]
+fn wrong_type() {
+    let m = app().get_matches_from(vec!["prog", "--port", "22"]);
+    m.get::<u32>("port");
+}
+
+#[test]
+fn parse_error() {
+    assert!(test::compare_output(app(), "prog --port abc", PARSE_ERROR, true));
+}
+
+#[test]
+fn parse_error_kind_and_info() {
+    let err = app().get_matches_from_safe(vec!["prog", "--level", "medium"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert!(err.message.contains("expected 'low' or 'high'"));
+    assert_eq!(err.info, Some(vec!["level".to_owned(), "medium".to_owned()]));
+}
+
+#[test]
+fn default_value_is_parsed() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("jobs")
+            .long("jobs")
+            .default_value("4")
+            .value_parser(str::parse::<usize>))
+        .get_matches_from(vec!["prog"]);
+    assert_eq!(m.get::<usize>("jobs"), Some(&4));
+}
+
+#[test]
+fn env_value_is_parsed() {
+    env::set_var("CLAP_VALUE_PARSER_TEST_JOBS", "nope");
+    let res = App::new("prog")
+        .arg(Arg::with_name("jobs")
+            .long("jobs")
+            .env("CLAP_VALUE_PARSER_TEST_JOBS")
+            .value_parser(str::parse::<usize>))
+        .get_matches_from_safe(vec!["prog"]);
+    env::remove_var("CLAP_VALUE_PARSER_TEST_JOBS");
+    assert_eq!(res.unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[test]
+fn runs_after_validator() {
+    let res = App::new("prog")
+        .arg(Arg::with_name("port")
+            .long("port")
+            .validator(|v| if v == "0" { Err("port can't be 0".into()) } else { Ok(()) })
+            .value_parser(str::parse::<u16>))
+        .get_matches_from_safe(vec!["prog", "--port", "0"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::ValueValidation);
+}
+
+#[test]
+fn subcommand() {
+    let m = App::new("prog")
+        .subcommand(clap::SubCommand::with_name("sub")
+            .arg(Arg::with_name("n").value_parser(str::parse::<i64>)))
+        .get_matches_from(vec!["prog", "sub", "5"]);
+    assert_eq!(m.subcommand_matches("sub").unwrap().get::<i64>("n"), Some(&5));
+}
+
+#[test]
+fn global_in_subcommand() {
+    let m = App::new("prog")
+        .setting(AppSettings::PropagateGlobalValuesDown)
+        .arg(Arg::with_name("jobs")
+            .long("jobs")
+            .value_parser(str::parse::<u32>)
+            .global(true))
+        .subcommand(clap::SubCommand::with_name("build"))
+        .get_matches_from(vec!["prog", "--jobs", "4", "build"]);
+    assert_eq!(m.subcommand_matches("build").unwrap().get::<u32>("jobs"), Some(&4));
+}
+
+#[test]
+fn matches_are_send_and_sync() {
+    fn check<T: Send + Sync>(_: &T) {}
+    let m: ArgMatches = app().get_matches_from(vec!["prog", "--port", "1"]);
+    check(&m);
+}
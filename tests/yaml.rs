@@ -3,7 +3,7 @@
 #[macro_use]
 extern crate clap;
 
-use clap::App;
+use clap::{App, YamlLoader};
 
 #[test]
 fn create_app_from_yaml() {
@@ -38,3 +38,31 @@ fn author() {
     assert!(help_string.contains(
         "Kevin K. <kbknapp@gmail.com>"));
 }
+
+#[test]
+fn default_value_if_null_and_string() {
+    let docs = YamlLoader::load_from_str("
+name: prog
+args:
+  - flag:
+      long: flag
+  - opt:
+      long: opt
+      takes_value: true
+  - out:
+      long: out
+      takes_value: true
+      default_value_if:
+        - [flag, null, on]
+        - [opt, fast, quick]
+        - [opt, Null, none]
+").unwrap();
+    let out = |args: Vec<&str>| {
+        App::from_yaml(&docs[0]).get_matches_from(args).value_of("out").map(|s| s.to_owned())
+    };
+    assert_eq!(out(vec!["prog", "--flag"]), Some("on".to_owned()));
+    assert_eq!(out(vec!["prog", "--opt", "fast"]), Some("quick".to_owned()));
+    assert_eq!(out(vec!["prog", "--opt", "slow"]), None);
+    // Only `null` and `~` are YAML nulls, `Null` is a string
+    assert_eq!(out(vec!["prog", "--opt", "Null"]), Some("none".to_owned()));
+}